@@ -0,0 +1,225 @@
+use hdk::holochain_core_types::{
+    chain_header::ChainHeader, link::link_data::LinkData, time::Timeout,
+};
+use hdk::holochain_wasm_utils::api_serialization::get_entry::{
+    GetEntryOptions, GetEntryResultType, StatusRequestKind,
+};
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::aggregate::MAX_CONTENT_DURATION;
+use crate::course;
+use crate::section::anchor::SectionAnchor;
+
+pub const SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK: &str = "section_anchor->section_activity";
+
+// Public counters of one student's activity in a section. Student publishes them once
+// and updates them whenever a learning event is recorded, see analytics::handlers.
+// Instead of the student's address they're identified by a pseudonym: the address of their first
+// version, which only the student who committed it can update.
+// NOTE: like with every public entry, the author can still be found from it's header in the DHT.
+// Pseudonymity means that the student's address isn't a part of counters or links to them
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct SectionActivity {
+    pub section_anchor_address: Address,
+    pub course_anchor_address: Address,
+    // it's only Some if student consented to share their identity with the teacher
+    pub student_address: Option<Address>,
+    // it's false if student has disabled tracking: then counters are empty and student is left out
+    pub tracking: bool,
+    // numbers of different contents and quizzes
+    pub opened_count: u32,
+    pub completed_count: u32,
+    pub quizzes_started: u32,
+    pub quizzes_submitted: u32,
+    // seconds between opening and completing content, one value per completion
+    pub content_durations: Vec<u64>,
+    pub timestamp: u64,
+}
+
+impl HolochainEntry for SectionActivity {
+    fn entry_type() -> String {
+        String::from("section_activity")
+    }
+}
+
+// checks that counters are consistent with each other and belong to a section of the course
+fn validate_activity(activity: &SectionActivity, sources: &[Address]) -> Result<(), String> {
+    if let Some(student_address) = &activity.student_address {
+        if !sources.contains(student_address) {
+            return Err("Only the student can share their identity".to_owned());
+        }
+    }
+    if activity.completed_count > activity.opened_count {
+        return Err("Student can't complete content they haven't opened".to_owned());
+    }
+    if activity
+        .content_durations
+        .iter()
+        .any(|duration| *duration > MAX_CONTENT_DURATION)
+    {
+        return Err(format!(
+            "Content duration can't be longer than {} seconds",
+            MAX_CONTENT_DURATION
+        ));
+    }
+    if !activity.tracking
+        && (activity.opened_count > 0
+            || activity.quizzes_started > 0
+            || activity.quizzes_submitted > 0
+            || activity.student_address.is_some())
+    {
+        return Err("Counters can't be shared when tracking is disabled".to_owned());
+    }
+    let section_anchor: SectionAnchor =
+        hdk::utils::get_as_type(activity.section_anchor_address.clone())
+            .map_err(|_| "Can't find the section of this activity".to_owned())?;
+    if section_anchor.course_anchor_address != activity.course_anchor_address {
+        return Err("Section doesn't belong to the course of this activity".to_owned());
+    }
+    Ok(())
+}
+
+// student can only update their own counters and can't move them to another section
+fn validate_update(
+    new_activity: &SectionActivity,
+    old_activity: &SectionActivity,
+    old_activity_header: &ChainHeader,
+    sources: &[Address],
+) -> Result<(), String> {
+    let is_author = old_activity_header
+        .provenances()
+        .iter()
+        .any(|provenance| sources.contains(&provenance.source()));
+    if !is_author {
+        return Err("Students can only update their own section activity".to_owned());
+    }
+    if new_activity.section_anchor_address != old_activity.section_anchor_address
+        || new_activity.course_anchor_address != old_activity.course_anchor_address
+    {
+        return Err("Section activity can't be moved to another section".to_owned());
+    }
+    validate_activity(new_activity, sources)
+}
+
+// agent that committed the first version of the activity at activity_address
+fn get_author(activity_address: &Address) -> Result<Address, String> {
+    let options = GetEntryOptions::new(StatusRequestKind::Initial, false, true, Timeout::default());
+    let item = match hdk::get_entry_result(activity_address, options) {
+        Ok(get_entry_result) => match get_entry_result.result {
+            GetEntryResultType::Single(item) => item,
+            GetEntryResultType::All(history) => history
+                .items
+                .into_iter()
+                .next()
+                .ok_or_else(|| "Can't find the section activity".to_owned())?,
+        },
+        Err(_) => return Err("Can't find the section activity".to_owned()),
+    };
+    item.headers
+        .first()
+        .and_then(|header| header.provenances().first())
+        .map(|provenance| provenance.source())
+        .ok_or_else(|| "Can't find the author of the section activity".to_owned())
+}
+
+// section links to the first version of every student's activity. Student links their own
+// activity, once per section, and only if they're enrolled in the course
+fn validate_activity_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let activity_address = link_data.link.target();
+    let activity: SectionActivity = hdk::utils::get_as_type(activity_address.clone())
+        .map_err(|_| "Can't find the linked section activity".to_owned())?;
+    if *link_data.link.base() != activity.section_anchor_address {
+        return Err("Section activity can only be linked from it's section".to_owned());
+    }
+    let author_address = get_author(activity_address)?;
+    if !sources.contains(&author_address) {
+        return Err("Students can only publish their own section activity".to_owned());
+    }
+    match course::handlers::is_enrolled(&activity.course_anchor_address, &author_address) {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err(
+                "Only students enrolled in the course can publish their activity".to_owned(),
+            )
+        }
+        Err(_) => return Err("Can't check if student is enrolled in the course".to_owned()),
+    }
+
+    let linked_addresses = hdk::get_links(
+        link_data.link.base(),
+        LinkMatch::Exactly(SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK),
+        LinkMatch::Any,
+    )
+    .map_err(|_| "Can't get activities of the section".to_owned())?
+    .addresses();
+    for linked_address in linked_addresses {
+        if linked_address != *activity_address && get_author(&linked_address)? == author_address {
+            return Err(
+                "Student can only publish one section activity per section, update it instead"
+                    .to_owned(),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_activity_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_activity_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => {
+            if !validation_data
+                .sources()
+                .contains(&get_author(link.link.target())?)
+            {
+                return Err("Students can only unpublish their own section activity".to_owned());
+            }
+            Ok(())
+        }
+    }
+}
+
+// Holochain entry definition for SectionActivity
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: SectionActivity::entry_type(),
+        description: "this is the definition of anonymized counters of a student's activity in a section",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<SectionActivity>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_activity(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, old_entry_header, validation_data } => {
+                    validate_update(&new_entry, &old_entry, &old_entry_header, &validation_data.sources())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Err("Section activity can't be deleted, disable tracking instead".to_owned())
+                }
+            }
+        },
+        links: [
+            // link from the section this activity belongs to. It always points to the first version
+            // of the activity, which is the student's pseudonym in this section
+            from!(
+                SectionAnchor::entry_type(),
+                link_type: SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_activity_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,112 @@
+use super::event::{LearningEvent, LearningEventKind};
+use hdk::holochain_persistence_api::cas::content::Address;
+
+// content that stayed open longer than this is counted as if it took this long (in seconds)
+pub const MAX_CONTENT_DURATION: u64 = 7 * 24 * 60 * 60;
+// histogram can't be split into more buckets than this, so a small bucket size can't make it huge
+pub const MAX_HISTOGRAM_BUCKETS: u64 = 1000;
+
+// counters of a student's activity in a section, computed from their learning events
+pub struct ActivityCounters {
+    pub opened_count: u32,
+    pub completed_count: u32,
+    pub quizzes_started: u32,
+    pub quizzes_submitted: u32,
+    pub content_durations: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistogramBucket {
+    // in seconds, from is inclusive and to is exclusive
+    pub from: u64,
+    pub to: u64,
+    pub count: u32,
+}
+
+fn push_unique(addresses: &mut Vec<Address>, address: &Address) {
+    if !addresses.contains(address) {
+        addresses.push(address.clone());
+    }
+}
+
+// events are expected to be ordered by their timestamp
+pub fn count_activity(events: &[LearningEvent]) -> ActivityCounters {
+    let mut opened_contents = Vec::new();
+    let mut completed_contents = Vec::new();
+    let mut started_quizzes = Vec::new();
+    let mut submitted_quizzes = Vec::new();
+    // moment every content was last opened at, so we know how long it took to complete it
+    let mut opened_at: Vec<(Address, u64)> = Vec::new();
+    let mut content_durations = Vec::new();
+
+    for event in events.iter() {
+        match &event.kind {
+            LearningEventKind::ContentOpened { content_address } => {
+                push_unique(&mut opened_contents, content_address);
+                opened_at.retain(|(address, _timestamp)| address != content_address);
+                opened_at.push((content_address.clone(), event.timestamp));
+            }
+            LearningEventKind::ContentCompleted { content_address } => {
+                // completing content that wasn't opened first doesn't count
+                if let Some((_address, timestamp)) = opened_at
+                    .iter()
+                    .find(|(address, _timestamp)| address == content_address)
+                {
+                    push_unique(&mut completed_contents, content_address);
+                    let duration = event.timestamp.saturating_sub(*timestamp);
+                    content_durations.push(std::cmp::min(duration, MAX_CONTENT_DURATION));
+                }
+            }
+            LearningEventKind::QuizStarted { quiz_address } => {
+                push_unique(&mut started_quizzes, quiz_address);
+            }
+            LearningEventKind::QuizSubmitted { quiz_address } => {
+                push_unique(&mut submitted_quizzes, quiz_address);
+            }
+        }
+    }
+
+    ActivityCounters {
+        opened_count: opened_contents.len() as u32,
+        completed_count: completed_contents.len() as u32,
+        quizzes_started: started_quizzes.len() as u32,
+        quizzes_submitted: submitted_quizzes.len() as u32,
+        content_durations: content_durations,
+    }
+}
+
+// splits durations into buckets of bucket_size seconds, from 0 up to the longest duration.
+// Empty buckets in between are kept so histogram can be drawn as is.
+// Fails if that would take more than MAX_HISTOGRAM_BUCKETS buckets
+pub fn build_histogram(
+    durations: &[u64],
+    bucket_size: u64,
+) -> Result<Vec<HistogramBucket>, String> {
+    if bucket_size == 0 {
+        return Err("Bucket size should be at least one second".to_owned());
+    }
+    let longest_duration = match durations.iter().max() {
+        Some(longest_duration) => *longest_duration,
+        None => return Ok(Vec::new()),
+    };
+
+    let bucket_count = longest_duration / bucket_size + 1;
+    if bucket_count > MAX_HISTOGRAM_BUCKETS {
+        return Err(format!(
+            "Histogram can't have more than {} buckets, use a bigger bucket size",
+            MAX_HISTOGRAM_BUCKETS
+        ));
+    }
+    let mut histogram: Vec<HistogramBucket> = (0..bucket_count)
+        .map(|index| HistogramBucket {
+            from: index.saturating_mul(bucket_size),
+            to: (index + 1).saturating_mul(bucket_size),
+            count: 0,
+        })
+        .collect();
+    for duration in durations.iter() {
+        let bucket = &mut histogram[(duration / bucket_size) as usize];
+        bucket.count = bucket.count.saturating_add(1);
+    }
+    Ok(histogram)
+}
@@ -0,0 +1,59 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+// Private entry with the student's analytics choices for a course.
+// A new one is committed every time student changes them and the latest one wins
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct AnalyticsConsent {
+    pub course_anchor_address: Address,
+    // if it's false, no learning events are recorded for this course
+    pub tracking: bool,
+    // if it's true, teacher can see the student's address next to their section activity
+    pub share_identity: bool,
+    pub timestamp: u64,
+}
+
+impl AnalyticsConsent {
+    pub fn new(
+        course_anchor_address: Address,
+        tracking: bool,
+        share_identity: bool,
+        timestamp: u64,
+    ) -> Self {
+        AnalyticsConsent {
+            course_anchor_address: course_anchor_address,
+            tracking: tracking,
+            share_identity: share_identity,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for AnalyticsConsent {
+    fn entry_type() -> String {
+        String::from("analytics_consent")
+    }
+}
+
+// Holochain entry definition for AnalyticsConsent
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: AnalyticsConsent::entry_type(),
+        description: "this is the definition of a private analytics consent of a student",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<AnalyticsConsent>| {
+            match validation_data {
+                EntryValidationData::Create { entry, .. } => {
+                    if entry.share_identity && !entry.tracking {
+                        return Err("Identity can only be shared when tracking is enabled".to_owned());
+                    }
+                    Ok(())
+                },
+                _ => Ok(())
+            }
+        }
+    )
+}
@@ -0,0 +1,58 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum LearningEventKind {
+    ContentOpened { content_address: Address },
+    ContentCompleted { content_address: Address },
+    QuizStarted { quiz_address: Address },
+    QuizSubmitted { quiz_address: Address },
+}
+
+// Private entry with something student did in a section. Events are only recorded
+// if student has opted in (see analytics::consent) and never leave the student's source chain.
+// Teachers only see aggregated counters, see analytics::activity
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct LearningEvent {
+    pub kind: LearningEventKind,
+    pub section_anchor_address: Address,
+    pub course_anchor_address: Address,
+    pub timestamp: u64,
+}
+
+impl LearningEvent {
+    pub fn new(
+        kind: LearningEventKind,
+        section_anchor_address: Address,
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        LearningEvent {
+            kind: kind,
+            section_anchor_address: section_anchor_address,
+            course_anchor_address: course_anchor_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for LearningEvent {
+    fn entry_type() -> String {
+        String::from("learning_event")
+    }
+}
+
+// Holochain entry definition for LearningEvent
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: LearningEvent::entry_type(),
+        description: "this is the definition of a private learning event of a student",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | _validation_data: hdk::EntryValidationData<LearningEvent>| {
+            Ok(())
+        }
+    )
+}
@@ -0,0 +1,278 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::activity::{SectionActivity, SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK};
+use super::aggregate::{self, HistogramBucket};
+use super::consent::AnalyticsConsent;
+use super::event::{LearningEvent, LearningEventKind};
+use crate::content;
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+use crate::helper;
+use crate::section;
+use crate::section::anchor::SectionAnchor;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct SectionDropOff {
+    pub section_anchor_address: Address,
+    pub title: String,
+    // students who opened at least one content of this section
+    pub reached_count: usize,
+    // students who completed every content of this section
+    pub completed_count: usize,
+    // students who reached this section but not the next one. It's None for the last section
+    pub dropped_count: Option<usize>,
+}
+
+pub fn get_my_consent(course_anchor_address: &Address) -> ZomeApiResult<Option<AnalyticsConsent>> {
+    Ok(helper::query_my_entries::<AnalyticsConsent>()?
+        .into_iter()
+        .map(|(consent, _address)| consent)
+        .filter(|consent| consent.course_anchor_address == *course_anchor_address)
+        .max_by_key(|consent| consent.timestamp))
+}
+
+// learning events of the agent that calls this function in the section, ordered by timestamp
+fn get_my_section_events(
+    section_anchor_address: &Address,
+) -> ZomeApiResult<Vec<(LearningEvent, Address)>> {
+    let mut events: Vec<(LearningEvent, Address)> = helper::query_my_entries::<LearningEvent>()?
+        .into_iter()
+        .filter(|(event, _address)| event.section_anchor_address == *section_anchor_address)
+        .collect();
+    events.sort_by_key(|(event, _address)| event.timestamp);
+    Ok(events)
+}
+
+// sections of the course that agent that calls this function has learning events in
+fn get_my_course_sections(course_anchor_address: &Address) -> ZomeApiResult<Vec<Address>> {
+    let mut section_anchor_addresses = Vec::new();
+    for (event, _address) in helper::query_my_entries::<LearningEvent>()? {
+        if event.course_anchor_address == *course_anchor_address
+            && !section_anchor_addresses.contains(&event.section_anchor_address)
+        {
+            section_anchor_addresses.push(event.section_anchor_address);
+        }
+    }
+    Ok(section_anchor_addresses)
+}
+
+// first version of the counters that agent that calls this function has published for the section
+fn get_my_activity_address(section_anchor_address: &Address) -> ZomeApiResult<Option<Address>> {
+    let linked_addresses = hdk::get_links(
+        section_anchor_address,
+        LinkMatch::Exactly(SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+    Ok(helper::query_my_entries::<SectionActivity>()?
+        .into_iter()
+        .map(|(_activity, activity_address)| activity_address)
+        .find(|activity_address| linked_addresses.contains(activity_address)))
+}
+
+// recounts learning events of the agent that calls this function in the section and publishes
+// the counters, or updates the ones that were published before. If tracking is disabled,
+// counters are emptied so the teacher leaves this student out
+fn publish_section_activity(
+    section_anchor_address: &Address,
+    consent: &AnalyticsConsent,
+    timestamp: u64,
+) -> ZomeApiResult<()> {
+    let events: Vec<LearningEvent> = get_my_section_events(section_anchor_address)?
+        .into_iter()
+        .map(|(event, _address)| event)
+        .collect();
+    if events.is_empty() {
+        return Ok(());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(consent.course_anchor_address.clone())?;
+    if course_anchor.teacher_address == *AGENT_ADDRESS {
+        return Ok(());
+    }
+
+    let counters = if consent.tracking {
+        aggregate::count_activity(&events)
+    } else {
+        aggregate::count_activity(&[])
+    };
+    let student_address = if consent.tracking && consent.share_identity {
+        Some(AGENT_ADDRESS.clone())
+    } else {
+        None
+    };
+    let activity = SectionActivity {
+        section_anchor_address: section_anchor_address.clone(),
+        course_anchor_address: consent.course_anchor_address.clone(),
+        student_address: student_address,
+        tracking: consent.tracking,
+        opened_count: counters.opened_count,
+        completed_count: counters.completed_count,
+        quizzes_started: counters.quizzes_started,
+        quizzes_submitted: counters.quizzes_submitted,
+        content_durations: counters.content_durations,
+        timestamp: timestamp,
+    };
+
+    match get_my_activity_address(section_anchor_address)? {
+        Some(activity_address) => {
+            // activity is referenced by the address of it's first version, but we have to update the latest one
+            let previous_activity: SectionActivity = hdk::utils::get_as_type(activity_address)?;
+            hdk::update_entry(activity.entry(), &previous_activity.address()?)?;
+        }
+        None => {
+            let activity_address = hdk::commit_entry(&activity.entry())?;
+            hdk::link_entries(
+                section_anchor_address,
+                &activity_address,
+                SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK,
+                "",
+            )?;
+        }
+    }
+    Ok(())
+}
+
+// changes analytics choices of the agent that calls this function for the course
+// and republishes their counters of every section (empty ones, if tracking is disabled)
+pub fn set_consent(
+    course_anchor_address: Address,
+    tracking: bool,
+    share_identity: bool,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let consent = AnalyticsConsent::new(
+        course_anchor_address.clone(),
+        tracking,
+        share_identity,
+        timestamp,
+    );
+    let consent_address = hdk::commit_entry(&consent.entry())?;
+
+    for section_anchor_address in get_my_course_sections(&course_anchor_address)? {
+        publish_section_activity(&section_anchor_address, &consent, timestamp)?;
+    }
+
+    Ok(consent_address)
+}
+
+// records learning event of the agent that calls this function.
+// Nothing is recorded and None is returned unless student has enabled tracking for the course
+pub fn record_event(
+    kind: LearningEventKind,
+    section_anchor_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<Option<Address>> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    let consent = match get_my_consent(&section_anchor.course_anchor_address)? {
+        Some(consent) if consent.tracking => consent,
+        _ => return Ok(None),
+    };
+
+    let event = LearningEvent::new(
+        kind,
+        section_anchor_address.clone(),
+        section_anchor.course_anchor_address,
+        timestamp,
+    );
+    let event_address = hdk::commit_entry(&event.entry())?;
+    publish_section_activity(&section_anchor_address, &consent, timestamp)?;
+
+    Ok(Some(event_address))
+}
+
+// published counters of every student in the section. Students who've disabled tracking are left out
+fn get_activities(section_anchor_address: &Address) -> ZomeApiResult<Vec<SectionActivity>> {
+    let mut activities = Vec::new();
+    for activity_address in hdk::get_links(
+        section_anchor_address,
+        LinkMatch::Exactly(SECTION_ANCHOR_TO_SECTION_ACTIVITY_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses()
+    {
+        let activity: SectionActivity = hdk::utils::get_as_type(activity_address)?;
+        if activity.tracking {
+            activities.push(activity);
+        }
+    }
+    Ok(activities)
+}
+
+fn ensure_section_teacher(section_anchor_address: &Address) -> ZomeApiResult<()> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    course::handlers::ensure_teacher(&section_anchor.course_anchor_address)?;
+    Ok(())
+}
+
+// returns published counters of all students in the section.
+// student_address is only filled in for students who've consented to share their identity
+pub fn get_section_activity(
+    section_anchor_address: Address,
+) -> ZomeApiResult<Vec<SectionActivity>> {
+    ensure_section_teacher(&section_anchor_address)?;
+    get_activities(&section_anchor_address)
+}
+
+// shows how many students reach and complete every section of the course, in the course's order
+pub fn get_drop_off(course_anchor_address: Address) -> ZomeApiResult<Vec<SectionDropOff>> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Err(ZomeApiError::from("Can't find this course".to_owned())),
+    };
+
+    let mut drop_off = Vec::new();
+    for section_anchor_address in course.sections {
+        let title = match section::handlers::get_latest_section(&section_anchor_address)? {
+            Some((section, _section_address)) => section.title,
+            // skip sections that were deleted
+            None => continue,
+        };
+        let content_count = content::handlers::get_contents(&section_anchor_address)?.len() as u32;
+        let activities = get_activities(&section_anchor_address)?;
+        drop_off.push(SectionDropOff {
+            reached_count: activities
+                .iter()
+                .filter(|activity| activity.opened_count > 0)
+                .count(),
+            completed_count: activities
+                .iter()
+                .filter(|activity| content_count > 0 && activity.completed_count >= content_count)
+                .count(),
+            dropped_count: None,
+            section_anchor_address: section_anchor_address,
+            title: title,
+        });
+    }
+    // pseudonyms are different in every section, so we can only compare the numbers of students
+    let reached_counts: Vec<usize> = drop_off
+        .iter()
+        .map(|section_drop_off| section_drop_off.reached_count)
+        .collect();
+    for (section_drop_off, next_reached_count) in
+        drop_off.iter_mut().zip(reached_counts.iter().skip(1))
+    {
+        section_drop_off.dropped_count = Some(
+            section_drop_off
+                .reached_count
+                .saturating_sub(*next_reached_count),
+        );
+    }
+    Ok(drop_off)
+}
+
+// histogram of how long it took students to complete contents of the section
+pub fn get_time_on_content_histogram(
+    section_anchor_address: Address,
+    bucket_size: u64,
+) -> ZomeApiResult<Vec<HistogramBucket>> {
+    ensure_section_teacher(&section_anchor_address)?;
+    let durations: Vec<u64> = get_activities(&section_anchor_address)?
+        .into_iter()
+        .flat_map(|activity| activity.content_durations)
+        .collect();
+    aggregate::build_histogram(&durations, bucket_size).map_err(ZomeApiError::from)
+}
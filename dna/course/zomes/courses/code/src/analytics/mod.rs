@@ -0,0 +1,5 @@
+pub mod activity;
+pub mod aggregate;
+pub mod consent;
+pub mod event;
+pub mod handlers;
@@ -0,0 +1,92 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_ANNOUNCEMENT_LINK: &str = "course_anchor->announcement";
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Announcement {
+    pub title: String,
+    pub body: String,
+    pub timestamp: u64,
+    pub author_address: Address,
+    pub course_anchor_address: Address,
+}
+
+impl Announcement {
+    pub fn new(
+        title: String,
+        body: String,
+        timestamp: u64,
+        author_address: Address,
+        course_anchor_address: Address,
+    ) -> Self {
+        Announcement {
+            title: title,
+            body: body,
+            timestamp: timestamp,
+            author_address: author_address,
+            course_anchor_address: course_anchor_address,
+        }
+    }
+}
+
+impl HolochainEntry for Announcement {
+    fn entry_type() -> String {
+        String::from("announcement")
+    }
+}
+
+// announcements are posted by the teacher of the course they're posted to
+fn validate_author(announcement: &Announcement, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&announcement.author_address) {
+        return Err("Announcement can only be posted by it's author".to_owned());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(announcement.course_anchor_address.clone())
+            .map_err(|_| "Can't find the course of this announcement".to_owned())?;
+    if announcement.author_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can post announcements to it".to_owned());
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Announcement
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Announcement::entry_type(),
+        description: "this is the definition of an announcement that teacher sends to course students",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Announcement>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_author(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Announcements can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course this announcement was posted to
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_ANNOUNCEMENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,93 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{Announcement, COURSE_ANCHOR_TO_ANNOUNCEMENT_LINK};
+use super::read_marker::AnnouncementReadMarker;
+use crate::course;
+use crate::helper;
+use crate::signal;
+use crate::signal::handlers::LeapSignal;
+
+pub fn post(
+    course_anchor_address: Address,
+    title: String,
+    body: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let new_announcement = Announcement::new(
+        title,
+        body,
+        timestamp,
+        AGENT_ADDRESS.clone(),
+        course_anchor_address.clone(),
+    );
+    let new_announcement_address = hdk::commit_entry(&new_announcement.entry())?;
+    hdk::link_entries(
+        &course_anchor_address,
+        &new_announcement_address,
+        COURSE_ANCHOR_TO_ANNOUNCEMENT_LINK,
+        "",
+    )?;
+
+    // push announcement to students that are online right now
+    signal::handlers::notify_course_students(
+        &course_anchor_address,
+        &LeapSignal::AnnouncementPosted {
+            course_anchor_address: course_anchor_address.clone(),
+            announcement_address: new_announcement_address.clone(),
+        },
+    )?;
+
+    Ok(new_announcement_address)
+}
+
+// returns announcements of the course posted at or after the since timestamp, oldest first
+pub fn list(course_anchor_address: &Address, since: u64) -> ZomeApiResult<Vec<Announcement>> {
+    let announcement_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_ANNOUNCEMENT_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut announcements = Vec::new();
+    for announcement_address in announcement_addresses {
+        let announcement: Announcement = hdk::utils::get_as_type(announcement_address)?;
+        if announcement.timestamp >= since {
+            announcements.push(announcement);
+        }
+    }
+    announcements.sort_by_key(|announcement| announcement.timestamp);
+
+    Ok(announcements)
+}
+
+// timestamp up to which agent that calls this function has read announcements of the course
+fn get_read_until(course_anchor_address: &Address) -> ZomeApiResult<Option<u64>> {
+    let read_markers = helper::query_my_entries::<AnnouncementReadMarker>()?;
+    Ok(read_markers
+        .into_iter()
+        .filter(|(read_marker, _address)| {
+            &read_marker.course_anchor_address == course_anchor_address
+        })
+        .map(|(read_marker, _address)| read_marker.read_until)
+        .max())
+}
+
+pub fn get_unread(course_anchor_address: &Address) -> ZomeApiResult<Vec<Announcement>> {
+    match get_read_until(course_anchor_address)? {
+        // list returns announcements posted at or after the timestamp, so we're moving it by one
+        // to skip the announcement that was read last
+        Some(read_until) => list(course_anchor_address, read_until + 1),
+        None => list(course_anchor_address, 0),
+    }
+}
+
+// marks all announcements of the course posted up to read_until as read
+pub fn mark_read(course_anchor_address: Address, read_until: u64) -> ZomeApiResult<Address> {
+    let read_marker = AnnouncementReadMarker::new(course_anchor_address, read_until);
+    hdk::commit_entry(&read_marker.entry())
+}
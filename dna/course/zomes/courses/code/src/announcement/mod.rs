@@ -0,0 +1,3 @@
+pub mod entry;
+pub mod handlers;
+pub mod read_marker;
@@ -0,0 +1,40 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+// Private entry that records up to which moment student has read announcements of a course.
+// A new marker is committed every time student reads announcements and the latest one wins
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct AnnouncementReadMarker {
+    pub course_anchor_address: Address,
+    pub read_until: u64,
+}
+
+impl AnnouncementReadMarker {
+    pub fn new(course_anchor_address: Address, read_until: u64) -> Self {
+        AnnouncementReadMarker {
+            course_anchor_address: course_anchor_address,
+            read_until: read_until,
+        }
+    }
+}
+
+impl HolochainEntry for AnnouncementReadMarker {
+    fn entry_type() -> String {
+        String::from("announcement_read_marker")
+    }
+}
+
+// Holochain entry definition for AnnouncementReadMarker
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: AnnouncementReadMarker::entry_type(),
+        description: "this is the definition of a private marker of read announcements",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | _validation_data: hdk::EntryValidationData<AnnouncementReadMarker>| {
+            Ok(())
+        }
+    )
+}
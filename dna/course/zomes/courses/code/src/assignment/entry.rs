@@ -0,0 +1,122 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+use crate::rubric::entry::Rubric;
+
+pub const COURSE_ANCHOR_TO_ASSIGNMENT_LINK: &str = "course_anchor->assignment";
+
+// Task that students of a course submit their work for.
+// Assignments are referenced by the address of their first version
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Assignment {
+    pub title: String,
+    pub description: String,
+    pub due_date: u64,
+    // the highest score for the assignment if it's graded without a rubric
+    pub max_points: u32,
+    // rubric that teacher grades this assignment by. If it's set, max_points is taken from the rubric
+    pub rubric_address: Option<Address>,
+    // how many submissions of other students every student reviews after the due date.
+    // 0 means there's no peer review for this assignment
+    #[serde(default)]
+    pub peer_review_count: u32,
+    pub course_anchor_address: Address,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Assignment {
+    pub fn new(
+        title: String,
+        description: String,
+        due_date: u64,
+        max_points: u32,
+        course_anchor_address: Address,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Assignment {
+            title: title,
+            description: description,
+            due_date: due_date,
+            max_points: max_points,
+            rubric_address: None,
+            peer_review_count: 0,
+            course_anchor_address: course_anchor_address,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Assignment {
+    fn entry_type() -> String {
+        String::from("assignment")
+    }
+}
+
+// checks that assignment is authored by the teacher of it's course and is graded by that teacher's rubric
+fn validate_assignment(assignment: &Assignment, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&assignment.teacher_address) {
+        return Err("Assignment can only be committed by it's teacher".to_owned());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(assignment.course_anchor_address.clone())
+            .map_err(|_| "Can't find the course of this assignment".to_owned())?;
+    if assignment.teacher_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can create assignments in it".to_owned());
+    }
+    if let Some(rubric_address) = &assignment.rubric_address {
+        let rubric: Rubric = hdk::utils::get_as_type(rubric_address.clone())
+            .map_err(|_| "Can't find the rubric of this assignment".to_owned())?;
+        if rubric.teacher_address != assignment.teacher_address {
+            return Err("Assignment can only be graded by a rubric of it's teacher".to_owned());
+        }
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Assignment
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Assignment::entry_type(),
+        description: "this is the definition of an assignment of a course",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Assignment>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_assignment(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    if new_entry.course_anchor_address != old_entry.course_anchor_address
+                        || new_entry.teacher_address != old_entry.teacher_address
+                    {
+                        return Err("Can't move an assignment to another course or teacher".to_owned());
+                    }
+                    validate_assignment(&new_entry, &validation_data.sources())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course this assignment belongs to
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_ASSIGNMENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
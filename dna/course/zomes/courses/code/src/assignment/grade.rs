@@ -0,0 +1,128 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::Assignment;
+use super::submission::Submission;
+use crate::rubric::entry::Rubric;
+
+pub const SUBMISSION_TO_GRADE_LINK: &str = "submission->grade";
+
+// Teacher's grade of a submission
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Grade {
+    pub submission_address: Address,
+    pub assignment_address: Address,
+    pub student_address: Address,
+    pub grader_address: Address,
+    // rubric the submission was graded by. It's None for point-only grades
+    pub rubric_address: Option<Address>,
+    // index of the selected level for every criterion of the rubric
+    pub selected_levels: Vec<usize>,
+    pub points: u32,
+    pub max_points: u32,
+    pub feedback: String,
+    pub timestamp: u64,
+}
+
+impl Grade {
+    // percentage of max_points the student has got
+    pub fn score(&self) -> f64 {
+        if self.max_points == 0 {
+            return 0.0;
+        }
+        self.points as f64 * 100.0 / self.max_points as f64
+    }
+}
+
+impl HolochainEntry for Grade {
+    fn entry_type() -> String {
+        String::from("grade")
+    }
+}
+
+// checks that points of the grade match the rubric it was given by
+fn validate_points(grade: &Grade) -> Result<(), String> {
+    match &grade.rubric_address {
+        Some(rubric_address) => {
+            let rubric: Rubric = hdk::utils::get_as_type(rubric_address.clone())
+                .map_err(|_| "Can't find the rubric of this grade".to_owned())?;
+            if grade.points != rubric.get_points(&grade.selected_levels)? {
+                return Err("Points of the grade don't match the selected rubric levels".to_owned());
+            }
+            if grade.max_points != rubric.max_points()? {
+                return Err("Max points of the grade don't match the rubric".to_owned());
+            }
+        }
+        None => {
+            if !grade.selected_levels.is_empty() {
+                return Err("Grade without a rubric can't have selected levels".to_owned());
+            }
+            if grade.points > grade.max_points {
+                return Err("Grade can't have more than max points".to_owned());
+            }
+        }
+    }
+    Ok(())
+}
+
+// checks that grade was given by the teacher of the assignment to the submission it references
+fn validate_grader(grade: &Grade, sources: Vec<Address>) -> Result<(), String> {
+    let assignment: Assignment = hdk::utils::get_as_type(grade.assignment_address.clone())
+        .map_err(|_| "Can't find the assignment of this grade".to_owned())?;
+    if grade.grader_address != assignment.teacher_address
+        || !sources.contains(&grade.grader_address)
+    {
+        return Err("Only the teacher of the course can grade submissions".to_owned());
+    }
+    let submission: Submission = hdk::utils::get_as_type(grade.submission_address.clone())
+        .map_err(|_| "Can't find the submission of this grade".to_owned())?;
+    if submission.assignment_address != grade.assignment_address
+        || submission.student_address != grade.student_address
+    {
+        return Err("Grade doesn't match it's submission".to_owned());
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Grade
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Grade::entry_type(),
+        description: "this is the definition of a teacher's grade of a submission",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Grade>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_points(&entry)?;
+                    validate_grader(&entry, validation_data.sources())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    if new_entry.submission_address != old_entry.submission_address {
+                        return Err("Can't move a grade to another submission".to_owned());
+                    }
+                    validate_points(&new_entry)?;
+                    validate_grader(&new_entry, validation_data.sources())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the submission that was graded
+            from!(
+                Submission::entry_type(),
+                link_type: SUBMISSION_TO_GRADE_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,360 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{Assignment, COURSE_ANCHOR_TO_ASSIGNMENT_LINK};
+use super::grade::{Grade, SUBMISSION_TO_GRADE_LINK};
+use super::submission::{Submission, ASSIGNMENT_TO_SUBMISSION_LINK};
+use crate::course;
+use crate::rubric::entry::Rubric;
+
+pub fn create(
+    course_anchor_address: Address,
+    title: String,
+    description: String,
+    due_date: u64,
+    max_points: u32,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let assignment = Assignment::new(
+        title,
+        description,
+        due_date,
+        max_points,
+        course_anchor_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let assignment_address = hdk::commit_entry(&assignment.entry())?;
+
+    hdk::link_entries(
+        &course_anchor_address,
+        &assignment_address,
+        COURSE_ANCHOR_TO_ASSIGNMENT_LINK,
+        "",
+    )?;
+
+    Ok(assignment_address)
+}
+
+// NOTE: get_entry follows updates, so this returns the latest version of the assignment
+// when called with the address of it's first version
+pub fn get_assignment(assignment_address: Address) -> ZomeApiResult<Option<Assignment>> {
+    match hdk::get_entry(&assignment_address)? {
+        Some(entry) => Ok(Assignment::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+// returns assignments of the course ordered by their due date
+pub fn get_assignments(
+    course_anchor_address: &Address,
+) -> ZomeApiResult<Vec<(Assignment, Address)>> {
+    let assignment_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_ASSIGNMENT_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut assignments = Vec::new();
+    for assignment_address in assignment_addresses {
+        let assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+        assignments.push((assignment, assignment_address));
+    }
+    assignments.sort_by_key(|(assignment, _assignment_address)| assignment.due_date);
+    Ok(assignments)
+}
+
+// wrapper for the get_assignments that only returns addresses of assignments
+pub fn list_assignments(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+    let assignments = get_assignments(&course_anchor_address)?;
+    Ok(assignments
+        .into_iter()
+        .map(|(_assignment, assignment_address)| assignment_address)
+        .collect())
+}
+
+// sets the rubric that teacher grades the assignment by. None switches back to point-only grading.
+// Grades that were already given keep the rubric they were given by
+pub fn attach_rubric(
+    assignment_address: Address,
+    rubric_address: Option<Address>,
+) -> ZomeApiResult<Address> {
+    let mut assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+    course::handlers::ensure_teacher(&assignment.course_anchor_address)?;
+    if let Some(rubric_address) = &rubric_address {
+        let rubric: Rubric = hdk::utils::get_as_type(rubric_address.clone())?;
+        if rubric.teacher_address != *AGENT_ADDRESS {
+            return Err(ZomeApiError::from(
+                "You can only attach your own rubrics. Clone this rubric first".to_owned(),
+            ));
+        }
+    }
+
+    // assignment is referenced by the address of it's first version, but we have to update the latest one
+    let latest_assignment_address = assignment.address()?;
+    assignment.rubric_address = rubric_address;
+    hdk::update_entry(assignment.entry(), &latest_assignment_address)?;
+
+    Ok(assignment_address)
+}
+
+// enables peer review of the assignment: after the due date every student who submitted it
+// reviews peer_review_count submissions of other students. 0 disables peer review
+pub fn set_peer_review_count(
+    assignment_address: Address,
+    peer_review_count: u32,
+) -> ZomeApiResult<Address> {
+    let mut assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+    course::handlers::ensure_teacher(&assignment.course_anchor_address)?;
+
+    let latest_assignment_address = assignment.address()?;
+    assignment.peer_review_count = peer_review_count;
+    hdk::update_entry(assignment.entry(), &latest_assignment_address)?;
+
+    Ok(assignment_address)
+}
+
+// the highest score for the assignment: either from it's rubric or it's max_points
+pub fn get_max_points(assignment: &Assignment) -> ZomeApiResult<u32> {
+    match &assignment.rubric_address {
+        Some(rubric_address) => {
+            let rubric: Rubric = hdk::utils::get_as_type(rubric_address.clone())?;
+            rubric.max_points().map_err(ZomeApiError::from)
+        }
+        None => Ok(assignment.max_points),
+    }
+}
+
+// retrieves submission of the student for the assignment (if there is one) together with it's address
+pub fn get_submission(
+    assignment_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<(Submission, Address)>> {
+    let submission_addresses = hdk::get_links(
+        assignment_address,
+        LinkMatch::Exactly(ASSIGNMENT_TO_SUBMISSION_LINK),
+        // links to submissions are tagged with the address of the student that submitted them
+        LinkMatch::Exactly(&student_address.to_string()),
+    )?
+    .addresses();
+
+    match submission_addresses.first() {
+        Some(submission_address) => {
+            let submission: Submission = hdk::utils::get_as_type(submission_address.clone())?;
+            Ok(Some((submission, submission_address.clone())))
+        }
+        None => Ok(None),
+    }
+}
+
+// creates submission of the agent that calls this function or replaces the previous one
+pub fn submit(assignment_address: Address, text: String, timestamp: u64) -> ZomeApiResult<Address> {
+    let assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+    if !course::handlers::is_enrolled(&assignment.course_anchor_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "Only students enrolled in this course can submit assignments".to_owned(),
+        ));
+    }
+    if timestamp > assignment.due_date {
+        return Err(ZomeApiError::from(
+            "Can't submit an assignment after it's due date".to_owned(),
+        ));
+    }
+
+    let student_tag = AGENT_ADDRESS.to_string();
+    let new_submission = Submission::new(
+        assignment_address.clone(),
+        AGENT_ADDRESS.clone(),
+        text,
+        timestamp,
+    );
+
+    match get_submission(&assignment_address, &AGENT_ADDRESS)? {
+        Some((_previous_submission, previous_submission_address)) => {
+            let new_submission_address =
+                hdk::update_entry(new_submission.entry(), &previous_submission_address)?;
+
+            // remove link to previous version of submission
+            hdk::remove_link(
+                &assignment_address,
+                &previous_submission_address,
+                ASSIGNMENT_TO_SUBMISSION_LINK,
+                student_tag.as_str(),
+            )?;
+
+            // create link to new version of submission
+            hdk::link_entries(
+                &assignment_address,
+                &new_submission_address,
+                ASSIGNMENT_TO_SUBMISSION_LINK,
+                student_tag.as_str(),
+            )?;
+
+            Ok(new_submission_address)
+        }
+        None => {
+            let new_submission_address = hdk::commit_entry(&new_submission.entry())?;
+            hdk::link_entries(
+                &assignment_address,
+                &new_submission_address,
+                ASSIGNMENT_TO_SUBMISSION_LINK,
+                student_tag.as_str(),
+            )?;
+
+            Ok(new_submission_address)
+        }
+    }
+}
+
+// returns addresses of all submissions for the assignment
+pub fn get_submissions(assignment_address: Address) -> ZomeApiResult<Vec<Address>> {
+    let assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+    course::handlers::ensure_teacher(&assignment.course_anchor_address)?;
+
+    let links = hdk::get_links(
+        &assignment_address,
+        LinkMatch::Exactly(ASSIGNMENT_TO_SUBMISSION_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+fn get_grade_with_address(submission_address: &Address) -> ZomeApiResult<Option<(Grade, Address)>> {
+    let grade_addresses = hdk::get_links(
+        submission_address,
+        LinkMatch::Exactly(SUBMISSION_TO_GRADE_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    match grade_addresses.first() {
+        Some(grade_address) => {
+            let grade: Grade = hdk::utils::get_as_type(grade_address.clone())?;
+            Ok(Some((grade, grade_address.clone())))
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn get_grade(submission_address: Address) -> ZomeApiResult<Option<Grade>> {
+    let grade_result = get_grade_with_address(&submission_address)?;
+    Ok(grade_result.map(|(grade, _grade_address)| grade))
+}
+
+pub fn get_student_grade(
+    assignment_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<Grade>> {
+    match get_submission(assignment_address, student_address)? {
+        Some((_submission, submission_address)) => get_grade(submission_address),
+        None => Ok(None),
+    }
+}
+
+pub fn get_my_grade(assignment_address: Address) -> ZomeApiResult<Option<Grade>> {
+    get_student_grade(&assignment_address, &AGENT_ADDRESS)
+}
+
+// grades submission or replaces it's previous grade.
+// If assignment has a rubric, points are computed from the selected levels and the points argument is ignored
+pub fn grade(
+    submission_address: Address,
+    selected_levels: Vec<usize>,
+    points: Option<u32>,
+    feedback: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let submission: Submission = hdk::utils::get_as_type(submission_address.clone())?;
+    let assignment: Assignment = hdk::utils::get_as_type(submission.assignment_address.clone())?;
+    course::handlers::ensure_teacher(&assignment.course_anchor_address)?;
+
+    let (points, max_points) = match &assignment.rubric_address {
+        Some(rubric_address) => {
+            let rubric: Rubric = hdk::utils::get_as_type(rubric_address.clone())?;
+            let points = rubric
+                .get_points(&selected_levels)
+                .map_err(ZomeApiError::from)?;
+            let max_points = rubric.max_points().map_err(ZomeApiError::from)?;
+            (points, max_points)
+        }
+        None => match points {
+            Some(points) => (points, assignment.max_points),
+            None => {
+                return Err(ZomeApiError::from(
+                    "Points are required for assignments without a rubric".to_owned(),
+                ));
+            }
+        },
+    };
+
+    let new_grade = Grade {
+        submission_address: submission_address.clone(),
+        assignment_address: submission.assignment_address,
+        student_address: submission.student_address,
+        grader_address: AGENT_ADDRESS.clone(),
+        rubric_address: assignment.rubric_address,
+        selected_levels: selected_levels,
+        points: points,
+        max_points: max_points,
+        feedback: feedback,
+        timestamp: timestamp,
+    };
+
+    match get_grade_with_address(&submission_address)? {
+        Some((_previous_grade, previous_grade_address)) => {
+            let new_grade_address = hdk::update_entry(new_grade.entry(), &previous_grade_address)?;
+
+            // remove link to previous version of grade
+            hdk::remove_link(
+                &submission_address,
+                &previous_grade_address,
+                SUBMISSION_TO_GRADE_LINK,
+                "",
+            )?;
+
+            // create link to new version of grade
+            hdk::link_entries(
+                &submission_address,
+                &new_grade_address,
+                SUBMISSION_TO_GRADE_LINK,
+                "",
+            )?;
+
+            Ok(new_grade_address)
+        }
+        None => {
+            let new_grade_address = hdk::commit_entry(&new_grade.entry())?;
+            hdk::link_entries(
+                &submission_address,
+                &new_grade_address,
+                SUBMISSION_TO_GRADE_LINK,
+                "",
+            )?;
+
+            Ok(new_grade_address)
+        }
+    }
+}
+
+// average score in percents of the graded assignments of the student.
+// It's None if none of student's assignments were graded yet
+pub fn get_student_score(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<f64>> {
+    let mut scores = Vec::new();
+    for (_assignment, assignment_address) in get_assignments(course_anchor_address)? {
+        if let Some(grade) = get_student_grade(&assignment_address, student_address)? {
+            scores.push(grade.score());
+        }
+    }
+    if scores.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(scores.iter().sum::<f64>() / scores.len() as f64))
+}
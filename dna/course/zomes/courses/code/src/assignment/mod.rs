@@ -0,0 +1,4 @@
+pub mod entry;
+pub mod grade;
+pub mod handlers;
+pub mod submission;
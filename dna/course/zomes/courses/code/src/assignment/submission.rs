@@ -0,0 +1,110 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::Assignment;
+use crate::calendar::ical::parse_date_time;
+
+pub const ASSIGNMENT_TO_SUBMISSION_LINK: &str = "assignment->submission";
+
+// Student's work for an assignment. Student can resubmit until the due date,
+// which updates this entry
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Submission {
+    pub assignment_address: Address,
+    pub student_address: Address,
+    pub text: String,
+    pub timestamp: u64,
+}
+
+impl Submission {
+    pub fn new(
+        assignment_address: Address,
+        student_address: Address,
+        text: String,
+        timestamp: u64,
+    ) -> Self {
+        Submission {
+            assignment_address: assignment_address,
+            student_address: student_address,
+            text: text,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Submission {
+    fn entry_type() -> String {
+        String::from("submission")
+    }
+}
+
+// how far the clock of the student can be off when we compare the due date with the commit time, in seconds
+const MAX_CLOCK_SKEW: u64 = 5 * 60;
+
+// students can only resubmit until the due date. Timestamp in the entry comes from the student's UI,
+// so we check the time of the commit in the student's source chain instead
+fn validate_resubmission(submission: &Submission, committed_at: &str) -> Result<(), String> {
+    let assignment: Assignment = hdk::utils::get_as_type(submission.assignment_address.clone())
+        .map_err(|_| "Can't find the assignment of this submission".to_owned())?;
+    let committed_at = parse_date_time(committed_at)
+        .ok_or_else(|| "Can't read time of the submission".to_owned())?;
+    if submission.timestamp > assignment.due_date
+        || committed_at > assignment.due_date.saturating_add(MAX_CLOCK_SKEW)
+    {
+        return Err("Submission can't be changed after the due date".to_owned());
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Submission
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Submission::entry_type(),
+        description: "this is the definition of a student's submission for an assignment",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Submission>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if !validation_data.sources().contains(&entry.student_address) {
+                        return Err("Submission can only be created by the student it belongs to".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    if new_entry.assignment_address != old_entry.assignment_address
+                        || new_entry.student_address != old_entry.student_address
+                    {
+                        return Err("Can't move a submission to another assignment or student".to_owned());
+                    }
+                    if !validation_data.sources().contains(&old_entry.student_address) {
+                        return Err("Submission can only be changed by the student who created it".to_owned());
+                    }
+                    validate_resubmission(
+                        &new_entry,
+                        &validation_data.package.chain_header.timestamp().to_string(),
+                    )
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the assignment this submission is for.
+            // Tag of this link is an address of the student so we can quickly find their submission
+            from!(
+                Assignment::entry_type(),
+                link_type: ASSIGNMENT_TO_SUBMISSION_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
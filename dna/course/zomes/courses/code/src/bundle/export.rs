@@ -0,0 +1,121 @@
+use hdk::prelude::*;
+
+use super::schema::{
+    self, BundleAttachment, BundleContent, BundleCourse, BundleQuestion, BundleQuiz, BundleSection,
+    BundleSource, CourseBundle, BUNDLE_FORMAT, BUNDLE_SCHEMA_VERSION,
+};
+use crate::content;
+use crate::content::entry::Content;
+use crate::course;
+use crate::question::entry::Question;
+use crate::quiz;
+use crate::section;
+
+fn export_section(section_anchor_address: &Address) -> ZomeApiResult<Option<BundleSection>> {
+    let section = match section::handlers::get_latest_section(section_anchor_address)? {
+        Some((section, _section_address)) => section,
+        None => return Ok(None),
+    };
+
+    let mut contents = Vec::new();
+    for content_address in content::handlers::get_contents(section_anchor_address)? {
+        let content: Content = hdk::utils::get_as_type(content_address)?;
+        contents.push(content);
+    }
+    // links don't keep their order, so contents are ordered by the moment they were created
+    contents.sort_by_key(|content| content.timestamp);
+
+    Ok(Some(BundleSection {
+        title: section.title,
+        release: section.release,
+        contents: contents
+            .into_iter()
+            .map(|content| BundleContent {
+                name: content.name,
+                url: content.url,
+                description: content.description,
+            })
+            .collect(),
+    }))
+}
+
+fn export_quizzes(course_anchor_address: &Address) -> ZomeApiResult<Vec<BundleQuiz>> {
+    let mut bundle_quizzes = Vec::new();
+    for (quiz, _quiz_address) in quiz::handlers::get_quizzes(course_anchor_address)? {
+        let mut questions = Vec::new();
+        for question_address in quiz.question_pool.iter() {
+            let question: Question = hdk::utils::get_as_type(question_address.clone())?;
+            questions.push(BundleQuestion {
+                text: question.text,
+                options: question.options,
+                correct_option: question.correct_option,
+                topics: question.topics,
+                difficulty: question.difficulty,
+            });
+        }
+        bundle_quizzes.push(BundleQuiz {
+            title: quiz.title,
+            topics: quiz.topics,
+            difficulty: quiz.difficulty,
+            question_count: quiz.question_count,
+            questions: questions,
+        });
+    }
+    Ok(bundle_quizzes)
+}
+
+// exports the course as a JSON bundle that import_course can read in any other network.
+// attachments are files of the course that UI has downloaded, they're checked and inlined as they are.
+// Only the teacher can export their course
+pub fn export_course(
+    course_anchor_address: Address,
+    include_quizzes: bool,
+    attachments: Option<Vec<BundleAttachment>>,
+    timestamp: u64,
+) -> ZomeApiResult<String> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+    if let Some(attachments) = &attachments {
+        schema::validate_attachments(attachments, "attachments").map_err(ZomeApiError::from)?;
+    }
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Err(ZomeApiError::from("Can't find this course".to_owned())),
+    };
+
+    let mut sections = Vec::new();
+    for section_anchor_address in course.sections.iter() {
+        // deleted sections are skipped
+        if let Some(bundle_section) = export_section(section_anchor_address)? {
+            sections.push(bundle_section);
+        }
+    }
+    let quizzes = if include_quizzes {
+        Some(export_quizzes(&course_anchor_address)?)
+    } else {
+        None
+    };
+
+    let bundle = CourseBundle {
+        format: BUNDLE_FORMAT.to_owned(),
+        schema_version: BUNDLE_SCHEMA_VERSION,
+        source: BundleSource {
+            course_anchor_address: course_anchor_address,
+            teacher_address: course.teacher_address,
+            exported_at: timestamp,
+        },
+        course: BundleCourse {
+            title: course.title,
+            prerequisite_policy: course.prerequisite_policy,
+            visibility: course.visibility,
+            max_students: course.max_students,
+            enrollment_mode: course.enrollment_mode,
+            grade_weights: course.grade_weights,
+        },
+        sections: sections,
+        quizzes: quizzes,
+        attachments: attachments,
+    };
+
+    serde_json::to_string_pretty(&bundle)
+        .map_err(|error| ZomeApiError::from(format!("Can't serialize the bundle: {}", error)))
+}
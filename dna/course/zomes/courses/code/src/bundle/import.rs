@@ -0,0 +1,422 @@
+use hdk::prelude::*;
+
+use super::schema::{
+    self, BundleAttachment, BundleQuiz, BundleSection, CourseBundle, BUNDLE_FORMAT,
+    BUNDLE_SCHEMA_VERSION,
+};
+use crate::content;
+use crate::course;
+use crate::question;
+use crate::quiz;
+use crate::section;
+use crate::section::entry::SectionRelease;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct ImportResult {
+    pub course_anchor_address: Address,
+    // true if this bundle was imported before and nothing new was created
+    pub already_imported: bool,
+    // files that were inlined into the bundle. Zome can't host files,
+    // so UI has to upload them and update urls of the contents that use them
+    pub attachments: Vec<BundleAttachment>,
+}
+
+// brings a bundle of an older schema version to the current one
+fn migrate(
+    mut bundle: serde_json::Value,
+    schema_version: u64,
+) -> Result<serde_json::Value, String> {
+    match schema_version {
+        // attachments were added in version 2
+        1 => {
+            if let Some(bundle_object) = bundle.as_object_mut() {
+                bundle_object.insert("attachments".to_owned(), serde_json::Value::Null);
+            }
+            migrate(bundle, 2)
+        }
+        2 => Ok(bundle),
+        _ => Err(format!(
+            "Bundle schema version {} isn't supported, the latest supported version is {}",
+            schema_version, BUNDLE_SCHEMA_VERSION
+        )),
+    }
+}
+
+// reads bundle of any supported schema version
+pub fn parse_bundle(bundle_json: &str) -> Result<CourseBundle, String> {
+    let bundle: serde_json::Value = serde_json::from_str(bundle_json)
+        .map_err(|error| format!("Bundle isn't valid JSON: {}", error))?;
+
+    match bundle.get("format").and_then(|format| format.as_str()) {
+        Some(BUNDLE_FORMAT) => {}
+        Some(format) => {
+            return Err(format!(
+                "Expected bundle format '{}', got '{}'",
+                BUNDLE_FORMAT, format
+            ));
+        }
+        None => return Err("Bundle doesn't have a 'format' field".to_owned()),
+    }
+    let schema_version = match bundle
+        .get("schema_version")
+        .and_then(|schema_version| schema_version.as_u64())
+    {
+        Some(schema_version) => schema_version,
+        None => {
+            return Err("Bundle doesn't have a numeric 'schema_version' field".to_owned());
+        }
+    };
+
+    let bundle = migrate(bundle, schema_version)?;
+    serde_json::from_value(bundle)
+        .map_err(|error| format!("Bundle doesn't match it's schema: {}", error))
+}
+
+// checks what serde can't: that bundle can be imported without failing halfway through
+pub fn validate_bundle(bundle: &CourseBundle) -> Result<(), String> {
+    if bundle.course.title.trim().is_empty() {
+        return Err("course.title can't be empty".to_owned());
+    }
+    bundle
+        .course
+        .grade_weights
+        .validate()
+        .map_err(|error| format!("course.grade_weights: {}", error))?;
+
+    for (section_index, section) in bundle.sections.iter().enumerate() {
+        if section.title.trim().is_empty() {
+            return Err(format!("sections[{}].title can't be empty", section_index));
+        }
+        for (content_index, content) in section.contents.iter().enumerate() {
+            if content.name.trim().is_empty() {
+                return Err(format!(
+                    "sections[{}].contents[{}].name can't be empty",
+                    section_index, content_index
+                ));
+            }
+        }
+    }
+
+    if let Some(attachments) = &bundle.attachments {
+        schema::validate_attachments(attachments, "attachments")?;
+    }
+
+    for (quiz_index, quiz) in bundle.quizzes.iter().flatten().enumerate() {
+        if quiz.questions.len() < quiz.question_count as usize {
+            return Err(format!(
+                "quizzes[{}] needs {} questions, but only has {}",
+                quiz_index,
+                quiz.question_count,
+                quiz.questions.len()
+            ));
+        }
+        for (question_index, question) in quiz.questions.iter().enumerate() {
+            if question.options.len() < 2 {
+                return Err(format!(
+                    "quizzes[{}].questions[{}] should have at least two options",
+                    quiz_index, question_index
+                ));
+            }
+            if question.correct_option >= question.options.len() {
+                return Err(format!(
+                    "quizzes[{}].questions[{}].correct_option is {}, but question only has {} options",
+                    quiz_index,
+                    question_index,
+                    question.correct_option,
+                    question.options.len()
+                ));
+            }
+            if question.topics.is_empty() {
+                return Err(format!(
+                    "quizzes[{}].questions[{}] should have at least one topic",
+                    quiz_index, question_index
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// creates sections, contents and quizzes of the bundle in the course that was just created for it
+fn import_course_items(
+    course_anchor_address: &Address,
+    sections: Vec<BundleSection>,
+    quizzes: Option<Vec<BundleQuiz>>,
+    timestamp: u64,
+) -> ZomeApiResult<()> {
+    // every entry gets it's own timestamp: sections and contents with the same title
+    // would otherwise have the same address, and contents are ordered by their timestamp
+    let mut entry_timestamp = timestamp;
+    for bundle_section in sections {
+        entry_timestamp += 1;
+        let section_anchor_address = section::handlers::create(
+            bundle_section.title,
+            course_anchor_address,
+            entry_timestamp,
+        )?;
+        if bundle_section.release != SectionRelease::Immediately {
+            section::handlers::set_release(&section_anchor_address, bundle_section.release)?;
+        }
+
+        for bundle_content in bundle_section.contents {
+            entry_timestamp += 1;
+            content::handlers::create(
+                bundle_content.name,
+                bundle_content.url,
+                bundle_content.description,
+                entry_timestamp,
+                section_anchor_address.clone(),
+            )?;
+        }
+    }
+
+    for bundle_quiz in quizzes.into_iter().flatten() {
+        let mut question_pool = Vec::new();
+        for bundle_question in bundle_quiz.questions {
+            entry_timestamp += 1;
+            question_pool.push(question::handlers::create(
+                bundle_question.text,
+                bundle_question.options,
+                bundle_question.correct_option,
+                bundle_question.topics,
+                bundle_question.difficulty,
+                entry_timestamp,
+            )?);
+        }
+        entry_timestamp += 1;
+        quiz::handlers::create_with_pool(
+            course_anchor_address.clone(),
+            bundle_quiz.title,
+            bundle_quiz.topics,
+            bundle_quiz.difficulty,
+            bundle_quiz.question_count,
+            question_pool,
+            entry_timestamp,
+        )?;
+    }
+    Ok(())
+}
+
+// recreates the course from the bundle under the agent that calls this function.
+// Importing the same bundle again returns the course that was imported the first time.
+// If an import fails halfway, the course it has created is deleted and isn't marked as imported,
+// so importing the bundle again starts over
+pub fn import_course(bundle_json: String, timestamp: u64) -> ZomeApiResult<ImportResult> {
+    let bundle = parse_bundle(&bundle_json).map_err(ZomeApiError::from)?;
+    validate_bundle(&bundle).map_err(ZomeApiError::from)?;
+
+    let attachments = bundle.attachments.unwrap_or_default();
+    let source_id = bundle.source.course_anchor_address.to_string();
+    if let Some(course_anchor_address) = course::handlers::get_my_imported_course(&source_id)? {
+        return Ok(ImportResult {
+            course_anchor_address: course_anchor_address,
+            already_imported: true,
+            attachments: attachments,
+        });
+    }
+
+    let course_anchor_address =
+        course::handlers::create(bundle.course.title, timestamp, bundle.course.visibility)?;
+    let import_result = course::handlers::set_imported_settings(
+        &course_anchor_address,
+        bundle.course.prerequisite_policy,
+        bundle.course.max_students,
+        bundle.course.enrollment_mode,
+        bundle.course.grade_weights,
+    )
+    .and_then(|_course_address| {
+        import_course_items(
+            &course_anchor_address,
+            bundle.sections,
+            bundle.quizzes,
+            timestamp,
+        )
+    })
+    .and_then(|_| course::handlers::set_source_id(&course_anchor_address, source_id));
+    if let Err(error) = import_result {
+        // entries that were already committed stay in the source chain, but without the course
+        // nobody can find them. We're returning the original error, it says what went wrong
+        let _delete_result = course::handlers::delete(course_anchor_address);
+        return Err(error);
+    }
+
+    Ok(ImportResult {
+        course_anchor_address: course_anchor_address,
+        already_imported: false,
+        attachments: attachments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::course::entry::GradeWeights;
+
+    fn bundle_json(schema_version: u64) -> serde_json::Value {
+        serde_json::json!({
+            "format": BUNDLE_FORMAT,
+            "schema_version": schema_version,
+            "source": {
+                "course_anchor_address": "QmCourse",
+                "teacher_address": "QmTeacher",
+                "exported_at": 1600000000
+            },
+            "course": {
+                "title": "Rust basics",
+                "prerequisite_policy": "Warn",
+                "visibility": "Public",
+                "max_students": null,
+                "enrollment_mode": "Open",
+                "grade_weights": { "quizzes": 40, "assignments": 40, "progress": 20, "attendance": 0 }
+            },
+            "sections": [{
+                "title": "Ownership",
+                "release": "Immediately",
+                "contents": [{ "name": "Borrowing", "url": "https://example.com/borrowing", "description": "" }]
+            }],
+            "quizzes": [{
+                "title": "Ownership quiz",
+                "topics": ["ownership"],
+                "difficulty": null,
+                "question_count": 1,
+                "questions": [{
+                    "text": "Who owns a moved value?",
+                    "options": ["The old variable", "The new variable"],
+                    "correct_option": 1,
+                    "topics": ["ownership"],
+                    "difficulty": "Easy"
+                }]
+            }],
+            "attachments": []
+        })
+    }
+
+    fn valid_bundle() -> CourseBundle {
+        serde_json::from_value(bundle_json(u64::from(BUNDLE_SCHEMA_VERSION))).unwrap()
+    }
+
+    #[test]
+    fn parses_current_bundle() {
+        let bundle = parse_bundle(&bundle_json(2).to_string()).unwrap();
+        assert_eq!(bundle.course.title, "Rust basics");
+        assert_eq!(bundle.sections[0].contents.len(), 1);
+        assert_eq!(
+            bundle.attachments.map(|attachments| attachments.len()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn parses_version_1_bundle_without_attachments() {
+        let mut bundle = bundle_json(1);
+        bundle.as_object_mut().unwrap().remove("attachments");
+        let bundle = parse_bundle(&bundle.to_string()).unwrap();
+        assert!(bundle.attachments.is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(parse_bundle("{ not json").is_err());
+    }
+
+    #[test]
+    fn rejects_other_format() {
+        let mut bundle = bundle_json(2);
+        bundle["format"] = serde_json::json!("something-else");
+        assert!(parse_bundle(&bundle.to_string())
+            .unwrap_err()
+            .contains("something-else"));
+    }
+
+    #[test]
+    fn rejects_missing_schema_version() {
+        let mut bundle = bundle_json(2);
+        bundle.as_object_mut().unwrap().remove("schema_version");
+        assert!(parse_bundle(&bundle.to_string()).is_err());
+    }
+
+    #[test]
+    fn rejects_bundle_that_doesnt_match_schema() {
+        let mut bundle = bundle_json(2);
+        bundle["sections"] = serde_json::json!("Ownership");
+        assert!(parse_bundle(&bundle.to_string()).is_err());
+    }
+
+    #[test]
+    fn migrates_version_1_to_current() {
+        let migrated = migrate(serde_json::json!({ "format": BUNDLE_FORMAT }), 1).unwrap();
+        assert_eq!(migrated["attachments"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn keeps_current_version_as_is() {
+        let bundle = bundle_json(2);
+        assert_eq!(migrate(bundle.clone(), 2).unwrap(), bundle);
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        assert!(migrate(bundle_json(0), 0).is_err());
+        assert!(migrate(bundle_json(3), 3).is_err());
+    }
+
+    #[test]
+    fn accepts_valid_bundle() {
+        assert_eq!(validate_bundle(&valid_bundle()), Ok(()));
+    }
+
+    #[test]
+    fn rejects_empty_titles() {
+        let mut bundle = valid_bundle();
+        bundle.course.title = " ".to_owned();
+        assert!(validate_bundle(&bundle).is_err());
+
+        let mut bundle = valid_bundle();
+        bundle.sections[0].contents[0].name = "".to_owned();
+        assert!(validate_bundle(&bundle)
+            .unwrap_err()
+            .starts_with("sections[0].contents[0].name"));
+    }
+
+    #[test]
+    fn rejects_invalid_grade_weights() {
+        let mut bundle = valid_bundle();
+        bundle.course.grade_weights = GradeWeights {
+            quizzes: 0,
+            assignments: 0,
+            progress: 0,
+            attendance: 0,
+        };
+        assert!(validate_bundle(&bundle).is_err());
+
+        bundle.course.grade_weights.quizzes = std::u32::MAX;
+        bundle.course.grade_weights.progress = 1;
+        assert!(validate_bundle(&bundle)
+            .unwrap_err()
+            .starts_with("course.grade_weights"));
+    }
+
+    #[test]
+    fn rejects_quiz_that_cant_be_generated() {
+        let mut bundle = valid_bundle();
+        bundle.quizzes.as_mut().unwrap()[0].question_count = 2;
+        assert!(validate_bundle(&bundle).is_err());
+
+        let mut bundle = valid_bundle();
+        bundle.quizzes.as_mut().unwrap()[0].questions[0].correct_option = 2;
+        assert!(validate_bundle(&bundle)
+            .unwrap_err()
+            .contains("correct_option"));
+    }
+
+    #[test]
+    fn rejects_invalid_attachments() {
+        let mut bundle = valid_bundle();
+        bundle.attachments = Some(vec![BundleAttachment {
+            name: "slides.pdf".to_owned(),
+            mime: "pdf".to_owned(),
+            data_base64: "".to_owned(),
+        }]);
+        assert!(validate_bundle(&bundle).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use hdk::holochain_persistence_api::cas::content::Address;
+
+use crate::course::entry::{CourseVisibility, EnrollmentMode, GradeWeights, PrerequisitePolicy};
+use crate::question::entry::QuestionDifficulty;
+use crate::section::entry::SectionRelease;
+
+// every bundle starts with these two fields, so importer can tell what it's reading
+// and migrate bundles written by older versions
+pub const BUNDLE_FORMAT: &str = "leap-course-bundle";
+// NOTE: increase this when the bundle structure changes and teach the importer to read the old version
+pub const BUNDLE_SCHEMA_VERSION: u32 = 2;
+
+// Portable copy of a course that doesn't depend on addresses of the DHT it came from.
+// Contents only store urls and zome can't download them, so files are inlined as attachments
+// that the caller passes to export_course
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CourseBundle {
+    pub format: String,
+    pub schema_version: u32,
+    pub source: BundleSource,
+    pub course: BundleCourse,
+    // in the same order as in the course
+    pub sections: Vec<BundleSection>,
+    // it's None if quizzes weren't exported
+    pub quizzes: Option<Vec<BundleQuiz>>,
+    // it's None if attachments weren't exported. Added in schema version 2
+    pub attachments: Option<Vec<BundleAttachment>>,
+}
+
+// where the bundle was exported from
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleSource {
+    pub course_anchor_address: Address,
+    pub teacher_address: Address,
+    pub exported_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleCourse {
+    pub title: String,
+    pub prerequisite_policy: PrerequisitePolicy,
+    pub visibility: CourseVisibility,
+    pub max_students: Option<u32>,
+    pub enrollment_mode: EnrollmentMode,
+    pub grade_weights: GradeWeights,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleSection {
+    pub title: String,
+    pub release: SectionRelease,
+    // in the order they were created
+    pub contents: Vec<BundleContent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleContent {
+    pub name: String,
+    pub url: String,
+    pub description: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleQuiz {
+    pub title: String,
+    pub topics: Vec<String>,
+    pub difficulty: Option<QuestionDifficulty>,
+    pub question_count: u32,
+    // the whole question pool of the quiz, so it can be generated the same way after import
+    pub questions: Vec<BundleQuestion>,
+}
+
+// file inlined into the bundle
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleAttachment {
+    pub name: String,
+    // e.g. "application/pdf"
+    pub mime: String,
+    // standard base64 with padding
+    pub data_base64: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleQuestion {
+    pub text: String,
+    pub options: Vec<String>,
+    pub correct_option: usize,
+    pub topics: Vec<String>,
+    pub difficulty: QuestionDifficulty,
+}
+
+fn is_base64(data: &str) -> bool {
+    let bytes = data.as_bytes();
+    if bytes.len() % 4 != 0 {
+        return false;
+    }
+    let padding = bytes.iter().rev().take_while(|byte| **byte == b'=').count();
+    padding <= 2
+        && bytes[..bytes.len() - padding]
+            .iter()
+            .all(|byte| byte.is_ascii_alphanumeric() || *byte == b'+' || *byte == b'/')
+}
+
+// checks attachments of a bundle. field is the path of the list in the bundle, e.g. "attachments"
+pub fn validate_attachments(attachments: &[BundleAttachment], field: &str) -> Result<(), String> {
+    for (index, attachment) in attachments.iter().enumerate() {
+        if attachment.name.trim().is_empty() {
+            return Err(format!("{}[{}].name can't be empty", field, index));
+        }
+        if attachments[..index]
+            .iter()
+            .any(|previous_attachment| previous_attachment.name == attachment.name)
+        {
+            return Err(format!(
+                "{}[{}].name '{}' is used by another attachment",
+                field, index, attachment.name
+            ));
+        }
+        let mut mime_parts = attachment.mime.splitn(2, '/');
+        let is_mime = match (mime_parts.next(), mime_parts.next()) {
+            (Some(mime_type), Some(mime_subtype)) => {
+                !mime_type.is_empty() && !mime_subtype.is_empty()
+            }
+            _ => false,
+        };
+        if !is_mime {
+            return Err(format!(
+                "{}[{}].mime should look like 'type/subtype', got '{}'",
+                field, index, attachment.mime
+            ));
+        }
+        if !is_base64(&attachment.data_base64) {
+            return Err(format!(
+                "{}[{}].data_base64 isn't valid base64",
+                field, index
+            ));
+        }
+    }
+    Ok(())
+}
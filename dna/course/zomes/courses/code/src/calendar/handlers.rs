@@ -0,0 +1,164 @@
+use hdk::error::{ZomeApiError, ZomeApiResult};
+use hdk::holochain_core_types::{chain_header::ChainHeader, link::LinkMatch, time::Timeout};
+use hdk::holochain_persistence_api::cas::content::Address;
+use hdk::holochain_wasm_utils::api_serialization::{
+    get_entry::{GetEntryOptions, GetEntryResultType, StatusRequestKind},
+    get_links::{GetLinksOptions, LinksStatusRequestKind},
+};
+use hdk::AGENT_ADDRESS;
+
+use super::ical::{event_uid, parse_date_time, render_calendar, CalendarEvent, Revision};
+use crate::anchor_trait::AnchorTrait;
+use crate::assignment;
+use crate::cohort;
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+use crate::live_session;
+use crate::section;
+use crate::section::anchor::SectionAnchor;
+use crate::section::entry::SectionRelease;
+
+// builds revision from headers of all versions of an entry. Entry that was never changed
+// has sequence 0 and was last modified when it was created
+fn to_revision<'a, I>(headers: I, version_count: usize, created_at: u64) -> Revision
+where
+    I: Iterator<Item = &'a ChainHeader>,
+{
+    let last_modified = headers
+        .filter_map(|header| parse_date_time(&header.timestamp().to_string()))
+        .fold(created_at, u64::max);
+    let sequence = version_count.saturating_sub(1) as u32;
+    Revision::new(last_modified, sequence)
+}
+
+// revision of an entry that is updated in place and referenced by the address of it's first version
+fn get_entry_revision(entry_address: &Address, created_at: u64) -> ZomeApiResult<Revision> {
+    let options = GetEntryOptions::new(StatusRequestKind::All, false, true, Timeout::default());
+    let items = match hdk::get_entry_result(entry_address, options)?.result {
+        GetEntryResultType::All(history) => history.items,
+        GetEntryResultType::Single(item) => vec![item],
+    };
+    Ok(to_revision(
+        items.iter().flat_map(|item| item.headers.iter()),
+        items.len(),
+        created_at,
+    ))
+}
+
+// revision of data that anchor links to, like Course or Section. Anchor is relinked to every
+// new version, so links to previous versions are deleted but can still be read with their headers
+fn get_linked_revision(
+    anchor_address: &Address,
+    link_type: &str,
+    created_at: u64,
+) -> ZomeApiResult<Revision> {
+    let options = GetLinksOptions {
+        status_request: LinksStatusRequestKind::All,
+        headers: true,
+        ..GetLinksOptions::default()
+    };
+    let links = hdk::get_links_with_options(
+        anchor_address,
+        LinkMatch::Exactly(link_type),
+        LinkMatch::Any,
+        options,
+    )?
+    .links();
+    Ok(to_revision(
+        links.iter().flat_map(|link| link.headers.iter()),
+        links.len(),
+        created_at,
+    ))
+}
+
+// returns RFC 5545 iCalendar with the schedule of the course as seen by the agent that calls this function.
+// Sections released relative to enrollment are only included for students who are enrolled
+pub fn export_course_calendar(course_anchor_address: Address) -> ZomeApiResult<String> {
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't export calendar of a deleted course".to_owned(),
+            ));
+        }
+    };
+    let enrollment_timestamp =
+        course::handlers::get_enrollment_timestamp(&course_anchor_address, &AGENT_ADDRESS)?;
+
+    // course title is a part of every event, so changing it changes all of them
+    let course_revision = get_linked_revision(
+        &course_anchor_address,
+        &CourseAnchor::link_type(),
+        course.timestamp,
+    )?;
+
+    let mut events = Vec::new();
+
+    for section_anchor_address in course.sections.iter() {
+        let section = match section::handlers::get_latest_section(section_anchor_address)? {
+            Some((section, _section_address)) => section,
+            None => continue,
+        };
+        // sections that are available right away don't need a calendar event
+        if section.release == SectionRelease::Immediately {
+            continue;
+        }
+        if let Some(release_at) =
+            section::handlers::get_release_time(&section, enrollment_timestamp)
+        {
+            let section_revision = get_linked_revision(
+                section_anchor_address,
+                &SectionAnchor::link_type(),
+                section.timestamp,
+            )?;
+            events.push(CalendarEvent::new(
+                event_uid("section-release", &section_anchor_address.to_string()),
+                section_revision.combine(&course_revision),
+                release_at,
+                format!("{}: {} is released", course.title, section.title),
+            ));
+        }
+    }
+
+    for (cohort, cohort_address) in cohort::handlers::get_cohorts(&course_anchor_address)? {
+        let mut event = CalendarEvent::new(
+            event_uid("cohort", &cohort_address.to_string()),
+            Revision::new(cohort.timestamp, 0).combine(&course_revision),
+            cohort.start_date,
+            format!("{}: {}", course.title, cohort.name),
+        );
+        event.end = Some(cohort.end_date);
+        events.push(event);
+    }
+
+    for (assignment, assignment_address) in
+        assignment::handlers::get_assignments(&course_anchor_address)?
+    {
+        let assignment_revision = get_entry_revision(&assignment_address, assignment.timestamp)?;
+        let mut event = CalendarEvent::new(
+            event_uid("assignment-due", &assignment_address.to_string()),
+            assignment_revision.combine(&course_revision),
+            assignment.due_date,
+            format!("{}: {} is due", course.title, assignment.title),
+        );
+        event.description = Some(assignment.description);
+        events.push(event);
+    }
+
+    for (live_session, live_session_address) in
+        live_session::handlers::get_course_sessions(&course_anchor_address)?
+    {
+        let mut event = CalendarEvent::new(
+            event_uid("live-session", &live_session_address.to_string()),
+            Revision::new(live_session.timestamp, 0).combine(&course_revision),
+            live_session.start_time,
+            format!("{}: {}", course.title, live_session.title),
+        );
+        event.end = Some(live_session.end_time());
+        event.url = Some(live_session.meeting_url);
+        events.push(event);
+    }
+
+    events.sort_by_key(|event| event.start);
+    Ok(render_calendar(&course.title, &events))
+}
@@ -0,0 +1,329 @@
+// Minimal RFC 5545 (iCalendar) writer. We only need VEVENTs with UTC times, so instead of pulling
+// in a whole crate for it we format everything by hand here
+
+const PRODUCT_ID: &str = "-//Leap//Leap Courses//EN";
+// domain part of event UIDs, so they don't clash with events from other calendars
+const UID_DOMAIN: &str = "leap.holochain";
+// RFC 5545 recommends not to have lines longer than 75 octets
+const MAX_LINE_LENGTH: usize = 75;
+const SECONDS_IN_DAY: u64 = 86400;
+
+// how many times the entries that an event is built from were changed and when it last happened.
+// Calendar apps only replace their copy of an event when it's SEQUENCE goes up
+#[derive(Clone, Copy)]
+pub struct Revision {
+    pub last_modified: u64,
+    pub sequence: u32,
+}
+
+impl Revision {
+    pub fn new(last_modified: u64, sequence: u32) -> Self {
+        Revision {
+            last_modified: last_modified,
+            sequence: sequence,
+        }
+    }
+
+    // revision of an event that is built from both entries
+    pub fn combine(&self, other: &Revision) -> Revision {
+        Revision {
+            last_modified: self.last_modified.max(other.last_modified),
+            sequence: self.sequence.saturating_add(other.sequence),
+        }
+    }
+}
+
+pub struct CalendarEvent {
+    // stable id of the event. It's derived from entry addresses so that importing calendar
+    // again updates events instead of duplicating them
+    pub uid: String,
+    pub revision: Revision,
+    pub start: u64,
+    pub end: Option<u64>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub url: Option<String>,
+}
+
+impl CalendarEvent {
+    pub fn new(uid: String, revision: Revision, start: u64, summary: String) -> Self {
+        CalendarEvent {
+            uid: uid,
+            revision: revision,
+            start: start,
+            end: None,
+            summary: summary,
+            description: None,
+            url: None,
+        }
+    }
+}
+
+// builds event UID from the kind of the event and the address of the entry it's based on
+pub fn event_uid(kind: &str, address: &str) -> String {
+    format!("{}-{}@{}", kind, address, UID_DOMAIN)
+}
+
+// converts days since 1970-01-01 to (year, month, day).
+// This is the days_from_civil algorithm by Howard Hinnant run backwards
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719468;
+    let era = z / 146097;
+    let day_of_era = z - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+// converts (year, month, day) to days since 1970-01-01. This is the days_from_civil algorithm
+// by Howard Hinnant. Dates before 1970 aren't supported
+fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    let year = if month <= 2 {
+        year.checked_sub(1)?
+    } else {
+        year
+    };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * mp + 2) / 5 + day.checked_sub(1)?;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    (era * 146097 + day_of_era).checked_sub(719468)
+}
+
+// parses RFC 3339 date-time, e.g. 2020-06-15T09:30:00+00:00, into unix timestamp in seconds.
+// This is the format Holochain uses for timestamps in entry headers
+pub fn parse_date_time(date_time: &str) -> Option<u64> {
+    let number = |range: std::ops::Range<usize>| -> Option<u64> {
+        date_time.get(range)?.parse::<u64>().ok()
+    };
+    let days = days_from_civil(number(0..4)?, number(5..7)?, number(8..10)?)?;
+    let seconds_of_day = number(11..13)? * 3600 + number(14..16)? * 60 + number(17..19)?;
+    let local_time = days * SECONDS_IN_DAY + seconds_of_day;
+
+    // fraction of a second is ignored, offset is either Z or +HH:MM / -HH:MM
+    let offset = date_time
+        .get(19..)?
+        .trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    if offset == "Z" {
+        return Some(local_time);
+    }
+    let offset_seconds =
+        offset.get(1..3)?.parse::<u64>().ok()? * 3600 + offset.get(4..6)?.parse::<u64>().ok()? * 60;
+    match offset.get(0..1)? {
+        "+" => local_time.checked_sub(offset_seconds),
+        "-" => local_time.checked_add(offset_seconds),
+        _ => None,
+    }
+}
+
+// formats unix timestamp in seconds as an iCalendar UTC date-time, e.g. 20200615T093000Z
+pub fn format_date_time(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days(timestamp / SECONDS_IN_DAY);
+    let seconds_of_day = timestamp % SECONDS_IN_DAY;
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60,
+        seconds_of_day % 60
+    )
+}
+
+// escapes characters that have special meaning in iCalendar TEXT values
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+// URL property is a URI, so it isn't escaped like TEXT. Control characters are dropped instead,
+// otherwise a line break in the URL would start a new property of the event
+pub fn sanitize_uri(uri: &str) -> String {
+    uri.chars().filter(|c| !c.is_control()).collect()
+}
+
+// splits content line into lines of at most MAX_LINE_LENGTH octets.
+// Continuation lines start with a space. We never split a multi-byte UTF-8 character
+fn fold_line(line: &str, output: &mut String) {
+    let mut line_length = 0;
+    for character in line.chars() {
+        let character_length = character.len_utf8();
+        if line_length + character_length > MAX_LINE_LENGTH {
+            output.push_str("\r\n ");
+            // leading space counts towards the length of continuation line
+            line_length = 1;
+        }
+        output.push(character);
+        line_length += character_length;
+    }
+    output.push_str("\r\n");
+}
+
+pub fn render_calendar(name: &str, events: &[CalendarEvent]) -> String {
+    let mut output = String::new();
+    fold_line("BEGIN:VCALENDAR", &mut output);
+    fold_line("VERSION:2.0", &mut output);
+    fold_line(&format!("PRODID:{}", PRODUCT_ID), &mut output);
+    fold_line("CALSCALE:GREGORIAN", &mut output);
+    fold_line(&format!("X-WR-CALNAME:{}", escape_text(name)), &mut output);
+    for event in events {
+        fold_line("BEGIN:VEVENT", &mut output);
+        fold_line(&format!("UID:{}", event.uid), &mut output);
+        // NOTE: calendar is published without a METHOD, so DTSTAMP is the time event was last
+        // changed rather than the time of the export
+        fold_line(
+            &format!("DTSTAMP:{}", format_date_time(event.revision.last_modified)),
+            &mut output,
+        );
+        fold_line(
+            &format!(
+                "LAST-MODIFIED:{}",
+                format_date_time(event.revision.last_modified)
+            ),
+            &mut output,
+        );
+        fold_line(
+            &format!("SEQUENCE:{}", event.revision.sequence),
+            &mut output,
+        );
+        fold_line(
+            &format!("DTSTART:{}", format_date_time(event.start)),
+            &mut output,
+        );
+        if let Some(end) = event.end {
+            fold_line(&format!("DTEND:{}", format_date_time(end)), &mut output);
+        }
+        fold_line(
+            &format!("SUMMARY:{}", escape_text(&event.summary)),
+            &mut output,
+        );
+        if let Some(description) = &event.description {
+            fold_line(
+                &format!("DESCRIPTION:{}", escape_text(description)),
+                &mut output,
+            );
+        }
+        if let Some(url) = &event.url {
+            fold_line(&format!("URL:{}", sanitize_uri(url)), &mut output);
+        }
+        fold_line("END:VEVENT", &mut output);
+    }
+    fold_line("END:VCALENDAR", &mut output);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_text_escapes_special_characters() {
+        assert_eq!(escape_text("plain text"), "plain text");
+        assert_eq!(escape_text("a;b,c\\d"), "a\\;b\\,c\\\\d");
+        assert_eq!(escape_text("line\r\nbreak\nend"), "line\\nbreak\\nend");
+    }
+
+    #[test]
+    fn sanitize_uri_drops_control_characters() {
+        assert_eq!(
+            sanitize_uri("https://example.com/a,b;c"),
+            "https://example.com/a,b;c"
+        );
+        assert_eq!(
+            sanitize_uri("https://example.com\r\nATTENDEE:x"),
+            "https://example.comATTENDEE:x"
+        );
+    }
+
+    #[test]
+    fn fold_line_keeps_short_lines() {
+        let mut output = String::new();
+        fold_line("SUMMARY:short", &mut output);
+        assert_eq!(output, "SUMMARY:short\r\n");
+    }
+
+    #[test]
+    fn fold_line_splits_long_lines() {
+        let line = "a".repeat(MAX_LINE_LENGTH + 80);
+        let mut output = String::new();
+        fold_line(&line, &mut output);
+        let lines: Vec<&str> = output.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].len(), MAX_LINE_LENGTH);
+        assert_eq!(lines[1], format!(" {}", "a".repeat(MAX_LINE_LENGTH - 1)));
+        assert_eq!(lines[2], " aaaaaa");
+        assert_eq!(output.replace("\r\n ", ""), format!("{}\r\n", line));
+    }
+
+    #[test]
+    fn fold_line_doesnt_split_multi_byte_characters() {
+        // every character takes 2 octets, so the first line can only fit 37 of them
+        let line = "\u{e9}".repeat(40);
+        let mut output = String::new();
+        fold_line(&line, &mut output);
+        let lines: Vec<&str> = output.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines[0], "\u{e9}".repeat(37));
+        assert_eq!(lines[1], format!(" {}", "\u{e9}".repeat(3)));
+    }
+
+    #[test]
+    fn parse_date_time_reads_utc_and_offsets() {
+        assert_eq!(parse_date_time("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(
+            parse_date_time("2020-06-15T09:30:00+00:00"),
+            Some(1_592_213_400)
+        );
+        assert_eq!(
+            parse_date_time("2020-06-15T11:30:00+02:00"),
+            Some(1_592_213_400)
+        );
+        assert_eq!(
+            parse_date_time("2020-06-15T04:30:00-05:00"),
+            Some(1_592_213_400)
+        );
+        assert_eq!(
+            parse_date_time("2020-06-15T09:30:00.123Z"),
+            Some(1_592_213_400)
+        );
+        assert_eq!(parse_date_time("2020-02-29T00:00:00Z"), Some(1_582_934_400));
+    }
+
+    #[test]
+    fn parse_date_time_rejects_invalid_input() {
+        assert_eq!(parse_date_time(""), None);
+        assert_eq!(parse_date_time("2020-06-15"), None);
+        assert_eq!(parse_date_time("2020-06-15T09:30:00"), None);
+        assert_eq!(parse_date_time("2020-06-15T09:30:00*01:00"), None);
+        assert_eq!(parse_date_time("1969-12-31T23:59:59Z"), None);
+    }
+
+    #[test]
+    fn format_date_time_matches_parsed_time() {
+        let parse_and_format = |date_time: &str| parse_date_time(date_time).map(format_date_time);
+        assert_eq!(
+            parse_and_format("2020-06-15T11:30:00+02:00"),
+            Some("20200615T093000Z".to_owned())
+        );
+        assert_eq!(
+            parse_and_format("2020-02-29T00:00:00Z"),
+            Some("20200229T000000Z".to_owned())
+        );
+    }
+}
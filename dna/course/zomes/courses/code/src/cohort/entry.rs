@@ -0,0 +1,280 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_COHORT_LINK: &str = "course_anchor->cohort";
+pub const COHORT_TO_STUDENT_LINK: &str = "cohort->student";
+pub const STUDENT_TO_COHORT_LINK: &str = "student->cohort";
+pub const STUDENT_TO_PENDING_COHORT_LINK: &str = "student->pending_cohort";
+
+// A single run of a course with it's own dates and students.
+// Students are enrolled in the course as usual and additionally linked to one of it's cohorts
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Cohort {
+    pub name: String,
+    pub start_date: u64,
+    pub end_date: u64,
+    // how many students can join this cohort. None means there's no limit
+    pub max_students: Option<u32>,
+    pub course_anchor_address: Address,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Cohort {
+    pub fn new(
+        name: String,
+        start_date: u64,
+        end_date: u64,
+        max_students: Option<u32>,
+        course_anchor_address: Address,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Cohort {
+            name: name,
+            start_date: start_date,
+            end_date: end_date,
+            max_students: max_students,
+            course_anchor_address: course_anchor_address,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Cohort {
+    fn entry_type() -> String {
+        String::from("cohort")
+    }
+}
+
+fn validate_cohort(cohort: &Cohort) -> Result<(), String> {
+    if cohort.name.trim().is_empty() {
+        return Err("Cohort name can't be empty".to_owned());
+    }
+    if cohort.end_date <= cohort.start_date {
+        return Err("Cohort has to end after it starts".to_owned());
+    }
+    Ok(())
+}
+
+// cohorts are created by the teacher of the course they're a run of
+fn validate_author(cohort: &Cohort, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&cohort.teacher_address) {
+        return Err("Cohort can only be created by the teacher of the course".to_owned());
+    }
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(cohort.course_anchor_address.clone())
+        .map_err(|_| "Can't find the course of this cohort".to_owned())?;
+    if cohort.teacher_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can create it's cohorts".to_owned());
+    }
+    Ok(())
+}
+
+fn has_chosen_cohort(
+    student_address: &Address,
+    cohort_address: &Address,
+    cohort: &Cohort,
+) -> Result<bool, String> {
+    let links = hdk::get_links(
+        student_address,
+        LinkMatch::Exactly(STUDENT_TO_PENDING_COHORT_LINK),
+        LinkMatch::Exactly(&cohort.course_anchor_address.to_string()),
+    )
+    .map_err(|_| "Can't check which cohort student has chosen".to_owned())?;
+    Ok(links.addresses().contains(cohort_address))
+}
+
+// cohort is full when max_students other students are already linked to it
+fn validate_capacity(
+    cohort: &Cohort,
+    cohort_address: &Address,
+    student_address: &Address,
+) -> Result<(), String> {
+    let max_students = match cohort.max_students {
+        Some(max_students) => max_students as usize,
+        None => return Ok(()),
+    };
+    let links = hdk::get_links(
+        cohort_address,
+        LinkMatch::Exactly(COHORT_TO_STUDENT_LINK),
+        LinkMatch::Any,
+    )
+    .map_err(|_| "Can't get students of the cohort".to_owned())?;
+    let mut other_students: Vec<Address> = links
+        .addresses()
+        .into_iter()
+        .filter(|linked_address| linked_address != student_address)
+        .collect();
+    other_students.sort();
+    other_students.dedup();
+    if other_students.len() >= max_students {
+        return Err("This cohort is full".to_owned());
+    }
+    Ok(())
+}
+
+// students join cohorts themselves and the teacher adds students they admit later.
+// Only students enrolled in the course can be added to it's cohort on it, while there are free seats
+fn validate_cohort_student_link(
+    validation_data: hdk::LinkValidationData,
+    is_from_student: bool,
+) -> Result<(), String> {
+    let (link, sources, is_removed) = match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => (link, validation_data.sources(), false),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => (link, validation_data.sources(), true),
+    };
+    let (student_address, cohort_address) = if is_from_student {
+        (link.link.base(), link.link.target())
+    } else {
+        (link.link.target(), link.link.base())
+    };
+    let cohort: Cohort = hdk::utils::get_as_type(cohort_address.clone())
+        .map_err(|_| "Can't find the cohort".to_owned())?;
+    // whoever promotes a student from the course waitlist also adds them to the cohort they've chosen
+    if !sources.contains(student_address)
+        && !sources.contains(&cohort.teacher_address)
+        && (is_removed || !has_chosen_cohort(student_address, cohort_address, &cohort)?)
+    {
+        return Err(
+            "Only the student or the teacher of the course can change who is in a cohort"
+                .to_owned(),
+        );
+    }
+    // links from students to cohorts are tagged with the course address
+    if is_from_student && *link.link.tag() != cohort.course_anchor_address.to_string() {
+        return Err("Link to a cohort has to be tagged with the course of the cohort".to_owned());
+    }
+    if is_removed {
+        return Ok(());
+    }
+    match course::handlers::is_enrolled(&cohort.course_anchor_address, student_address) {
+        Ok(true) => {}
+        Ok(false) => {
+            return Err("Only students enrolled in the course can join it's cohort".to_owned())
+        }
+        Err(_) => return Err("Can't check if student is enrolled in the course".to_owned()),
+    }
+    validate_capacity(&cohort, cohort_address, student_address)
+}
+
+// student can only choose a cohort for themselves. This choice is removed by whoever admits them
+fn validate_pending_cohort_link(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => {
+            if !validation_data.sources().contains(link.link.base()) {
+                return Err("Students can only choose cohorts for themselves".to_owned());
+            }
+            Ok(())
+        }
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => {
+            let sources = validation_data.sources();
+            if sources.contains(link.link.base()) {
+                return Ok(());
+            }
+            let cohort: Cohort = hdk::utils::get_as_type(link.link.target().clone())
+                .map_err(|_| "Can't find the cohort".to_owned())?;
+            if sources.contains(&cohort.teacher_address) {
+                return Ok(());
+            }
+            match course::handlers::is_enrolled(&cohort.course_anchor_address, link.link.base()) {
+                Ok(true) => Ok(()),
+                Ok(false) => {
+                    Err("Only the student or the teacher can remove cohort choice".to_owned())
+                }
+                Err(_) => Err("Can't check if student is enrolled in the course".to_owned()),
+            }
+        }
+    }
+}
+
+// Holochain entry definition for Cohort
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Cohort::entry_type(),
+        description: "this is the definition of a single run of a course",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Cohort>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_cohort(&entry)?;
+                    validate_author(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Cohorts can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course this cohort is a run of
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_COHORT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            ),
+            // link to an agent who is a student of this cohort.
+            // Tag of this link is the timestamp of when student joined the cohort
+            to!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: COHORT_TO_STUDENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_cohort_student_link(validation_data, false)
+                }
+            ),
+            // link from agent that is a student of this cohort
+            // This is for student to keep track of cohorts they've joined
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: STUDENT_TO_COHORT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_cohort_student_link(validation_data, true)
+                }
+            ),
+            // link from agent that was waitlisted or requested to enroll while joining this cohort.
+            // Tag of this link is the course address. Student is added to the cohort once they're admitted
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: STUDENT_TO_PENDING_COHORT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_pending_cohort_link(validation_data)
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,321 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{
+    Cohort, COHORT_TO_STUDENT_LINK, COURSE_ANCHOR_TO_COHORT_LINK, STUDENT_TO_COHORT_LINK,
+    STUDENT_TO_PENDING_COHORT_LINK,
+};
+use crate::course;
+use crate::course::handlers::EnrollmentResult;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct CohortStudentProgress {
+    pub student_address: Address,
+    pub completed: bool,
+}
+
+pub fn create(
+    course_anchor_address: Address,
+    name: String,
+    start_date: u64,
+    end_date: u64,
+    max_students: Option<u32>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let cohort = Cohort::new(
+        name,
+        start_date,
+        end_date,
+        max_students,
+        course_anchor_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let cohort_address = hdk::commit_entry(&cohort.entry())?;
+
+    hdk::link_entries(
+        &course_anchor_address,
+        &cohort_address,
+        COURSE_ANCHOR_TO_COHORT_LINK,
+        "",
+    )?;
+
+    Ok(cohort_address)
+}
+
+pub fn get_cohort(cohort_address: Address) -> ZomeApiResult<Option<Cohort>> {
+    match hdk::get_entry(&cohort_address)? {
+        Some(entry) => Ok(Cohort::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+// returns cohorts of the course ordered by their start date
+pub fn get_cohorts(course_anchor_address: &Address) -> ZomeApiResult<Vec<(Cohort, Address)>> {
+    let cohort_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_COHORT_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut cohorts = Vec::new();
+    for cohort_address in cohort_addresses {
+        let cohort: Cohort = hdk::utils::get_as_type(cohort_address.clone())?;
+        cohorts.push((cohort, cohort_address));
+    }
+    cohorts.sort_by_key(|(cohort, _cohort_address)| cohort.start_date);
+    Ok(cohorts)
+}
+
+// wrapper for the get_cohorts that only returns addresses of cohorts
+pub fn list_cohorts(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+    let cohorts = get_cohorts(&course_anchor_address)?;
+    Ok(cohorts
+        .into_iter()
+        .map(|(_cohort, cohort_address)| cohort_address)
+        .collect())
+}
+
+pub fn get_students(cohort_address: &Address) -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        cohort_address,
+        LinkMatch::Exactly(COHORT_TO_STUDENT_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+// checks if one more student can join the cohort without exceeding it's max_students limit
+pub fn has_free_seat(cohort: &Cohort, cohort_address: &Address) -> ZomeApiResult<bool> {
+    match cohort.max_students {
+        Some(max_students) => {
+            let student_count = hdk::get_links_count(
+                cohort_address,
+                LinkMatch::Exactly(COHORT_TO_STUDENT_LINK),
+                LinkMatch::Any,
+            )?
+            .count;
+            Ok(student_count < max_students as usize)
+        }
+        None => Ok(true),
+    }
+}
+
+// returns the cohort of the course that student at student_address belongs to
+pub fn get_student_cohort(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<Address>> {
+    let cohort_addresses = hdk::get_links(
+        student_address,
+        LinkMatch::Exactly(STUDENT_TO_COHORT_LINK),
+        // links from students to cohorts are tagged with the course address
+        LinkMatch::Exactly(&course_anchor_address.to_string()),
+    )?
+    .addresses();
+
+    Ok(cohort_addresses.first().cloned())
+}
+
+pub fn get_my_cohort(course_anchor_address: Address) -> ZomeApiResult<Option<Address>> {
+    get_student_cohort(&course_anchor_address, &AGENT_ADDRESS)
+}
+
+// links student to the cohort in both directions.
+// Link from cohort to student is tagged with the moment student joined it
+fn add_student(
+    cohort_address: &Address,
+    course_anchor_address: &Address,
+    student_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<()> {
+    hdk::link_entries(
+        cohort_address,
+        student_address,
+        COHORT_TO_STUDENT_LINK,
+        timestamp.to_string().as_str(),
+    )?;
+    hdk::link_entries(
+        student_address,
+        cohort_address,
+        STUDENT_TO_COHORT_LINK,
+        course_anchor_address.to_string().as_str(),
+    )?;
+    Ok(())
+}
+
+// enrolls the agent that calls this function in the course of the cohort (if they're not enrolled yet)
+// and adds them to the cohort.
+// If the course puts student on the waitlist or requires teacher's approval, we remember their choice
+// and student is added to the cohort once the teacher admits them, see join_pending_cohort
+pub fn enroll(
+    cohort_address: Address,
+    timestamp: u64,
+    invite_code: Option<String>,
+) -> ZomeApiResult<EnrollmentResult> {
+    let cohort: Cohort = hdk::utils::get_as_type(cohort_address.clone())?;
+    let course_anchor_address = cohort.course_anchor_address.clone();
+
+    if timestamp >= cohort.end_date {
+        return Err(ZomeApiError::from(
+            "Can't join a cohort that has already ended".to_owned(),
+        ));
+    }
+    if get_student_cohort(&course_anchor_address, &AGENT_ADDRESS)?.is_some() {
+        return Err(ZomeApiError::from(
+            "You've already joined a cohort of this course".to_owned(),
+        ));
+    }
+    if !has_free_seat(&cohort, &cohort_address)? {
+        return Err(ZomeApiError::from(
+            "This cohort has reached it's max_students limit".to_owned(),
+        ));
+    }
+
+    let enrollment_result =
+        if course::handlers::is_enrolled(&course_anchor_address, &AGENT_ADDRESS)? {
+            EnrollmentResult {
+                course_anchor_address: course_anchor_address.clone(),
+                missing_prerequisites: Vec::new(),
+                waitlist_position: None,
+                enrollment_request_address: None,
+            }
+        } else {
+            course::handlers::enroll(course_anchor_address.clone(), timestamp, invite_code)?
+        };
+
+    if enrollment_result.waitlist_position.is_none()
+        && enrollment_result.enrollment_request_address.is_none()
+    {
+        add_student(
+            &cohort_address,
+            &course_anchor_address,
+            &AGENT_ADDRESS,
+            timestamp,
+        )?;
+    } else {
+        hdk::link_entries(
+            &AGENT_ADDRESS,
+            &cohort_address,
+            STUDENT_TO_PENDING_COHORT_LINK,
+            course_anchor_address.to_string().as_str(),
+        )?;
+    }
+
+    Ok(enrollment_result)
+}
+
+// returns the cohort that student has chosen before being admitted into the course
+fn get_pending_cohort(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<Address>> {
+    let pending_cohort_addresses = hdk::get_links(
+        student_address,
+        LinkMatch::Exactly(STUDENT_TO_PENDING_COHORT_LINK),
+        LinkMatch::Exactly(&course_anchor_address.to_string()),
+    )?
+    .addresses();
+    Ok(pending_cohort_addresses.into_iter().next())
+}
+
+// removes the cohort that student has chosen before being admitted into the course
+pub fn forget_pending_cohort(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<()> {
+    let course_tag = course_anchor_address.to_string();
+    let pending_cohort_addresses = hdk::get_links(
+        student_address,
+        LinkMatch::Exactly(STUDENT_TO_PENDING_COHORT_LINK),
+        LinkMatch::Exactly(&course_tag),
+    )?
+    .addresses();
+    for cohort_address in pending_cohort_addresses.iter() {
+        hdk::remove_link(
+            student_address,
+            cohort_address,
+            STUDENT_TO_PENDING_COHORT_LINK,
+            course_tag.as_str(),
+        )?;
+    }
+    Ok(())
+}
+
+// adds student who was admitted from the waitlist or by teacher's approval to the cohort
+// they've chosen when enrolling. Student stays without a cohort if it's full or has already ended.
+// Their choice is removed after they're added, since it's what lets others add them to the cohort
+pub fn join_pending_cohort(
+    course_anchor_address: &Address,
+    student_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<()> {
+    let cohort_address = match get_pending_cohort(course_anchor_address, student_address)? {
+        Some(cohort_address) => cohort_address,
+        None => return Ok(()),
+    };
+    let cohort: Cohort = hdk::utils::get_as_type(cohort_address.clone())?;
+    if get_student_cohort(course_anchor_address, student_address)?.is_none()
+        && timestamp < cohort.end_date
+        && has_free_seat(&cohort, &cohort_address)?
+    {
+        add_student(
+            &cohort_address,
+            course_anchor_address,
+            student_address,
+            timestamp,
+        )?;
+    }
+    forget_pending_cohort(course_anchor_address, student_address)
+}
+
+// removes student from their cohort of the course. It's called when student leaves the course
+pub fn leave(course_anchor_address: &Address, student_address: &Address) -> ZomeApiResult<()> {
+    if let Some(cohort_address) = get_student_cohort(course_anchor_address, student_address)? {
+        hdk::remove_link(
+            student_address,
+            &cohort_address,
+            STUDENT_TO_COHORT_LINK,
+            course_anchor_address.to_string().as_str(),
+        )?;
+
+        let links = hdk::get_links(
+            &cohort_address,
+            LinkMatch::Exactly(COHORT_TO_STUDENT_LINK),
+            LinkMatch::Any,
+        )?;
+        for link in links.links() {
+            if link.address == *student_address {
+                hdk::remove_link(
+                    &cohort_address,
+                    student_address,
+                    COHORT_TO_STUDENT_LINK,
+                    link.tag.as_str(),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// completion of the course by every student of the cohort
+pub fn get_progress(cohort_address: Address) -> ZomeApiResult<Vec<CohortStudentProgress>> {
+    let cohort: Cohort = hdk::utils::get_as_type(cohort_address.clone())?;
+    course::handlers::ensure_teacher(&cohort.course_anchor_address)?;
+
+    let mut progress = Vec::new();
+    for student_address in get_students(&cohort_address)? {
+        let completed =
+            course::handlers::has_completed(&cohort.course_anchor_address, &student_address)?;
+        progress.push(CohortStudentProgress {
+            student_address: student_address,
+            completed: completed,
+        });
+    }
+    Ok(progress)
+}
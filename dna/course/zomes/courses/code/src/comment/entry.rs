@@ -0,0 +1,189 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::content::entry::Content;
+use crate::course::anchor::CourseAnchor;
+use crate::section::anchor::SectionAnchor;
+
+pub const CONTENT_TO_COMMENT_LINK: &str = "content->comment";
+pub const SECTION_ANCHOR_TO_COMMENT_LINK: &str = "section_anchor->comment";
+pub const COMMENT_TO_UPVOTER_LINK: &str = "comment->upvoter";
+pub const COMMENT_TO_ANSWER_LINK: &str = "comment->answer";
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Comment {
+    pub text: String,
+    // address of the Content or SectionAnchor that this comment is about
+    pub target_address: Address,
+    // comment that this one replies to. It's None for comments that start a new thread
+    pub parent_address: Option<Address>,
+    pub course_anchor_address: Address,
+    pub author_address: Address,
+    pub timestamp: u64,
+    // NOTE: deleted comments are kept with their text erased instead of being removed
+    // from the DHT, so that replies to them can still be displayed as part of the thread
+    pub deleted: bool,
+}
+
+impl Comment {
+    pub fn new(
+        text: String,
+        target_address: Address,
+        parent_address: Option<Address>,
+        course_anchor_address: Address,
+        author_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Comment {
+            text: text,
+            target_address: target_address,
+            parent_address: parent_address,
+            course_anchor_address: course_anchor_address,
+            author_address: author_address,
+            timestamp: timestamp,
+            deleted: false,
+        }
+    }
+}
+
+impl HolochainEntry for Comment {
+    fn entry_type() -> String {
+        String::from("comment")
+    }
+}
+
+// agents can only upvote (and take back upvote) for themselves. Upvote links aren't tagged,
+// so all upvotes of an agent point to the same agent address and are counted once
+fn validate_upvoter_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    let (link, sources) = match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => (link, validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => (link, validation_data.sources()),
+    };
+    if !sources.contains(link.link.target()) {
+        return Err("Agents can only upvote comments for themselves".to_owned());
+    }
+    if !link.link.tag().is_empty() {
+        return Err("Upvote links can't be tagged".to_owned());
+    }
+    Ok(())
+}
+
+// only the teacher of the course can mark a reply to the comment as it's answer
+fn validate_answer_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let reply: Comment = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the answer".to_owned())?;
+    if reply.parent_address.as_ref() != Some(link_data.link.base()) {
+        return Err("Only replies to the comment can be marked as it's answers".to_owned());
+    }
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(reply.course_anchor_address)
+        .map_err(|_| "Can't find the course of this comment".to_owned())?;
+    if !sources.contains(&course_anchor.teacher_address) {
+        return Err("Only the teacher of the course can mark answers".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_answer_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_answer_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_answer_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for Comment
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Comment::entry_type(),
+        description: "this is the definition of a comment in a discussion thread",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Comment>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if !validation_data.sources().contains(&entry.author_address) {
+                        return Err("Comment can only be created by it's author".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    if new_entry.author_address != old_entry.author_address
+                        || new_entry.target_address != old_entry.target_address
+                        || new_entry.parent_address != old_entry.parent_address
+                    {
+                        return Err("Can't move a comment to another author, target or thread".to_owned());
+                    }
+                    if !validation_data.sources().contains(&old_entry.author_address) {
+                        return Err("Comment can only be edited by it's author".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the content that this comment discusses.
+            // All comments of the thread, including replies, are linked from the target
+            from!(
+                Content::entry_type(),
+                link_type: CONTENT_TO_COMMENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            ),
+            // link from the section that this comment discusses
+            from!(
+                SectionAnchor::entry_type(),
+                link_type: SECTION_ANCHOR_TO_COMMENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            ),
+            // link to every agent that upvoted this comment
+            to!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: COMMENT_TO_UPVOTER_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_upvoter_link_change(validation_data)
+                }
+            ),
+            // link to a reply that teacher has marked as an answer to this comment
+            to!(
+                Comment::entry_type(),
+                link_type: COMMENT_TO_ANSWER_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_answer_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
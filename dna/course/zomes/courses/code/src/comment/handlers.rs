@@ -0,0 +1,329 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+use std::collections::HashSet;
+
+use super::entry::{
+    Comment, COMMENT_TO_ANSWER_LINK, COMMENT_TO_UPVOTER_LINK, CONTENT_TO_COMMENT_LINK,
+    SECTION_ANCHOR_TO_COMMENT_LINK,
+};
+use crate::anchor_trait::AnchorTrait;
+use crate::content;
+use crate::content::entry::Content;
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+use crate::moderation;
+use crate::moderation::anchor::HIDDEN_COMMENT_LINK;
+use crate::section::anchor::SectionAnchor;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct CommentNode {
+    // address of the first version of the comment. It doesn't change when comment is edited
+    pub address: Address,
+    pub comment: Comment,
+    pub upvotes: usize,
+    // true if teacher has marked this reply as an answer to it's parent comment
+    pub is_answer: bool,
+    pub replies: Vec<CommentNode>,
+}
+
+// returns type of the link that connects target at target_address to it's comments
+fn get_target_link_type(target_address: &Address) -> ZomeApiResult<&'static str> {
+    match hdk::get_entry(target_address)? {
+        Some(Entry::App(entry_type, _entry_value)) => {
+            if entry_type.to_string() == Content::entry_type() {
+                Ok(CONTENT_TO_COMMENT_LINK)
+            } else if entry_type.to_string() == SectionAnchor::entry_type() {
+                Ok(SECTION_ANCHOR_TO_COMMENT_LINK)
+            } else {
+                Err(ZomeApiError::from(
+                    "Only content and sections can be commented on".to_owned(),
+                ))
+            }
+        }
+        _ => Err(ZomeApiError::from(
+            "Can't comment on a deleted content or section".to_owned(),
+        )),
+    }
+}
+
+// comments are always referenced by the address of their first version and get_entry follows
+// updates to the latest version. To edit a comment we need the address of that latest version
+fn get_latest_comment(comment_address: &Address) -> ZomeApiResult<(Comment, Address)> {
+    let comment: Comment = hdk::utils::get_as_type(comment_address.clone())?;
+    let latest_comment_address = comment.address()?;
+    Ok((comment, latest_comment_address))
+}
+
+fn ensure_author(comment: &Comment) -> ZomeApiResult<()> {
+    if comment.author_address != *AGENT_ADDRESS {
+        return Err(ZomeApiError::from(
+            "Only the author of this comment can do that".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+// returns addresses of all comments in threads about the target, including replies
+fn get_comment_addresses(target_address: &Address) -> ZomeApiResult<Vec<Address>> {
+    // NOTE: we don't check what the target is here, so threads stay readable
+    // even after the content or section they're about was deleted
+    let mut comment_addresses = hdk::get_links(
+        target_address,
+        LinkMatch::Exactly(CONTENT_TO_COMMENT_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+    comment_addresses.extend(
+        hdk::get_links(
+            target_address,
+            LinkMatch::Exactly(SECTION_ANCHOR_TO_COMMENT_LINK),
+            LinkMatch::Any,
+        )?
+        .addresses(),
+    );
+    Ok(comment_addresses)
+}
+
+pub fn create(
+    target_address: Address,
+    section_anchor_address: Address,
+    parent_address: Option<Address>,
+    text: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let link_type = get_target_link_type(&target_address)?;
+    // content is reusable in other sections, so we're checking that it's a part of the section
+    // and taking the course from the section
+    if link_type == CONTENT_TO_COMMENT_LINK
+        && !content::handlers::get_contents(&section_anchor_address)?.contains(&target_address)
+    {
+        return Err(ZomeApiError::from(
+            "This content isn't a part of the section".to_owned(),
+        ));
+    }
+    if link_type == SECTION_ANCHOR_TO_COMMENT_LINK && target_address != section_anchor_address {
+        return Err(ZomeApiError::from(
+            "Section comment should be about the same section".to_owned(),
+        ));
+    }
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address)?;
+    let course_anchor_address = section_anchor.course_anchor_address;
+
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(course_anchor_address.clone())?;
+    if course_anchor.teacher_address != *AGENT_ADDRESS
+        && !course::handlers::is_enrolled(&course_anchor_address, &AGENT_ADDRESS)?
+    {
+        return Err(ZomeApiError::from(
+            "Only the teacher and students of this course can comment on it".to_owned(),
+        ));
+    }
+
+    if let Some(parent_address) = &parent_address {
+        if !get_comment_addresses(&target_address)?.contains(parent_address) {
+            return Err(ZomeApiError::from(
+                "Can only reply to comments about the same content or section".to_owned(),
+            ));
+        }
+    }
+
+    let new_comment = Comment::new(
+        text,
+        target_address.clone(),
+        parent_address,
+        course_anchor_address,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let new_comment_address = hdk::commit_entry(&new_comment.entry())?;
+    hdk::link_entries(&target_address, &new_comment_address, link_type, "")?;
+
+    Ok(new_comment_address)
+}
+
+pub fn edit(comment_address: Address, text: String) -> ZomeApiResult<Address> {
+    let (mut comment, latest_comment_address) = get_latest_comment(&comment_address)?;
+    ensure_author(&comment)?;
+    if comment.deleted {
+        return Err(ZomeApiError::from(
+            "Can't edit a deleted comment".to_owned(),
+        ));
+    }
+
+    comment.text = text;
+    hdk::update_entry(comment.entry(), &latest_comment_address)?;
+
+    // returning address of the first version so that callers keep using the same comment ID
+    Ok(comment_address)
+}
+
+pub fn delete(comment_address: Address) -> ZomeApiResult<Address> {
+    let (mut comment, latest_comment_address) = get_latest_comment(&comment_address)?;
+    ensure_author(&comment)?;
+
+    // NOTE: instead of removing the comment we erase it's text. Replies keep their place
+    // in the thread and UI can display this comment as deleted
+    comment.text = String::default();
+    comment.deleted = true;
+    hdk::update_entry(comment.entry(), &latest_comment_address)?;
+
+    Ok(comment_address)
+}
+
+pub fn mark_as_answer(comment_address: Address) -> ZomeApiResult<Address> {
+    let comment: Comment = hdk::utils::get_as_type(comment_address.clone())?;
+    course::handlers::ensure_teacher(&comment.course_anchor_address)?;
+
+    match comment.parent_address {
+        Some(parent_address) => {
+            hdk::link_entries(
+                &parent_address,
+                &comment_address,
+                COMMENT_TO_ANSWER_LINK,
+                "",
+            )?;
+            Ok(comment_address)
+        }
+        None => Err(ZomeApiError::from(
+            "Only replies can be marked as answers".to_owned(),
+        )),
+    }
+}
+
+pub fn upvote(comment_address: Address) -> ZomeApiResult<Address> {
+    let upvoters = hdk::get_links(
+        &comment_address,
+        LinkMatch::Exactly(COMMENT_TO_UPVOTER_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+    // every agent can only upvote a comment once
+    if !upvoters.contains(&AGENT_ADDRESS) {
+        hdk::link_entries(
+            &comment_address,
+            &AGENT_ADDRESS,
+            COMMENT_TO_UPVOTER_LINK,
+            "",
+        )?;
+    }
+    Ok(comment_address)
+}
+
+// content gets a new address on every update, so we link all comments about the previous version
+// to the new one for the discussion to continue there
+pub fn move_content_comments(
+    previous_content_address: &Address,
+    new_content_address: &Address,
+) -> ZomeApiResult<()> {
+    let comment_addresses = hdk::get_links(
+        previous_content_address,
+        LinkMatch::Exactly(CONTENT_TO_COMMENT_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+    for comment_address in comment_addresses {
+        hdk::link_entries(
+            new_content_address,
+            &comment_address,
+            CONTENT_TO_COMMENT_LINK,
+            "",
+        )?;
+    }
+    Ok(())
+}
+
+fn build_comment_node(
+    comment_address: &Address,
+    comment: &Comment,
+    all_comments: &[(Address, Comment)],
+    parent_answers: &[Address],
+) -> ZomeApiResult<CommentNode> {
+    // agent that upvoted the same comment twice is only counted once
+    let upvoters: HashSet<Address> = hdk::get_links(
+        comment_address,
+        LinkMatch::Exactly(COMMENT_TO_UPVOTER_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses()
+    .into_iter()
+    .collect();
+    let answers = hdk::get_links(
+        comment_address,
+        LinkMatch::Exactly(COMMENT_TO_ANSWER_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut replies = Vec::new();
+    for (reply_address, reply) in all_comments.iter() {
+        if reply.parent_address.as_ref() == Some(comment_address) {
+            replies.push(build_comment_node(
+                reply_address,
+                reply,
+                all_comments,
+                &answers,
+            )?);
+        }
+    }
+
+    Ok(CommentNode {
+        address: comment_address.clone(),
+        comment: comment.clone(),
+        upvotes: upvoters.len(),
+        is_answer: parent_answers.contains(comment_address),
+        replies: replies,
+    })
+}
+
+// returns a single page of threads about the target. Threads are sorted from oldest to newest
+// and every thread contains all of it's replies. page_number starts from 0
+pub fn get_thread(
+    target_address: Address,
+    page_number: usize,
+    page_size: usize,
+) -> ZomeApiResult<Vec<CommentNode>> {
+    // comments hidden by moderators are skipped together with their replies
+    let hidden_comments = moderation::handlers::get_hidden(HIDDEN_COMMENT_LINK)?;
+    let mut all_comments = Vec::new();
+    for comment_address in get_comment_addresses(&target_address)? {
+        if hidden_comments.contains(&comment_address) {
+            continue;
+        }
+        let comment: Comment = hdk::utils::get_as_type(comment_address.clone())?;
+        all_comments.push((comment_address, comment));
+    }
+    all_comments.sort_by_key(|(_comment_address, comment)| comment.timestamp);
+
+    let known_addresses: HashSet<Address> = all_comments
+        .iter()
+        .map(|(comment_address, _comment)| comment_address.clone())
+        .collect();
+
+    // comments that start a thread. Replies to comments we can't find are treated
+    // as separate threads so that they don't disappear
+    let thread_starts: Vec<&(Address, Comment)> = all_comments
+        .iter()
+        .filter(
+            |(_comment_address, comment)| match &comment.parent_address {
+                Some(parent_address) => {
+                    !known_addresses.contains(parent_address)
+                        && !hidden_comments.contains(parent_address)
+                }
+                None => true,
+            },
+        )
+        .skip(page_number * page_size)
+        .take(page_size)
+        .collect();
+
+    let mut threads = Vec::new();
+    for (comment_address, comment) in thread_starts {
+        threads.push(build_comment_node(
+            comment_address,
+            comment,
+            &all_comments,
+            &[],
+        )?);
+    }
+    Ok(threads)
+}
@@ -4,8 +4,13 @@ use holochain_entry_utils::HolochainEntry;
 use hdk::prelude::*;
 
 use super::entry::Content;
+use crate::comment;
+use crate::moderation;
+use crate::moderation::anchor::HIDDEN_CONTENT_LINK;
 use crate::section;
-use crate::section::anchor::SECTION_TO_CONTENT_LINK;
+use crate::section::anchor::{SectionAnchor, SECTION_TO_CONTENT_LINK};
+use crate::signal;
+use crate::signal::handlers::LeapSignal;
 
 pub fn create(
     name: String,
@@ -26,6 +31,14 @@ pub fn create(
                 "",
             )?;
 
+            signal::handlers::notify_section_students(
+                &section_anchor_address,
+                &LeapSignal::ContentCreated {
+                    section_anchor_address: section_anchor_address.clone(),
+                    content_address: new_content_address.clone(),
+                },
+            )?;
+
             Ok(new_content_address)
         }
         None => {
@@ -43,7 +56,41 @@ pub fn get_contents(section_anchor_address: &Address) -> ZomeApiResult<Vec<Addre
         LinkMatch::Any,
     )?;
 
-    Ok(links.addresses())
+    // skip content that was hidden by moderators in this section
+    let hidden_contents = moderation::handlers::get_hidden_with_tag(
+        HIDDEN_CONTENT_LINK,
+        &section_anchor_address.to_string(),
+    )?;
+    Ok(links
+        .addresses()
+        .into_iter()
+        .filter(|address| !hidden_contents.contains(address))
+        .collect())
+}
+
+// contents of the section as seen by the agent that calls this function at the given moment.
+// Contents of sections that aren't released yet can't be read. Callers that don't pass their time
+// can only read sections that are released immediately
+pub fn get_released_contents(
+    section_anchor_address: &Address,
+    timestamp: Option<u64>,
+) -> ZomeApiResult<Vec<Address>> {
+    if let Some((section, _section_address)) =
+        section::handlers::get_latest_section(section_anchor_address)?
+    {
+        let section_anchor: SectionAnchor =
+            hdk::utils::get_as_type(section_anchor_address.clone())?;
+        if !section::handlers::is_released(
+            &section,
+            &section_anchor.course_anchor_address,
+            timestamp.unwrap_or(0),
+        )? {
+            return Err(ZomeApiError::from(
+                "This section is locked until it's released".to_owned(),
+            ));
+        }
+    }
+    get_contents(section_anchor_address)
 }
 
 pub fn update(
@@ -76,6 +123,18 @@ pub fn update(
         "",
     )?;
 
+    // carry discussion threads over to the updated version of content
+    comment::handlers::move_content_comments(&content_address, &updated_content_address)?;
+
+    signal::handlers::notify_section_students(
+        &section_anchor_address,
+        &LeapSignal::ContentUpdated {
+            section_anchor_address: section_anchor_address.clone(),
+            content_address: content_address,
+            updated_content_address: updated_content_address.clone(),
+        },
+    )?;
+
     // return address of the updated content entry
     Ok(updated_content_address)
 }
@@ -90,7 +149,17 @@ pub fn delete(content_address: Address, section_anchor_address: Address) -> Zome
         "",
     )?;
 
+    signal::handlers::notify_section_students(
+        &section_anchor_address,
+        &LeapSignal::ContentDeleted {
+            section_anchor_address: section_anchor_address.clone(),
+            content_address: content_address.clone(),
+        },
+    )?;
+
     Ok(content_address)
     // content is reusable in other sections
+    // NOTE: discussion threads are linked from the content entry, so removing it
+    // would make them unreachable. That's one more reason to keep it
    // hdk::remove_entry(&content_address)
 }
@@ -1,12 +1,24 @@
 use hdk::prelude::*;
 use holochain_entry_utils::HolochainEntry;
 
-use super::entry::Course;
+use super::entry::{Course, CourseVisibility, EnrollmentMode, PrerequisitePolicy};
 use crate::anchor_trait::AnchorTrait;
+use crate::calendar::ical::parse_date_time;
+use crate::course;
+use crate::invite;
+use crate::prerequisite;
 
 pub const TEACHER_TO_COURSE_ANCHOR_LINK: &str = "teacher->course_anchor";
 pub const STUDENT_TO_COURSE_ANCHOR_LINK: &str = "student->course_anchor";
 pub const COURSE_ANCHOR_TO_STUDENT_LINK: &str = "course_anchor->student";
+pub const STUDENT_TO_COMPLETED_COURSE_ANCHOR_LINK: &str = "student->completed_course_anchor";
+pub const COURSE_ANCHOR_TO_COMPLETED_STUDENT_LINK: &str = "course_anchor->completed_student";
+pub const COURSE_ANCHOR_TO_PREREQUISITE_LINK: &str = "course_anchor->prerequisite";
+pub const STUDENT_TO_WAITLISTED_COURSE_ANCHOR_LINK: &str = "student->waitlisted_course_anchor";
+pub const COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK: &str = "course_anchor->waitlisted_student";
+
+// how far the clock of the student can be off when we compare waitlist tag with the commit time, in seconds
+const MAX_CLOCK_SKEW: u64 = 5 * 60;
 
 #[derive(Serialize, Deserialize, Debug, self::DefaultJson, Clone)]
 pub struct CourseAnchor {
@@ -39,6 +51,274 @@ impl CourseAnchor {
     }
 }
 
+// students enroll themselves and the teacher enrolls students whose requests they approve.
+// In invite-only courses students can only enroll themselves if they were invited.
+// Nobody can enroll a student who hasn't completed prerequisites of a course that blocks them
+fn validate_enrollment_author(
+    course: &Course,
+    student_address: &Address,
+    sources: &[Address],
+) -> Result<(), String> {
+    if course.prerequisite_policy == PrerequisitePolicy::Block {
+        let missing_prerequisites = prerequisite::handlers::get_missing_prerequisites(
+            &course.anchor_address,
+            student_address,
+        )
+        .map_err(|_| "Can't check prerequisites of the course".to_owned())?;
+        if !missing_prerequisites.is_empty() {
+            return Err(
+                "Can't enroll in this course before completing all of it's prerequisites"
+                    .to_owned(),
+            );
+        }
+    }
+    if sources.contains(&course.teacher_address) {
+        return Ok(());
+    }
+    if !sources.contains(student_address) {
+        return Err(
+            "Only the student or the teacher of the course can enroll a student".to_owned(),
+        );
+    }
+    if course.enrollment_mode == EnrollmentMode::ApprovalRequired {
+        return Err("Students need teacher's approval to enroll in this course".to_owned());
+    }
+    if course.visibility == CourseVisibility::InviteOnly {
+        match invite::handlers::is_invited(&course.anchor_address, student_address) {
+            Ok(true) => {}
+            Ok(false) => {
+                return Err(
+                    "Student needs an invite to enroll in this invite-only course".to_owned(),
+                )
+            }
+            Err(_) => return Err("Can't check if student was invited".to_owned()),
+        }
+    }
+    Ok(())
+}
+
+// students enrolled in the course apart from student_address.
+// Validator may already see the link that's being validated, so the student themselves isn't counted
+fn get_other_students(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> Result<Vec<Address>, String> {
+    let links = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_STUDENT_LINK),
+        LinkMatch::Any,
+    )
+    .map_err(|_| "Can't get students of the course".to_owned())?;
+    Ok(links
+        .addresses()
+        .into_iter()
+        .filter(|enrolled_address| enrolled_address != student_address)
+        .collect())
+}
+
+// when a seat frees up, anyone can enroll students that have been on the waitlist for the longest time.
+// This way the student that leaves the course lets the next one in without waiting for the teacher
+fn validate_promotion(
+    course: &Course,
+    student_address: &Address,
+    other_students: &[Address],
+) -> Result<(), String> {
+    let waitlist = course::handlers::get_waitlist(&course.anchor_address)
+        .map_err(|_| "Can't read the waitlist of the course".to_owned())?;
+    // students that were already promoted may still be on the waitlist for this validator
+    let position = waitlist
+        .iter()
+        .filter(|waitlisted_address| !other_students.contains(*waitlisted_address))
+        .position(|waitlisted_address| waitlisted_address == student_address)
+        .ok_or_else(|| {
+            "Only the student or the teacher of the course can enroll a student".to_owned()
+        })?;
+    let free_seats = match course.max_students {
+        Some(max_students) => (max_students as usize).saturating_sub(other_students.len()),
+        None => waitlist.len(),
+    };
+    if position >= free_seats {
+        return Err(
+            "Students from the waitlist are enrolled in the order they've joined it".to_owned(),
+        );
+    }
+    Ok(())
+}
+
+// checks that a new enrollment link was made by someone allowed to enroll the student
+// and that the course has a free seat for them
+fn validate_enrollment_link(
+    course_anchor_address: &Address,
+    student_address: &Address,
+    sources: &[Address],
+) -> Result<(), String> {
+    let course = match course::handlers::get_latest_course(course_anchor_address) {
+        Ok(Some((course, _course_address))) => course,
+        Ok(None) => return Err("Can't enroll in a deleted course".to_owned()),
+        Err(_) => return Err("Can't find the course".to_owned()),
+    };
+    let other_students = get_other_students(course_anchor_address, student_address)?;
+    if !sources.contains(&course.teacher_address) && !sources.contains(student_address) {
+        return validate_promotion(&course, student_address, &other_students);
+    }
+    validate_enrollment_author(&course, student_address, sources)?;
+    if let Some(max_students) = course.max_students {
+        if other_students.len() >= max_students as usize {
+            return Err("This course has reached it's max_students limit".to_owned());
+        }
+    }
+    Ok(())
+}
+
+// students can only join the waitlist of the course they're allowed to enroll in
+fn validate_waitlist_link(
+    course_anchor_address: &Address,
+    student_address: &Address,
+    sources: &[Address],
+) -> Result<(), String> {
+    let course = match course::handlers::get_latest_course(course_anchor_address) {
+        Ok(Some((course, _course_address))) => course,
+        Ok(None) => return Err("Can't join the waitlist of a deleted course".to_owned()),
+        Err(_) => return Err("Can't find the course".to_owned()),
+    };
+    validate_enrollment_author(&course, student_address, sources)
+}
+
+// waitlist is ordered by the tag, so it has to be the time of the commit.
+// Otherwise student could tag their link with 0 and jump the queue
+fn validate_waitlist_tag(tag: &str, committed_at: &str) -> Result<(), String> {
+    let joined_at = tag
+        .parse::<u64>()
+        .map_err(|_| "Tag of the waitlist link should be a timestamp".to_owned())?;
+    let committed_at = parse_date_time(committed_at)
+        .ok_or_else(|| "Can't read time of joining the waitlist".to_owned())?;
+    if joined_at < committed_at.saturating_sub(MAX_CLOCK_SKEW)
+        || joined_at > committed_at.saturating_add(MAX_CLOCK_SKEW)
+    {
+        return Err("Tag of the waitlist link doesn't match the time it was committed".to_owned());
+    }
+    Ok(())
+}
+
+// students promoted from the waitlist are removed from it by whoever promoted them
+fn validate_waitlist_removal(
+    course_anchor_address: &Address,
+    student_address: &Address,
+    sources: &[Address],
+) -> Result<(), String> {
+    match course::handlers::is_enrolled(course_anchor_address, student_address) {
+        Ok(true) => Ok(()),
+        Ok(false) => validate_student_or_teacher(course_anchor_address, student_address, sources),
+        Err(_) => Err("Can't check if student is enrolled in the course".to_owned()),
+    }
+}
+
+// enrollment and waitlist links go both ways, so is_from_student tells if the base of the link
+// is the student. Only the link from course to student keeps the time of joining the waitlist
+fn validate_student_link_change(
+    validation_data: hdk::LinkValidationData,
+    is_from_student: bool,
+    is_enrollment: bool,
+) -> Result<(), String> {
+    let (link, validation_data, is_removed) = match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => (link, validation_data, false),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => (link, validation_data, true),
+    };
+    let sources = validation_data.sources();
+    let (student_address, course_anchor_address) = if is_from_student {
+        (link.link.base(), link.link.target())
+    } else {
+        (link.link.target(), link.link.base())
+    };
+    match (is_enrollment, is_removed) {
+        (true, false) => validate_enrollment_link(course_anchor_address, student_address, &sources),
+        (true, true) => {
+            validate_student_or_teacher(course_anchor_address, student_address, &sources)
+        }
+        (false, false) => {
+            validate_waitlist_link(course_anchor_address, student_address, &sources)?;
+            if is_from_student {
+                return Ok(());
+            }
+            validate_waitlist_tag(
+                link.link.tag(),
+                &validation_data.package.chain_header.timestamp().to_string(),
+            )
+        }
+        (false, true) => {
+            validate_waitlist_removal(course_anchor_address, student_address, &sources)
+        }
+    }
+}
+
+fn validate_teacher(course_anchor_address: &Address, sources: &[Address]) -> Result<(), String> {
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(course_anchor_address.clone())
+        .map_err(|_| "Can't find the course".to_owned())?;
+    if !sources.contains(&course_anchor.teacher_address) {
+        return Err("Only the teacher of the course can do that".to_owned());
+    }
+    Ok(())
+}
+
+// completion is attested by the teacher, and only students enrolled in the course can complete it.
+// Links go both ways, so is_from_student tells if the base of the link is the student
+fn validate_completion_link_change(
+    validation_data: hdk::LinkValidationData,
+    is_from_student: bool,
+) -> Result<(), String> {
+    let (link, sources, is_removed) = match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => (link, validation_data.sources(), false),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => (link, validation_data.sources(), true),
+    };
+    let (student_address, course_anchor_address) = if is_from_student {
+        (link.link.base(), link.link.target())
+    } else {
+        (link.link.target(), link.link.base())
+    };
+    validate_teacher(course_anchor_address, &sources)?;
+    if is_removed {
+        return Ok(());
+    }
+    match course::handlers::is_enrolled(course_anchor_address, student_address) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Only students enrolled in the course can complete it".to_owned()),
+        Err(_) => Err("Can't check if student is enrolled in the course".to_owned()),
+    }
+}
+
+// only the teacher of the course chooses it's prerequisites
+fn validate_prerequisite_link_change(
+    validation_data: hdk::LinkValidationData,
+) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => {
+            if link.link.base() == link.link.target() {
+                return Err("Course can't be a prerequisite of itself".to_owned());
+            }
+            validate_teacher(link.link.base(), &validation_data.sources())
+        }
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_teacher(link.link.base(), &validation_data.sources()),
+    }
+}
+
 pub fn course_anchor_def() -> ValidatingEntryType {
     entry!(
         name: CourseAnchor::entry_type(),
@@ -93,8 +373,8 @@ pub fn course_anchor_def() -> ValidatingEntryType {
                 validation_package: || {
                     hdk::ValidationPackageDefinition::Entry
                 }              ,
-                validation: | _validation_data: hdk::LinkValidationData | {
-                    Ok(())
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_student_link_change(validation_data, true, true)
                 }
             ),
             // link to an agent who is a student enrolled in this course.
@@ -105,8 +385,69 @@ pub fn course_anchor_def() -> ValidatingEntryType {
                 validation_package: || {
                     hdk::ValidationPackageDefinition::Entry
                 },
-                validation: | _validation_data: hdk::LinkValidationData | {
-                    Ok(())
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_student_link_change(validation_data, false, true)
+                }
+            ),
+            // link from agent that is waiting for a free seat in this course
+            // This is for student to keep track of all the courses they're waitlisted for
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: STUDENT_TO_WAITLISTED_COURSE_ANCHOR_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_student_link_change(validation_data, true, false)
+                }
+            ),
+            // link to an agent that is waiting for a free seat in this course.
+            // Tag of this link is the timestamp of when student joined the waitlist
+            // so we can promote students in the order they've joined. It's checked against the commit time
+            to!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_student_link_change(validation_data, false, false)
+                }
+            ),
+            // link from agent that is a student who completed this course
+            // This is for student to keep track of all the courses they've finished
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: STUDENT_TO_COMPLETED_COURSE_ANCHOR_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_completion_link_change(validation_data, true)
+                }
+            ),
+            // link to an agent who is a student that completed this course.
+            // This is to keep track of course graduates from the course perspective
+            to!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: COURSE_ANCHOR_TO_COMPLETED_STUDENT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_completion_link_change(validation_data, false)
+                }
+            ),
+            // link to another course that should be completed before enrolling in this one.
+            // Together these links form a prerequisite graph of all courses
+            to!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_PREREQUISITE_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_prerequisite_link_change(validation_data)
                 }
             )
         ]
@@ -6,6 +6,91 @@ use hdk::{
 };
 use holochain_entry_utils::HolochainEntry;
 
+// What happens when a student tries to enroll in a course without completing it's prerequisites
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PrerequisitePolicy {
+    // student is enrolled and gets a list of missing prerequisites back
+    Warn,
+    // enrollment fails
+    Block,
+}
+
+impl Default for PrerequisitePolicy {
+    fn default() -> Self {
+        PrerequisitePolicy::Warn
+    }
+}
+
+// Who can find and enroll in a course
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum CourseVisibility {
+    // listed in the course catalog and open to everyone
+    Public,
+    // not listed in the catalog, but everyone who knows course anchor address can enroll
+    Unlisted,
+    // not listed in the catalog and only invited students can enroll
+    InviteOnly,
+}
+
+impl Default for CourseVisibility {
+    fn default() -> Self {
+        CourseVisibility::Public
+    }
+}
+
+// How students get into a course
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum EnrollmentMode {
+    // students are enrolled right away
+    Open,
+    // students create an enrollment request and teacher approves or rejects it
+    ApprovalRequired,
+}
+
+impl Default for EnrollmentMode {
+    fn default() -> Self {
+        EnrollmentMode::Open
+    }
+}
+
+// How much each category of scores contributes to the total grade of a student.
+// Weights are relative to each other, so they don't have to add up to 100
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GradeWeights {
+    pub quizzes: u32,
+    pub assignments: u32,
+    pub progress: u32,
+    pub attendance: u32,
+}
+
+impl Default for GradeWeights {
+    fn default() -> Self {
+        GradeWeights {
+            quizzes: 40,
+            assignments: 40,
+            progress: 20,
+            attendance: 0,
+        }
+    }
+}
+
+impl GradeWeights {
+    // weights are used as divisors of the total grade, so they can't all be zero
+    // and their sum has to fit into u32
+    pub fn validate(&self) -> Result<(), String> {
+        let total_weight = self
+            .quizzes
+            .checked_add(self.assignments)
+            .and_then(|weight| weight.checked_add(self.progress))
+            .and_then(|weight| weight.checked_add(self.attendance))
+            .ok_or_else(|| format!("Grade weights should add up to at most {}", std::u32::MAX))?;
+        if total_weight == 0 {
+            return Err("At least one grade category should have a non-zero weight".to_owned());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
 pub struct Course {
     pub title: String,
@@ -13,6 +98,28 @@ pub struct Course {
     pub teacher_address: Address,
     pub timestamp: u64,
     pub anchor_address: Address,
+    // NOTE: course settings have default values so that Course entries
+    // committed before the setting was introduced can still be read
+    #[serde(default)]
+    pub prerequisite_policy: PrerequisitePolicy,
+    #[serde(default)]
+    pub visibility: CourseVisibility,
+    // hash of the code that lets students enroll in an invite-only course without a personal invite.
+    // We never store the code itself since course entries are public
+    #[serde(default)]
+    pub invite_code_hash: Option<Address>,
+    // how many students can be enrolled at the same time. None means there's no limit.
+    // Students who enroll after the limit is reached are put on the waitlist
+    #[serde(default)]
+    pub max_students: Option<u32>,
+    #[serde(default)]
+    pub enrollment_mode: EnrollmentMode,
+    #[serde(default)]
+    pub grade_weights: GradeWeights,
+    // id of the course this one was imported from, see bundle::import.
+    // It's None for courses that were created here
+    #[serde(default)]
+    pub source_id: Option<String>,
 }
 
 impl HolochainEntry for Course {
@@ -35,6 +142,13 @@ impl Course {
             teacher_address: teacher_address,
             timestamp: timestamp,
             anchor_address: anchor_address,
+            prerequisite_policy: PrerequisitePolicy::default(),
+            visibility: CourseVisibility::default(),
+            invite_code_hash: None,
+            max_students: None,
+            enrollment_mode: EnrollmentMode::default(),
+            grade_weights: GradeWeights::default(),
+            source_id: None,
         }
     }
 }
@@ -1,3 +1,4 @@
+use hdk::holochain_json_api::{error::JsonError, json::JsonString};
 use hdk::holochain_persistence_api::cas::content::Address;
 use hdk::prelude::LinkMatch;
 use hdk::{
@@ -7,15 +8,80 @@ use hdk::{
 use holochain_entry_utils::HolochainEntry;
 
 use super::anchor::{
-    CourseAnchor, COURSE_ANCHOR_TO_STUDENT_LINK, STUDENT_TO_COURSE_ANCHOR_LINK,
+    CourseAnchor, COURSE_ANCHOR_TO_COMPLETED_STUDENT_LINK, COURSE_ANCHOR_TO_STUDENT_LINK,
+    COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK, STUDENT_TO_COMPLETED_COURSE_ANCHOR_LINK,
+    STUDENT_TO_COURSE_ANCHOR_LINK, STUDENT_TO_WAITLISTED_COURSE_ANCHOR_LINK,
     TEACHER_TO_COURSE_ANCHOR_LINK,
 };
 use super::catalog_anchor::CourseCatalogAnchor;
-use super::entry::Course;
+use super::entry::{Course, CourseVisibility, EnrollmentMode, GradeWeights, PrerequisitePolicy};
 use crate::anchor_trait::AnchorTrait;
+use crate::cohort;
+use crate::content;
+use crate::enrollment_request;
 use crate::helper;
+use crate::invite;
+use crate::moderation;
+use crate::moderation::anchor::HIDDEN_COURSE_ANCHOR_LINK;
+use crate::prerequisite;
+use crate::profile;
+use crate::review;
+use crate::section;
+use crate::section::entry::Section;
+use crate::signal;
+use crate::signal::handlers::LeapSignal;
 
-pub fn create(title: String, timestamp: u64) -> ZomeApiResult<Address> {
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct EnrollmentResult {
+    pub course_anchor_address: Address,
+    // prerequisites of this course that student hasn't completed yet.
+    // It's only non-empty if course's prerequisite_policy is PrerequisitePolicy::Warn
+    pub missing_prerequisites: Vec<Address>,
+    // position of the student on the waitlist, starting from 1.
+    // It's None if student was enrolled right away
+    pub waitlist_position: Option<usize>,
+    // address of the enrollment request that teacher has to approve.
+    // It's only Some if course's enrollment_mode is EnrollmentMode::ApprovalRequired
+    pub enrollment_request_address: Option<Address>,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct SectionTreeNode {
+    pub section_anchor_address: Address,
+    pub section: Section,
+    // locked sections aren't released to the student yet, so their contents aren't included
+    pub locked: bool,
+    // when this section is released to the student. It's None if it's not known yet
+    pub release_at: Option<u64>,
+    pub contents: Vec<Address>,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct CourseTree {
+    pub course_anchor_address: Address,
+    pub course: Course,
+    // it's None if teacher hasn't created a profile yet
+    pub teacher_display_name: Option<String>,
+    pub sections: Vec<SectionTreeNode>,
+}
+
+// short description of a course for course lists
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct CourseSummary {
+    pub course_anchor_address: Address,
+    pub title: String,
+    pub teacher_address: Address,
+    // it's None if teacher hasn't created a profile yet
+    pub teacher_display_name: Option<String>,
+    pub section_count: usize,
+    pub student_count: usize,
+}
+
+pub fn create(
+    title: String,
+    timestamp: u64,
+    visibility: CourseVisibility,
+) -> ZomeApiResult<Address> {
     // if catalog anchor already exists, this function would just return it's address without actually writing anything
     // new to the DHT.
     let catalog_anchor_address = hdk::commit_entry(&CourseCatalogAnchor::new().entry())?;
@@ -29,13 +95,14 @@ pub fn create(title: String, timestamp: u64) -> ZomeApiResult<Address> {
     let course_anchor_address = hdk::commit_entry(&course_anchor.entry())?;
 
     // create new Course entry
-    let new_course = Course::new(
+    let mut new_course = Course::new(
         title,
         Vec::default(), // section vector is empty by default
         teacher_address.to_owned().into(),
         timestamp,
         course_anchor_address.clone(),
     );
+    new_course.visibility = visibility.clone();
     // commit this entry to DHT and save it's address
     let new_course_address = hdk::commit_entry(&new_course.entry())?;
 
@@ -47,13 +114,16 @@ pub fn create(title: String, timestamp: u64) -> ZomeApiResult<Address> {
         "".to_owned(),
     )?;
 
-    // link CourseCatalogAnchor to CourseAnchor entry for this course to be findable
-    hdk::link_entries(
-        &catalog_anchor_address,
-        &course_anchor_address,
-        CourseCatalogAnchor::link_type(),
-        "".to_owned(),
-    )?;
+    // link CourseCatalogAnchor to CourseAnchor entry for this course to be findable.
+    // Unlisted and invite-only courses are only reachable by their anchor address
+    if visibility == CourseVisibility::Public {
+        hdk::link_entries(
+            &catalog_anchor_address,
+            &course_anchor_address,
+            CourseCatalogAnchor::link_type(),
+            "".to_owned(),
+        )?;
+    }
 
     // link address of the agent who called course::create to CourseAnchor
     // for this course to be findable in the list of courses that agent teaches
@@ -64,6 +134,10 @@ pub fn create(title: String, timestamp: u64) -> ZomeApiResult<Address> {
         "",
     )?;
 
+    signal::handlers::emit(&LeapSignal::CourseCreated {
+        course_anchor_address: course_anchor_address.clone(),
+    })?;
+
     Ok(course_anchor_address)
 }
 
@@ -101,6 +175,284 @@ fn commit_update(
     Ok(course_anchor_address.to_owned())
 }
 
+// retrieves CourseAnchor and checks that agent that calls this function is the teacher of this course
+pub fn ensure_teacher(course_anchor_address: &Address) -> ZomeApiResult<CourseAnchor> {
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(course_anchor_address.clone())?;
+    if course_anchor.teacher_address != *AGENT_ADDRESS {
+        return Err(ZomeApiError::from(
+            "Only the teacher of this course can do that".to_owned(),
+        ));
+    }
+    Ok(course_anchor)
+}
+
+// applies change_settings to the latest Course entry and commits the result.
+// Only the teacher of the course is allowed to change it's settings
+fn update_settings<F>(course_anchor_address: &Address, change_settings: F) -> ZomeApiResult<Address>
+where
+    F: FnOnce(&mut Course),
+{
+    ensure_teacher(course_anchor_address)?;
+    let latest_course_result = get_latest_course(course_anchor_address)?;
+    match latest_course_result {
+        Some((mut previous_course, previous_course_address)) => {
+            change_settings(&mut previous_course);
+            commit_update(
+                previous_course,
+                &previous_course_address,
+                course_anchor_address,
+            )?;
+
+            signal::handlers::notify_course_students(
+                course_anchor_address,
+                &LeapSignal::CourseUpdated {
+                    course_anchor_address: course_anchor_address.clone(),
+                },
+            )?;
+
+            Ok(course_anchor_address.clone())
+        }
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't change settings of a deleted course".to_owned(),
+            ));
+        }
+    }
+}
+
+pub fn set_prerequisite_policy(
+    course_anchor_address: &Address,
+    prerequisite_policy: PrerequisitePolicy,
+) -> ZomeApiResult<Address> {
+    update_settings(course_anchor_address, |course| {
+        course.prerequisite_policy = prerequisite_policy
+    })
+}
+
+pub fn set_visibility(
+    course_anchor_address: &Address,
+    visibility: CourseVisibility,
+) -> ZomeApiResult<Address> {
+    let previous_visibility = match get_latest_course(course_anchor_address)? {
+        Some((course, _course_address)) => course.visibility,
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't change visibility of a deleted course".to_owned(),
+            ));
+        }
+    };
+    update_settings(course_anchor_address, |course| {
+        course.visibility = visibility.clone()
+    })?;
+
+    // only public courses are listed in the catalog
+    let was_public = previous_visibility == CourseVisibility::Public;
+    let is_public = visibility == CourseVisibility::Public;
+    if was_public && !is_public {
+        hdk::remove_link(
+            &CourseCatalogAnchor::new().address()?,
+            course_anchor_address,
+            CourseCatalogAnchor::link_type(),
+            "".to_owned(),
+        )?;
+    } else if !was_public && is_public {
+        let catalog_anchor_address = hdk::commit_entry(&CourseCatalogAnchor::new().entry())?;
+        hdk::link_entries(
+            &catalog_anchor_address,
+            course_anchor_address,
+            CourseCatalogAnchor::link_type(),
+            "".to_owned(),
+        )?;
+    }
+
+    Ok(course_anchor_address.clone())
+}
+
+pub fn set_enrollment_mode(
+    course_anchor_address: &Address,
+    enrollment_mode: EnrollmentMode,
+) -> ZomeApiResult<Address> {
+    update_settings(course_anchor_address, |course| {
+        course.enrollment_mode = enrollment_mode
+    })
+}
+
+pub fn set_grade_weights(
+    course_anchor_address: &Address,
+    grade_weights: GradeWeights,
+) -> ZomeApiResult<Address> {
+    grade_weights.validate().map_err(ZomeApiError::from)?;
+    update_settings(course_anchor_address, |course| {
+        course.grade_weights = grade_weights
+    })
+}
+
+// applies settings of an imported course in a single update
+pub fn set_imported_settings(
+    course_anchor_address: &Address,
+    prerequisite_policy: PrerequisitePolicy,
+    max_students: Option<u32>,
+    enrollment_mode: EnrollmentMode,
+    grade_weights: GradeWeights,
+) -> ZomeApiResult<Address> {
+    update_settings(course_anchor_address, |course| {
+        course.prerequisite_policy = prerequisite_policy;
+        course.max_students = max_students;
+        course.enrollment_mode = enrollment_mode;
+        course.grade_weights = grade_weights;
+    })
+}
+
+// records where the course was imported from. It's the last step of the import,
+// so a course that failed to import halfway isn't mistaken for a finished one
+pub fn set_source_id(course_anchor_address: &Address, source_id: String) -> ZomeApiResult<Address> {
+    update_settings(course_anchor_address, |course| {
+        course.source_id = Some(source_id)
+    })
+}
+
+// finds a course of the agent that calls this function that was imported from source_id
+pub fn get_my_imported_course(source_id: &str) -> ZomeApiResult<Option<Address>> {
+    for course_anchor_address in get_my_courses()? {
+        if let Some((course, _course_address)) = get_latest_course(&course_anchor_address)? {
+            if course.source_id.as_deref() == Some(source_id) {
+                return Ok(Some(course_anchor_address));
+            }
+        }
+    }
+    Ok(None)
+}
+
+// sets how many students can be enrolled at the same time. None removes the limit.
+// If the new limit is higher, students from the waitlist take the free seats
+pub fn set_max_students(
+    course_anchor_address: &Address,
+    max_students: Option<u32>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    update_settings(course_anchor_address, |course| {
+        course.max_students = max_students
+    })?;
+    promote_waitlisted(course_anchor_address, timestamp)?;
+    Ok(course_anchor_address.clone())
+}
+
+// sets a code that lets students enroll in an invite-only course. None disables the code
+pub fn set_invite_code(
+    course_anchor_address: &Address,
+    invite_code: Option<String>,
+) -> ZomeApiResult<Address> {
+    let invite_code_hash = match invite_code {
+        Some(code) => {
+            invite::handlers::ensure_strong_invite_code(&code)?;
+            Some(invite::handlers::hash_invite_code(
+                course_anchor_address,
+                code,
+            )?)
+        }
+        None => None,
+    };
+    update_settings(course_anchor_address, |course| {
+        course.invite_code_hash = invite_code_hash
+    })
+}
+
+// builds section of the course tree as seen at the given moment by the student who enrolled
+// at enrollment_timestamp, or by the teacher who sees everything
+pub fn build_section_node(
+    section_anchor_address: Address,
+    section: Section,
+    is_teacher: bool,
+    enrollment_timestamp: Option<u64>,
+    timestamp: u64,
+) -> ZomeApiResult<SectionTreeNode> {
+    let release_at = section::handlers::get_release_time(&section, enrollment_timestamp);
+    let locked = !is_teacher
+        && match release_at {
+            Some(release_at) => release_at > timestamp,
+            None => true,
+        };
+    let contents = if locked {
+        Vec::new()
+    } else {
+        content::handlers::get_contents(&section_anchor_address)?
+    };
+    Ok(SectionTreeNode {
+        section_anchor_address: section_anchor_address,
+        section: section,
+        locked: locked,
+        release_at: release_at,
+        contents: contents,
+    })
+}
+
+// returns course together with it's sections and their contents as seen by the agent that calls
+// this function at the given moment. Teacher sees everything, students see sections that aren't
+// released yet as locked
+pub fn get_course_tree(
+    course_anchor_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<Option<CourseTree>> {
+    let course = match get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Ok(None),
+    };
+    let is_teacher = course.teacher_address == *AGENT_ADDRESS;
+    let enrollment_timestamp = get_enrollment_timestamp(&course_anchor_address, &AGENT_ADDRESS)?;
+
+    let mut sections = Vec::new();
+    for section_anchor_address in course.sections.iter() {
+        let section = match section::handlers::get_latest_section(section_anchor_address)? {
+            Some((section, _section_address)) => section,
+            // skip sections that were deleted
+            None => continue,
+        };
+        sections.push(build_section_node(
+            section_anchor_address.clone(),
+            section,
+            is_teacher,
+            enrollment_timestamp,
+            timestamp,
+        )?);
+    }
+
+    Ok(Some(CourseTree {
+        course_anchor_address: course_anchor_address,
+        teacher_display_name: profile::handlers::get_display_name(&course.teacher_address)?,
+        course: course,
+        sections: sections,
+    }))
+}
+
+pub fn get_course_summary(course_anchor_address: Address) -> ZomeApiResult<Option<CourseSummary>> {
+    let course = match get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Ok(None),
+    };
+    Ok(Some(CourseSummary {
+        teacher_display_name: profile::handlers::get_display_name(&course.teacher_address)?,
+        student_count: get_student_count(&course_anchor_address)?,
+        section_count: course.sections.len(),
+        course_anchor_address: course_anchor_address,
+        title: course.title,
+        teacher_address: course.teacher_address,
+    }))
+}
+
+// returns summaries of the given courses in the same order, skipping deleted ones.
+// This is meant to be used with list_all_courses and other functions that only return addresses
+pub fn get_course_summaries(
+    course_anchor_addresses: Vec<Address>,
+) -> ZomeApiResult<Vec<CourseSummary>> {
+    let mut course_summaries = Vec::new();
+    for course_anchor_address in course_anchor_addresses {
+        if let Some(course_summary) = get_course_summary(course_anchor_address)? {
+            course_summaries.push(course_summary);
+        }
+    }
+    Ok(course_summaries)
+}
+
 pub fn update(
     title: String,
     // NOTE(e-nastasia): since we have separate methods for section management
@@ -122,6 +474,13 @@ pub fn update(
                 course_anchor_address,
             )?;
 
+            signal::handlers::notify_course_students(
+                course_anchor_address,
+                &LeapSignal::CourseUpdated {
+                    course_anchor_address: course_anchor_address.clone(),
+                },
+            )?;
+
             // returning address of the course anchor. Sure, it doesn't change, but it makes our API consistent with hdk:: API
             // that always returns address of an updated entry
             return Ok(course_anchor_address.clone());
@@ -148,6 +507,14 @@ pub fn delete(course_anchor_address: Address) -> ZomeApiResult<Address> {
 
     // retrieve list of students that have enrolled in this course
     let students = get_students(course_anchor_address.clone())?;
+
+    // let students know that course is gone while we still have the list of them
+    let course_deleted_signal = LeapSignal::CourseDeleted {
+        course_anchor_address: course_anchor_address.clone(),
+    };
+    signal::handlers::emit(&course_deleted_signal)?;
+    signal::handlers::send_to_agents(students.clone(), &course_deleted_signal)?;
+
     // go through all students and remove their links to this course
     for student in students {
         hdk::remove_link(
@@ -182,7 +549,34 @@ pub fn list_all_courses() -> ZomeApiResult<Vec<Address>> {
     )?
     .addresses();
 
-    Ok(addresses)
+    // courses hidden by moderators stay reachable by their address but aren't listed
+    let hidden_courses = moderation::handlers::get_hidden(HIDDEN_COURSE_ANCHOR_LINK)?;
+    Ok(addresses
+        .into_iter()
+        .filter(|address| !hidden_courses.contains(address))
+        .collect())
+}
+
+// returns all courses sorted by their average rating, highest rated courses first.
+// Courses with the same rating are ordered by the number of reviews
+pub fn list_all_courses_by_rating() -> ZomeApiResult<Vec<Address>> {
+    let mut rated_courses = Vec::new();
+    for course_anchor_address in list_all_courses()? {
+        let rating = review::handlers::get_course_rating(&course_anchor_address)?;
+        rated_courses.push((course_anchor_address, rating));
+    }
+
+    rated_courses.sort_by(|(_, a), (_, b)| {
+        b.average
+            .partial_cmp(&a.average)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.count.cmp(&a.count))
+    });
+
+    Ok(rated_courses
+        .into_iter()
+        .map(|(course_anchor_address, _rating)| course_anchor_address)
+        .collect())
 }
 
 pub fn get_my_courses() -> ZomeApiResult<Vec<Address>> {
@@ -215,6 +609,416 @@ pub fn get_students(course_anchor_address: Address) -> ZomeApiResult<Vec<Address
     Ok(links.addresses())
 }
 
+// checks if agent at agent_address is enrolled in the course
+pub fn is_enrolled(
+    course_anchor_address: &Address,
+    agent_address: &Address,
+) -> ZomeApiResult<bool> {
+    let links = hdk::get_links(
+        agent_address,
+        LinkMatch::Exactly(STUDENT_TO_COURSE_ANCHOR_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses().contains(course_anchor_address))
+}
+
+// finds the tag of the link from base to target. We need it to remove links that are tagged with timestamps
+fn get_link_tag(
+    base_address: &Address,
+    link_type: &str,
+    target_address: &Address,
+) -> ZomeApiResult<Option<String>> {
+    let links = hdk::get_links(base_address, LinkMatch::Exactly(link_type), LinkMatch::Any)?;
+    Ok(links
+        .links()
+        .into_iter()
+        .find(|link| link.address == *target_address)
+        .map(|link| link.tag))
+}
+
+// moment when student at student_address enrolled in the course, or None if they aren't enrolled
+pub fn get_enrollment_timestamp(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<u64>> {
+    let tag = get_link_tag(
+        course_anchor_address,
+        COURSE_ANCHOR_TO_STUDENT_LINK,
+        student_address,
+    )?;
+    // NOTE: students who enrolled before enrollment links were tagged with timestamps
+    // are treated as if they've enrolled at the very beginning
+    Ok(tag.map(|tag| tag.parse::<u64>().unwrap_or(0)))
+}
+
+pub fn get_student_count(course_anchor_address: &Address) -> ZomeApiResult<usize> {
+    let links_count = hdk::get_links_count(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_STUDENT_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links_count.count)
+}
+
+// checks if one more student can be enrolled in the course without exceeding it's max_students limit
+pub fn has_free_seat(course: &Course, course_anchor_address: &Address) -> ZomeApiResult<bool> {
+    match course.max_students {
+        Some(max_students) => Ok(get_student_count(course_anchor_address)? < max_students as usize),
+        None => Ok(true),
+    }
+}
+
+// returns waitlisted students in the order they've joined the waitlist
+pub fn get_waitlist(course_anchor_address: &Address) -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK),
+        LinkMatch::Any,
+    )?;
+
+    let mut waitlist: Vec<(u64, Address)> = links
+        .links()
+        .into_iter()
+        .map(|link| (link.tag.parse::<u64>().unwrap_or(0), link.address))
+        .collect();
+    // students that joined at the same time are ordered by address so everyone sees the same order
+    waitlist.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.to_string().cmp(&b.1.to_string()))
+    });
+
+    Ok(waitlist
+        .into_iter()
+        .map(|(_timestamp, student_address)| student_address)
+        .collect())
+}
+
+// position of the student on the course waitlist starting from 1, or None if they aren't waitlisted
+pub fn get_waitlist_position(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<usize>> {
+    let waitlist = get_waitlist(course_anchor_address)?;
+    Ok(waitlist
+        .iter()
+        .position(|waitlisted_address| waitlisted_address == student_address)
+        .map(|index| index + 1))
+}
+
+pub fn get_my_waitlist_position(course_anchor_address: &Address) -> ZomeApiResult<Option<usize>> {
+    get_waitlist_position(course_anchor_address, &AGENT_ADDRESS)
+}
+
+pub fn get_my_waitlisted_courses() -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(STUDENT_TO_WAITLISTED_COURSE_ANCHOR_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+// links waitlisted student to the course in both directions.
+// Link from course to student is tagged with the timestamp of joining the waitlist
+fn add_to_waitlist(
+    course_anchor_address: &Address,
+    student_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<()> {
+    hdk::link_entries(
+        student_address,
+        course_anchor_address,
+        STUDENT_TO_WAITLISTED_COURSE_ANCHOR_LINK,
+        "",
+    )?;
+    hdk::link_entries(
+        course_anchor_address,
+        student_address,
+        COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK,
+        timestamp.to_string().as_str(),
+    )?;
+    Ok(())
+}
+
+fn remove_from_waitlist(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<()> {
+    hdk::remove_link(
+        student_address,
+        course_anchor_address,
+        STUDENT_TO_WAITLISTED_COURSE_ANCHOR_LINK,
+        "",
+    )?;
+    if let Some(tag) = get_link_tag(
+        course_anchor_address,
+        COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK,
+        student_address,
+    )? {
+        hdk::remove_link(
+            course_anchor_address,
+            student_address,
+            COURSE_ANCHOR_TO_WAITLISTED_STUDENT_LINK,
+            tag.as_str(),
+        )?;
+    }
+    Ok(())
+}
+
+// enrolls students from the waitlist while there are free seats in the course.
+// It's called by the teacher when they add seats and by students who leave the course
+pub fn promote_waitlisted(course_anchor_address: &Address, timestamp: u64) -> ZomeApiResult<()> {
+    let course = match get_latest_course(course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Ok(()),
+    };
+
+    for student_address in get_waitlist(course_anchor_address)? {
+        if !has_free_seat(&course, course_anchor_address)? {
+            break;
+        }
+        remove_from_waitlist(course_anchor_address, &student_address)?;
+        add_student(course_anchor_address, &student_address, timestamp)?;
+        cohort::handlers::join_pending_cohort(course_anchor_address, &student_address, timestamp)?;
+        notify_student_enrolled(&course, course_anchor_address, &student_address)?;
+    }
+    Ok(())
+}
+
+// lets our UI, the student and the teacher know about the new enrollment
+fn notify_student_enrolled(
+    course: &Course,
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<()> {
+    let student_enrolled_signal = LeapSignal::StudentEnrolled {
+        course_anchor_address: course_anchor_address.clone(),
+        student_address: student_address.clone(),
+    };
+    signal::handlers::emit(&student_enrolled_signal)?;
+    signal::handlers::send_to_agents(
+        vec![student_address.clone(), course.teacher_address.clone()],
+        &student_enrolled_signal,
+    )
+}
+
+// enrolls student in the course if there's a free seat or puts them on the waitlist otherwise.
+// Returns student's waitlist position if they were waitlisted
+pub fn admit_student(
+    course: &Course,
+    course_anchor_address: &Address,
+    student_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<Option<usize>> {
+    if !has_free_seat(course, course_anchor_address)? {
+        add_to_waitlist(course_anchor_address, student_address, timestamp)?;
+        return get_waitlist_position(course_anchor_address, student_address);
+    }
+
+    add_student(course_anchor_address, student_address, timestamp)?;
+    cohort::handlers::join_pending_cohort(course_anchor_address, student_address, timestamp)?;
+    notify_student_enrolled(course, course_anchor_address, student_address)?;
+    Ok(None)
+}
+
+// links student to the course in both directions.
+// Link from course to student is tagged with the enrollment timestamp
+fn add_student(
+    course_anchor_address: &Address,
+    student_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<()> {
+    hdk::link_entries(
+        student_address,
+        course_anchor_address,
+        STUDENT_TO_COURSE_ANCHOR_LINK,
+        "",
+    )?;
+    hdk::link_entries(
+        course_anchor_address,
+        student_address,
+        COURSE_ANCHOR_TO_STUDENT_LINK,
+        timestamp.to_string().as_str(),
+    )?;
+    Ok(())
+}
+
+fn remove_student(course_anchor_address: &Address, student_address: &Address) -> ZomeApiResult<()> {
+    hdk::remove_link(
+        student_address,
+        course_anchor_address,
+        STUDENT_TO_COURSE_ANCHOR_LINK,
+        "",
+    )?;
+    if let Some(tag) = get_link_tag(
+        course_anchor_address,
+        COURSE_ANCHOR_TO_STUDENT_LINK,
+        student_address,
+    )? {
+        hdk::remove_link(
+            course_anchor_address,
+            student_address,
+            COURSE_ANCHOR_TO_STUDENT_LINK,
+            tag.as_str(),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn enroll(
+    course_anchor_address: Address,
+    timestamp: u64,
+    invite_code: Option<String>,
+) -> ZomeApiResult<EnrollmentResult> {
+    let latest_course_result = get_latest_course(&course_anchor_address)?;
+    let course = match latest_course_result {
+        Some((course, _course_address)) => course,
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't enroll in a deleted course".to_owned(),
+            ));
+        }
+    };
+
+    if is_enrolled(&course_anchor_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "You're already enrolled in this course".to_owned(),
+        ));
+    }
+    if get_waitlist_position(&course_anchor_address, &AGENT_ADDRESS)?.is_some() {
+        return Err(ZomeApiError::from(
+            "You're already on the waitlist of this course".to_owned(),
+        ));
+    }
+
+    if course.visibility == CourseVisibility::InviteOnly
+        && !invite::handlers::can_join(&course, invite_code, timestamp)?
+    {
+        return Err(ZomeApiError::from(
+            "This course is invite-only. You need an invite or a valid invite code to enroll"
+                .to_owned(),
+        ));
+    }
+
+    let missing_prerequisites =
+        prerequisite::handlers::get_missing_prerequisites(&course_anchor_address, &AGENT_ADDRESS)?;
+    if !missing_prerequisites.is_empty() && course.prerequisite_policy == PrerequisitePolicy::Block
+    {
+        return Err(ZomeApiError::from(
+            "Can't enroll in this course before completing all of it's prerequisites".to_owned(),
+        ));
+    }
+
+    if course.enrollment_mode == EnrollmentMode::ApprovalRequired {
+        let enrollment_request_address =
+            enrollment_request::handlers::request(&course, &course_anchor_address, timestamp)?;
+        return Ok(EnrollmentResult {
+            course_anchor_address: course_anchor_address,
+            missing_prerequisites: missing_prerequisites,
+            waitlist_position: None,
+            enrollment_request_address: Some(enrollment_request_address),
+        });
+    }
+
+    let waitlist_position =
+        admit_student(&course, &course_anchor_address, &AGENT_ADDRESS, timestamp)?;
+
+    Ok(EnrollmentResult {
+        course_anchor_address: course_anchor_address,
+        missing_prerequisites: missing_prerequisites,
+        waitlist_position: waitlist_position,
+        enrollment_request_address: None,
+    })
+}
+
+// removes the agent that calls this function from the course or from it's waitlist.
+// Seat that was freed goes to the student who has been on the waitlist for the longest time
+pub fn unenroll(course_anchor_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+    if is_enrolled(&course_anchor_address, &AGENT_ADDRESS)? {
+        remove_student(&course_anchor_address, &AGENT_ADDRESS)?;
+        cohort::handlers::leave(&course_anchor_address, &AGENT_ADDRESS)?;
+        let course_anchor: CourseAnchor = hdk::utils::get_as_type(course_anchor_address.clone())?;
+        let student_unenrolled_signal = LeapSignal::StudentUnenrolled {
+            course_anchor_address: course_anchor_address.clone(),
+            student_address: AGENT_ADDRESS.clone(),
+        };
+        signal::handlers::emit(&student_unenrolled_signal)?;
+        signal::handlers::send_to_agents(
+            vec![course_anchor.teacher_address],
+            &student_unenrolled_signal,
+        )?;
+        promote_waitlisted(&course_anchor_address, timestamp)?;
+    } else if get_waitlist_position(&course_anchor_address, &AGENT_ADDRESS)?.is_some() {
+        remove_from_waitlist(&course_anchor_address, &AGENT_ADDRESS)?;
+        cohort::handlers::forget_pending_cohort(&course_anchor_address, &AGENT_ADDRESS)?;
+    } else {
+        return Err(ZomeApiError::from(
+            "You're not enrolled in this course".to_owned(),
+        ));
+    }
+
+    Ok(course_anchor_address)
+}
+
+// marks course as completed by the student at student_address.
+// Only the teacher can do this, so students can't skip prerequisites by completing courses themselves
+pub fn complete(
+    course_anchor_address: Address,
+    student_address: Address,
+) -> ZomeApiResult<Address> {
+    ensure_teacher(&course_anchor_address)?;
+    if !is_enrolled(&course_anchor_address, &student_address)? {
+        return Err(ZomeApiError::from(
+            "Only students enrolled in this course can complete it".to_owned(),
+        ));
+    }
+    if has_completed(&course_anchor_address, &student_address)? {
+        return Ok(course_anchor_address);
+    }
+
+    hdk::link_entries(
+        &student_address,
+        &course_anchor_address,
+        STUDENT_TO_COMPLETED_COURSE_ANCHOR_LINK,
+        "",
+    )?;
+    hdk::link_entries(
+        &course_anchor_address,
+        &student_address,
+        COURSE_ANCHOR_TO_COMPLETED_STUDENT_LINK,
+        "",
+    )?;
+
+    Ok(course_anchor_address)
+}
+
+// checks if agent at agent_address has completed the course
+pub fn has_completed(
+    course_anchor_address: &Address,
+    agent_address: &Address,
+) -> ZomeApiResult<bool> {
+    let links = hdk::get_links(
+        agent_address,
+        LinkMatch::Exactly(STUDENT_TO_COMPLETED_COURSE_ANCHOR_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses().contains(course_anchor_address))
+}
+
+pub fn get_my_completed_courses() -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(STUDENT_TO_COMPLETED_COURSE_ANCHOR_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
 pub fn add_section(
     course_anchor_address: &Address,
     section_anchor_address: &Address,
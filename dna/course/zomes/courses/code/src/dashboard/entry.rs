@@ -0,0 +1,127 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::calendar::ical::parse_date_time;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_COURSE_STATS_LINK: &str = "course_anchor->course_stats";
+
+// Cached statistics of a course for the teacher dashboard.
+// Computing them means reading every student's results, so teacher commits them
+// with dashboard::handlers::refresh_course_stats and dashboard uses them until they get
+// older than dashboard::handlers::STATS_MAX_AGE.
+// NOTE: the entry is public, so these counts are readable by anyone, not only by the teacher.
+// They're aggregates of data that is public in the DHT anyway
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct CourseStats {
+    pub course_anchor_address: Address,
+    pub title: String,
+    pub student_count: usize,
+    pub completed_count: usize,
+    // percentage of enrolled students that completed the course. It's None if there are no students
+    pub completion_rate: Option<f64>,
+    // average of students' quiz scores. It's None if nobody has taken a quiz yet
+    pub average_quiz_score: Option<f64>,
+    // it's None if course doesn't have any reviews yet
+    pub average_rating: Option<f64>,
+    pub review_count: usize,
+    pub announcement_count: usize,
+    // flags which targets aren't hidden yet
+    pub pending_flag_count: usize,
+    pub computed_at: u64,
+}
+
+impl HolochainEntry for CourseStats {
+    fn entry_type() -> String {
+        String::from("course_stats")
+    }
+}
+
+// how far the clock of the teacher can be off when we compare computed_at with the commit time, in seconds
+const MAX_CLOCK_SKEW: u64 = 5 * 60;
+
+fn validate_author(stats: &CourseStats, sources: &[Address]) -> Result<(), String> {
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(stats.course_anchor_address.clone())
+        .map_err(|_| "Can't find the course of these stats".to_owned())?;
+    if !sources.contains(&course_anchor.teacher_address) {
+        return Err("Course stats can only be computed by the teacher of the course".to_owned());
+    }
+    Ok(())
+}
+
+// stats from the future would never get stale, so computed_at can't be later than the commit
+fn validate_computed_at(stats: &CourseStats, committed_at: &str) -> Result<(), String> {
+    let committed_at = parse_date_time(committed_at)
+        .ok_or_else(|| "Can't read time of the course stats".to_owned())?;
+    if stats.computed_at > committed_at.saturating_add(MAX_CLOCK_SKEW) {
+        return Err("Course stats can't be computed in the future".to_owned());
+    }
+    Ok(())
+}
+
+// only the teacher can link stats to their course, and only stats of that course
+fn validate_stats_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let stats: CourseStats = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked course stats".to_owned())?;
+    if *link_data.link.base() != stats.course_anchor_address {
+        return Err("Course stats can only be linked from their course".to_owned());
+    }
+    validate_author(&stats, sources)
+}
+
+fn validate_stats_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_stats_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_stats_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for CourseStats
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: CourseStats::entry_type(),
+        description: "this is the definition of cached statistics of a course",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<CourseStats>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_author(&entry, &validation_data.sources())?;
+                    validate_computed_at(
+                        &entry,
+                        &validation_data.package.chain_header.timestamp().to_string(),
+                    )
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Course stats can't be modified, compute new ones instead".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course these stats belong to. Only the latest stats are linked
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_COURSE_STATS_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_stats_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
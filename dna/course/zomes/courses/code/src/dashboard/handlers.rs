@@ -0,0 +1,176 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{CourseStats, COURSE_ANCHOR_TO_COURSE_STATS_LINK};
+use crate::announcement;
+use crate::course;
+use crate::moderation;
+use crate::quiz;
+use crate::review;
+
+// stats older than this aren't shown on the dashboard, it computes fresh ones instead (in seconds)
+pub const STATS_MAX_AGE: u64 = 60 * 60;
+
+fn get_average(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
+fn compute_stats(
+    course_anchor_address: &Address,
+    title: String,
+    timestamp: u64,
+) -> ZomeApiResult<CourseStats> {
+    let students = course::handlers::get_students(course_anchor_address.clone())?;
+
+    let mut completed_count = 0;
+    let mut quiz_scores = Vec::new();
+    for student_address in students.iter() {
+        if course::handlers::has_completed(course_anchor_address, student_address)? {
+            completed_count += 1;
+        }
+        if let Some(quiz_score) =
+            quiz::handlers::get_student_score(course_anchor_address, student_address)?
+        {
+            quiz_scores.push(quiz_score);
+        }
+    }
+    let completion_rate = if students.is_empty() {
+        None
+    } else {
+        Some(completed_count as f64 * 100.0 / students.len() as f64)
+    };
+
+    let rating = review::handlers::get_course_rating(course_anchor_address)?;
+    let average_rating = if rating.count == 0 {
+        None
+    } else {
+        Some(rating.average)
+    };
+
+    Ok(CourseStats {
+        course_anchor_address: course_anchor_address.clone(),
+        title: title,
+        student_count: students.len(),
+        completed_count: completed_count,
+        completion_rate: completion_rate,
+        average_quiz_score: get_average(&quiz_scores),
+        average_rating: average_rating,
+        review_count: rating.count,
+        announcement_count: announcement::handlers::list(course_anchor_address, 0)?.len(),
+        pending_flag_count: moderation::handlers::list_pending_course_flags(course_anchor_address)?
+            .len(),
+        computed_at: timestamp,
+    })
+}
+
+// retrieves the latest cached stats of the course together with their address
+fn get_cached_stats(
+    course_anchor_address: &Address,
+) -> ZomeApiResult<Option<(CourseStats, Address)>> {
+    let stats_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_COURSE_STATS_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut cached_stats: Option<(CourseStats, Address)> = None;
+    for stats_address in stats_addresses {
+        let stats: CourseStats = hdk::utils::get_as_type(stats_address.clone())?;
+        // stats of another course could only be linked here by mistake
+        if stats.course_anchor_address != *course_anchor_address {
+            continue;
+        }
+        let is_newer = match &cached_stats {
+            Some((latest_stats, _latest_stats_address)) => {
+                stats.computed_at > latest_stats.computed_at
+            }
+            None => true,
+        };
+        if is_newer {
+            cached_stats = Some((stats, stats_address));
+        }
+    }
+    Ok(cached_stats)
+}
+
+// computes stats of the course and replaces the cached ones with them
+pub fn refresh_course_stats(
+    course_anchor_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<CourseStats> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Err(ZomeApiError::from("Can't find this course".to_owned())),
+    };
+
+    let stats = compute_stats(&course_anchor_address, course.title, timestamp)?;
+    let stats_address = hdk::commit_entry(&stats.entry())?;
+
+    if let Some((_previous_stats, previous_stats_address)) =
+        get_cached_stats(&course_anchor_address)?
+    {
+        hdk::remove_link(
+            &course_anchor_address,
+            &previous_stats_address,
+            COURSE_ANCHOR_TO_COURSE_STATS_LINK,
+            "",
+        )?;
+    }
+    hdk::link_entries(
+        &course_anchor_address,
+        &stats_address,
+        COURSE_ANCHOR_TO_COURSE_STATS_LINK,
+        "",
+    )?;
+
+    Ok(stats)
+}
+
+// cached stats are fresh if they were computed within STATS_MAX_AGE before timestamp.
+// Stats from after timestamp are treated as stale too, so a wrong clock can't pin them forever
+fn is_fresh(stats: &CourseStats, timestamp: u64) -> bool {
+    stats.computed_at <= timestamp && stats.computed_at.saturating_add(STATS_MAX_AGE) > timestamp
+}
+
+// returns stats of every course that agent that calls this function teaches.
+// Cached stats are used unless they're stale, then stats are computed without caching them:
+// this function only reads, call refresh_stale_course_stats to update the cache
+pub fn get_teacher_dashboard(timestamp: u64) -> ZomeApiResult<Vec<CourseStats>> {
+    let mut dashboard = Vec::new();
+    for course_anchor_address in course::handlers::get_my_courses()? {
+        let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+            Some((course, _course_address)) => course,
+            // skip deleted courses
+            None => continue,
+        };
+        let stats = match get_cached_stats(&course_anchor_address)? {
+            Some((stats, _stats_address)) if is_fresh(&stats, timestamp) => stats,
+            _ => compute_stats(&course_anchor_address, course.title, timestamp)?,
+        };
+        dashboard.push(stats);
+    }
+    Ok(dashboard)
+}
+
+// refreshes cached stats of every course that agent that calls this function teaches
+// if they're missing or stale. Returns stats that were refreshed.
+// UI calls it after get_teacher_dashboard so the next dashboard can use the cache
+pub fn refresh_stale_course_stats(timestamp: u64) -> ZomeApiResult<Vec<CourseStats>> {
+    let mut refreshed_stats = Vec::new();
+    for course_anchor_address in course::handlers::get_my_courses()? {
+        let is_stale = match get_cached_stats(&course_anchor_address)? {
+            Some((stats, _stats_address)) => !is_fresh(&stats, timestamp),
+            None => true,
+        };
+        // skip deleted courses
+        if is_stale && course::handlers::get_latest_course(&course_anchor_address)?.is_some() {
+            refreshed_stats.push(refresh_course_stats(course_anchor_address, timestamp)?);
+        }
+    }
+    Ok(refreshed_stats)
+}
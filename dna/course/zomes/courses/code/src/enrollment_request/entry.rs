@@ -0,0 +1,115 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK: &str = "course_anchor->enrollment_request";
+pub const STUDENT_TO_ENROLLMENT_REQUEST_LINK: &str = "student->enrollment_request";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum EnrollmentRequestStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+// Request of a student to join a course with EnrollmentMode::ApprovalRequired.
+// Teacher decides on it by updating it's status
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct EnrollmentRequest {
+    pub course_anchor_address: Address,
+    pub student_address: Address,
+    pub timestamp: u64,
+    pub status: EnrollmentRequestStatus,
+}
+
+impl EnrollmentRequest {
+    pub fn new(course_anchor_address: Address, student_address: Address, timestamp: u64) -> Self {
+        EnrollmentRequest {
+            course_anchor_address: course_anchor_address,
+            student_address: student_address,
+            timestamp: timestamp,
+            status: EnrollmentRequestStatus::Pending,
+        }
+    }
+}
+
+impl HolochainEntry for EnrollmentRequest {
+    fn entry_type() -> String {
+        String::from("enrollment_request")
+    }
+}
+
+// Holochain entry definition for EnrollmentRequest
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: EnrollmentRequest::entry_type(),
+        description: "this is the definition of a student's request to enroll in a course",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<EnrollmentRequest>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if !validation_data.sources().contains(&entry.student_address) {
+                        return Err("Enrollment request can only be created by the student it belongs to".to_owned());
+                    }
+                    if entry.status != EnrollmentRequestStatus::Pending {
+                        return Err("New enrollment request has to be pending".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    if new_entry.course_anchor_address != old_entry.course_anchor_address
+                        || new_entry.student_address != old_entry.student_address
+                        || new_entry.timestamp != old_entry.timestamp
+                    {
+                        return Err("Only the status of enrollment request can be changed".to_owned());
+                    }
+                    if old_entry.status != EnrollmentRequestStatus::Pending
+                        || new_entry.status == EnrollmentRequestStatus::Pending
+                    {
+                        return Err("Enrollment request can only be decided on once".to_owned());
+                    }
+                    // NOTE: course anchor stores teacher_address and it never changes, so we can rely on it here
+                    let course_anchor: CourseAnchor = hdk::utils::get_as_type(old_entry.course_anchor_address.clone())
+                        .map_err(|_| "Can't find the course of this enrollment request".to_owned())?;
+                    if !validation_data.sources().contains(&course_anchor.teacher_address) {
+                        return Err("Only the teacher of the course can decide on enrollment requests".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course that student wants to join. Only pending requests stay linked.
+            // Tag of this link is an address of the student so we can quickly find their request
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            ),
+            // link from the student who created the request so they can follow it's status
+            from!(
+                "%agent_id",
+                link_type: STUDENT_TO_ENROLLMENT_REQUEST_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,196 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{
+    EnrollmentRequest, EnrollmentRequestStatus, COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK,
+    STUDENT_TO_ENROLLMENT_REQUEST_LINK,
+};
+use crate::cohort;
+use crate::course;
+use crate::course::entry::Course;
+use crate::signal;
+use crate::signal::handlers::LeapSignal;
+
+// creates a pending enrollment request of the agent that calls this function.
+// Checks that course allows this student to enroll are done in course::handlers::enroll
+pub fn request(
+    course: &Course,
+    course_anchor_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let student_tag = AGENT_ADDRESS.to_string();
+    let pending_request_count = hdk::get_links_count(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK),
+        LinkMatch::Exactly(&student_tag),
+    )?
+    .count;
+    if pending_request_count > 0 {
+        return Err(ZomeApiError::from(
+            "You've already requested to enroll in this course".to_owned(),
+        ));
+    }
+
+    let enrollment_request = EnrollmentRequest::new(
+        course_anchor_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let enrollment_request_address = hdk::commit_entry(&enrollment_request.entry())?;
+
+    hdk::link_entries(
+        course_anchor_address,
+        &enrollment_request_address,
+        COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK,
+        student_tag.as_str(),
+    )?;
+    hdk::link_entries(
+        &AGENT_ADDRESS,
+        &enrollment_request_address,
+        STUDENT_TO_ENROLLMENT_REQUEST_LINK,
+        "",
+    )?;
+
+    let enrollment_requested_signal = LeapSignal::EnrollmentRequested {
+        course_anchor_address: course_anchor_address.clone(),
+        enrollment_request_address: enrollment_request_address.clone(),
+    };
+    signal::handlers::emit(&enrollment_requested_signal)?;
+    signal::handlers::send_to_agents(
+        vec![course.teacher_address.clone()],
+        &enrollment_requested_signal,
+    )?;
+
+    Ok(enrollment_request_address)
+}
+
+// NOTE: get_entry follows updates, so this always returns the latest status of the request
+// even when called with the address of it's first version
+pub fn get_enrollment_request(
+    enrollment_request_address: Address,
+) -> ZomeApiResult<Option<EnrollmentRequest>> {
+    match hdk::get_entry(&enrollment_request_address)? {
+        Some(entry) => Ok(EnrollmentRequest::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+// returns addresses of pending requests to join the course
+pub fn get_enrollment_requests(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let links = hdk::get_links(
+        &course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+// returns addresses of requests of the agent that calls this function that teacher hasn't decided on yet
+pub fn get_my_pending_enrollment_requests() -> ZomeApiResult<Vec<Address>> {
+    let enrollment_request_addresses = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(STUDENT_TO_ENROLLMENT_REQUEST_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut pending_requests = Vec::new();
+    for enrollment_request_address in enrollment_request_addresses {
+        if let Some(enrollment_request) =
+            get_enrollment_request(enrollment_request_address.clone())?
+        {
+            if enrollment_request.status == EnrollmentRequestStatus::Pending {
+                pending_requests.push(enrollment_request_address);
+            }
+        }
+    }
+    Ok(pending_requests)
+}
+
+// changes status of a pending request and unlinks it from the course. Returns the request before the change
+fn decide(
+    enrollment_request_address: &Address,
+    status: EnrollmentRequestStatus,
+) -> ZomeApiResult<EnrollmentRequest> {
+    let enrollment_request: EnrollmentRequest =
+        hdk::utils::get_as_type(enrollment_request_address.clone())?;
+    course::handlers::ensure_teacher(&enrollment_request.course_anchor_address)?;
+    if enrollment_request.status != EnrollmentRequestStatus::Pending {
+        return Err(ZomeApiError::from(
+            "This enrollment request was already decided on".to_owned(),
+        ));
+    }
+
+    let mut decided_request = enrollment_request.clone();
+    decided_request.status = status;
+    hdk::update_entry(decided_request.entry(), enrollment_request_address)?;
+
+    // only pending requests are linked from the course
+    hdk::remove_link(
+        &enrollment_request.course_anchor_address,
+        enrollment_request_address,
+        COURSE_ANCHOR_TO_ENROLLMENT_REQUEST_LINK,
+        enrollment_request.student_address.to_string().as_str(),
+    )?;
+
+    Ok(enrollment_request)
+}
+
+// approves the request and enrolls the student. If the course is full, student is put on the waitlist instead
+pub fn approve(enrollment_request_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+    let enrollment_request = decide(
+        &enrollment_request_address,
+        EnrollmentRequestStatus::Approved,
+    )?;
+    let course_anchor_address = enrollment_request.course_anchor_address;
+
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't enroll a student in a deleted course".to_owned(),
+            ));
+        }
+    };
+    let student_address = enrollment_request.student_address;
+    let is_waitlisted =
+        course::handlers::get_waitlist_position(&course_anchor_address, &student_address)?
+            .is_some();
+    if !course::handlers::is_enrolled(&course_anchor_address, &student_address)? && !is_waitlisted {
+        course::handlers::admit_student(
+            &course,
+            &course_anchor_address,
+            &student_address,
+            timestamp,
+        )?;
+    }
+
+    Ok(enrollment_request_address)
+}
+
+pub fn reject(enrollment_request_address: Address) -> ZomeApiResult<Address> {
+    let enrollment_request = decide(
+        &enrollment_request_address,
+        EnrollmentRequestStatus::Rejected,
+    )?;
+    cohort::handlers::forget_pending_cohort(
+        &enrollment_request.course_anchor_address,
+        &enrollment_request.student_address,
+    )?;
+
+    let enrollment_rejected_signal = LeapSignal::EnrollmentRejected {
+        course_anchor_address: enrollment_request.course_anchor_address,
+        enrollment_request_address: enrollment_request_address.clone(),
+    };
+    signal::handlers::emit(&enrollment_rejected_signal)?;
+    signal::handlers::send_to_agents(
+        vec![enrollment_request.student_address],
+        &enrollment_rejected_signal,
+    )?;
+
+    Ok(enrollment_request_address)
+}
@@ -0,0 +1,132 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+use crate::section::anchor::SectionAnchor;
+
+pub const SECTION_ANCHOR_TO_FLASHCARD_LINK: &str = "section_anchor->flashcard";
+
+// Card with a key concept of the section that students memorize
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Flashcard {
+    pub front: String,
+    pub back: String,
+    pub section_anchor_address: Address,
+    pub course_anchor_address: Address,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Flashcard {
+    pub fn new(
+        front: String,
+        back: String,
+        section_anchor_address: Address,
+        course_anchor_address: Address,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Flashcard {
+            front: front,
+            back: back,
+            section_anchor_address: section_anchor_address,
+            course_anchor_address: course_anchor_address,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Flashcard {
+    fn entry_type() -> String {
+        String::from("flashcard")
+    }
+}
+
+// flashcards are created by the teacher of the course their section belongs to
+fn validate_author(flashcard: &Flashcard, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&flashcard.teacher_address) {
+        return Err("Flashcard can only be created by the teacher of the course".to_owned());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(flashcard.course_anchor_address.clone())
+            .map_err(|_| "Can't find the course of this flashcard".to_owned())?;
+    if flashcard.teacher_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can create it's flashcards".to_owned());
+    }
+    let section_anchor: SectionAnchor =
+        hdk::utils::get_as_type(flashcard.section_anchor_address.clone())
+            .map_err(|_| "Can't find the section of this flashcard".to_owned())?;
+    if section_anchor.course_anchor_address != flashcard.course_anchor_address {
+        return Err("Section doesn't belong to the course of this flashcard".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_flashcard_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let flashcard: Flashcard = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked flashcard".to_owned())?;
+    if !sources.contains(&flashcard.teacher_address) {
+        return Err("Only the teacher of the course can link it's flashcards".to_owned());
+    }
+    if *link_data.link.base() != flashcard.section_anchor_address {
+        return Err("Flashcard can only be linked from it's section".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_flashcard_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_flashcard_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_flashcard_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for Flashcard
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Flashcard::entry_type(),
+        description: "this is the definition of a flashcard of a section",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Flashcard>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if entry.front.trim().is_empty() || entry.back.trim().is_empty() {
+                        return Err("Flashcard should have both sides filled in".to_owned());
+                    }
+                    validate_author(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Flashcards can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the section this flashcard belongs to
+            from!(
+                SectionAnchor::entry_type(),
+                link_type: SECTION_ANCHOR_TO_FLASHCARD_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_flashcard_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,162 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{Flashcard, SECTION_ANCHOR_TO_FLASHCARD_LINK};
+use super::review::FlashcardReview;
+use super::scheduler::{self, ReviewState};
+use crate::course;
+use crate::helper;
+use crate::section;
+use crate::section::anchor::SectionAnchor;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct DueFlashcard {
+    pub flashcard_address: Address,
+    pub flashcard: Flashcard,
+    // it's None for cards that student has never reviewed
+    pub state: Option<ReviewState>,
+}
+
+pub fn create(
+    section_anchor_address: Address,
+    front: String,
+    back: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    course::handlers::ensure_teacher(&section_anchor.course_anchor_address)?;
+
+    let flashcard = Flashcard::new(
+        front,
+        back,
+        section_anchor_address.clone(),
+        section_anchor.course_anchor_address,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let flashcard_address = hdk::commit_entry(&flashcard.entry())?;
+
+    hdk::link_entries(
+        &section_anchor_address,
+        &flashcard_address,
+        SECTION_ANCHOR_TO_FLASHCARD_LINK,
+        "",
+    )?;
+
+    Ok(flashcard_address)
+}
+
+pub fn get_flashcard(flashcard_address: Address) -> ZomeApiResult<Option<Flashcard>> {
+    match hdk::get_entry(&flashcard_address)? {
+        Some(entry) => Ok(Flashcard::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+pub fn get_section_flashcards(section_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &section_anchor_address,
+        LinkMatch::Exactly(SECTION_ANCHOR_TO_FLASHCARD_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+// latest review of every flashcard reviewed by the agent that calls this function
+fn get_my_review_states() -> ZomeApiResult<Vec<(Address, ReviewState)>> {
+    let mut reviews: Vec<FlashcardReview> = helper::query_my_entries::<FlashcardReview>()?
+        .into_iter()
+        .map(|(review, _address)| review)
+        .collect();
+    reviews.sort_by_key(|review| review.reviewed_at);
+
+    let mut review_states: Vec<(Address, ReviewState)> = Vec::new();
+    for review in reviews {
+        match review_states
+            .iter_mut()
+            .find(|(flashcard_address, _state)| *flashcard_address == review.flashcard_address)
+        {
+            Some((_flashcard_address, state)) => *state = review.state,
+            None => review_states.push((review.flashcard_address, review.state)),
+        }
+    }
+    Ok(review_states)
+}
+
+fn get_my_review_state(flashcard_address: &Address) -> ZomeApiResult<Option<ReviewState>> {
+    Ok(get_my_review_states()?
+        .into_iter()
+        .find(|(reviewed_flashcard_address, _state)| {
+            reviewed_flashcard_address == flashcard_address
+        })
+        .map(|(_flashcard_address, state)| state))
+}
+
+// returns flashcards of released sections in all courses that agent that calls this function
+// is enrolled in which are due by the end of the caller's day. utc_offset is the caller's
+// time zone in seconds. Cards that were never reviewed are always due
+pub fn get_due(timestamp: u64, utc_offset: i64) -> ZomeApiResult<Vec<DueFlashcard>> {
+    let end_of_day =
+        scheduler::get_end_of_day(timestamp, utc_offset).map_err(ZomeApiError::from)?;
+    let review_states = get_my_review_states()?;
+
+    let mut due_flashcards = Vec::new();
+    for course_anchor_address in course::handlers::get_my_enrolled_courses()? {
+        let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+            Some((course, _course_address)) => course,
+            None => continue,
+        };
+        for section_anchor_address in course.sections {
+            let section = match section::handlers::get_latest_section(&section_anchor_address)? {
+                Some((section, _section_address)) => section,
+                // skip sections that were deleted
+                None => continue,
+            };
+            if !section::handlers::is_released(&section, &course_anchor_address, timestamp)? {
+                continue;
+            }
+            for flashcard_address in get_section_flashcards(section_anchor_address)? {
+                let state = review_states
+                    .iter()
+                    .find(|(reviewed_flashcard_address, _state)| {
+                        *reviewed_flashcard_address == flashcard_address
+                    })
+                    .map(|(_flashcard_address, state)| state.clone());
+                let is_due = match &state {
+                    Some(state) => scheduler::is_due(state, end_of_day),
+                    None => true,
+                };
+                if is_due {
+                    let flashcard: Flashcard = hdk::utils::get_as_type(flashcard_address.clone())?;
+                    due_flashcards.push(DueFlashcard {
+                        flashcard_address: flashcard_address,
+                        flashcard: flashcard,
+                        state: state,
+                    });
+                }
+            }
+        }
+    }
+    Ok(due_flashcards)
+}
+
+// records how well the agent that calls this function recalled the flashcard (from 0 to 5)
+// and returns when it should be reviewed next
+pub fn review(flashcard_address: Address, grade: u8, timestamp: u64) -> ZomeApiResult<ReviewState> {
+    let flashcard: Flashcard = hdk::utils::get_as_type(flashcard_address.clone())?;
+    if !course::handlers::is_enrolled(&flashcard.course_anchor_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "Only students enrolled in this course can review it's flashcards".to_owned(),
+        ));
+    }
+
+    let previous_state = get_my_review_state(&flashcard_address)?.unwrap_or_default();
+    let state =
+        scheduler::schedule(&previous_state, grade, timestamp).map_err(ZomeApiError::from)?;
+    let review = FlashcardReview::new(flashcard_address, grade, state.clone(), timestamp);
+    hdk::commit_entry(&review.entry())?;
+
+    Ok(state)
+}
@@ -0,0 +1,51 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::scheduler::ReviewState;
+
+// Private entry with the student's review of a flashcard.
+// A new one is committed on every review and the latest one holds the current schedule of the card
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct FlashcardReview {
+    pub flashcard_address: Address,
+    pub grade: u8,
+    pub state: ReviewState,
+    pub reviewed_at: u64,
+}
+
+impl FlashcardReview {
+    pub fn new(
+        flashcard_address: Address,
+        grade: u8,
+        state: ReviewState,
+        reviewed_at: u64,
+    ) -> Self {
+        FlashcardReview {
+            flashcard_address: flashcard_address,
+            grade: grade,
+            state: state,
+            reviewed_at: reviewed_at,
+        }
+    }
+}
+
+impl HolochainEntry for FlashcardReview {
+    fn entry_type() -> String {
+        String::from("flashcard_review")
+    }
+}
+
+// Holochain entry definition for FlashcardReview
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: FlashcardReview::entry_type(),
+        description: "this is the definition of a private review of a flashcard",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | _validation_data: hdk::EntryValidationData<FlashcardReview>| {
+            Ok(())
+        }
+    )
+}
@@ -0,0 +1,187 @@
+// SM-2 spaced repetition scheduling. It doesn't use hdk, so it can be run outside of the conductor
+
+const SECONDS_IN_DAY: u64 = 24 * 60 * 60;
+const INITIAL_EASE_FACTOR: f64 = 2.5;
+const MIN_EASE_FACTOR: f64 = 1.3;
+// grades are from 0 (complete blackout) to 5 (perfect response)
+pub const MAX_GRADE: u8 = 5;
+// grades below this one mean student didn't recall the card and it starts over
+const PASSING_GRADE: u8 = 3;
+// local time zones are between UTC-12:00 and UTC+14:00
+const MAX_UTC_OFFSET: i64 = 14 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReviewState {
+    // number of reviews in a row that student recalled the card
+    pub repetitions: u32,
+    // in days
+    pub interval: u32,
+    pub ease_factor: f64,
+    // when the card should be reviewed next
+    pub due_at: u64,
+}
+
+impl Default for ReviewState {
+    fn default() -> Self {
+        ReviewState {
+            repetitions: 0,
+            interval: 0,
+            ease_factor: INITIAL_EASE_FACTOR,
+            due_at: 0,
+        }
+    }
+}
+
+// returns the state of the card after it was reviewed with grade at reviewed_at
+pub fn schedule(state: &ReviewState, grade: u8, reviewed_at: u64) -> Result<ReviewState, String> {
+    if grade > MAX_GRADE {
+        return Err(format!(
+            "Grade should be between 0 and {}, got {}",
+            MAX_GRADE, grade
+        ));
+    }
+
+    let (repetitions, interval) = if grade < PASSING_GRADE {
+        (0, 1)
+    } else {
+        let interval = match state.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (state.interval as f64 * state.ease_factor).round() as u32,
+        };
+        (state.repetitions + 1, interval)
+    };
+
+    let grade_distance = (MAX_GRADE - grade) as f64;
+    let ease_factor = (state.ease_factor + (0.1 - grade_distance * (0.08 + grade_distance * 0.02)))
+        .max(MIN_EASE_FACTOR);
+
+    Ok(ReviewState {
+        repetitions: repetitions,
+        interval: interval,
+        ease_factor: ease_factor,
+        due_at: reviewed_at.saturating_add(interval as u64 * SECONDS_IN_DAY),
+    })
+}
+
+// returns the last second of the caller's local day that timestamp falls into.
+// utc_offset is in seconds, e.g. 3600 for UTC+01:00
+pub fn get_end_of_day(timestamp: u64, utc_offset: i64) -> Result<u64, String> {
+    if utc_offset.abs() > MAX_UTC_OFFSET {
+        return Err(format!(
+            "UTC offset should be within {} seconds, got {}",
+            MAX_UTC_OFFSET, utc_offset
+        ));
+    }
+    let day = SECONDS_IN_DAY as i128;
+    let local_time = timestamp as i128 + utc_offset as i128;
+    let end_of_local_day = (local_time.div_euclid(day) + 1) * day - 1;
+    let end_of_day = end_of_local_day - utc_offset as i128;
+    Ok(end_of_day.max(0).min(u64::MAX as i128) as u64)
+}
+
+// card is due today if it's due at any moment before the caller's day ends
+pub fn is_due(state: &ReviewState, end_of_day: u64) -> bool {
+    state.due_at <= end_of_day
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn passing_grades_grow_interval() {
+        let first = schedule(&ReviewState::default(), 5, 1000).unwrap();
+        assert_eq!(first.repetitions, 1);
+        assert_eq!(first.interval, 1);
+        assert_close(first.ease_factor, 2.6);
+        assert_eq!(first.due_at, 1000 + SECONDS_IN_DAY);
+
+        let second = schedule(&first, 5, 2000).unwrap();
+        assert_eq!(second.repetitions, 2);
+        assert_eq!(second.interval, 6);
+        assert_close(second.ease_factor, 2.7);
+        assert_eq!(second.due_at, 2000 + 6 * SECONDS_IN_DAY);
+
+        // from the third repetition interval is multiplied by the ease factor of the previous state
+        let third = schedule(&second, 4, 3000).unwrap();
+        assert_eq!(third.repetitions, 3);
+        assert_eq!(third.interval, 16);
+        assert_close(third.ease_factor, 2.7);
+    }
+
+    #[test]
+    fn failing_grade_starts_over() {
+        let state = ReviewState {
+            repetitions: 4,
+            interval: 30,
+            ease_factor: 2.5,
+            due_at: 0,
+        };
+        let next = schedule(&state, 2, 1000).unwrap();
+        assert_eq!(next.repetitions, 0);
+        assert_eq!(next.interval, 1);
+        assert_close(next.ease_factor, 2.18);
+        assert_eq!(next.due_at, 1000 + SECONDS_IN_DAY);
+    }
+
+    #[test]
+    fn lowest_passing_grade_lowers_ease_factor() {
+        let next = schedule(&ReviewState::default(), PASSING_GRADE, 0).unwrap();
+        assert_eq!(next.repetitions, 1);
+        assert_close(next.ease_factor, 2.36);
+    }
+
+    #[test]
+    fn ease_factor_doesnt_go_below_minimum() {
+        let state = ReviewState {
+            ease_factor: 1.4,
+            ..ReviewState::default()
+        };
+        let next = schedule(&state, 0, 0).unwrap();
+        assert_close(next.ease_factor, MIN_EASE_FACTOR);
+        let next = schedule(&next, 0, 0).unwrap();
+        assert_close(next.ease_factor, MIN_EASE_FACTOR);
+    }
+
+    #[test]
+    fn grade_above_maximum_is_rejected() {
+        assert!(schedule(&ReviewState::default(), MAX_GRADE + 1, 0).is_err());
+    }
+
+    #[test]
+    fn end_of_day_is_in_callers_time_zone() {
+        // 2020-01-01T10:00:00Z
+        let timestamp = 1_577_872_800;
+        assert_eq!(get_end_of_day(timestamp, 0), Ok(1_577_923_199));
+        // it's already 2020-01-01T20:00 in UTC+10, so the day ends 4 hours later
+        assert_eq!(get_end_of_day(timestamp, 10 * 3600), Ok(1_577_887_199));
+        // and it's still 2019-12-31T22:00 in UTC-12
+        assert_eq!(get_end_of_day(timestamp, -12 * 3600), Ok(1_577_879_999));
+        assert!(get_end_of_day(timestamp, MAX_UTC_OFFSET + 1).is_err());
+    }
+
+    #[test]
+    fn card_due_later_today_is_due() {
+        let end_of_day = get_end_of_day(1_577_872_800, 0).unwrap();
+        let state = ReviewState {
+            due_at: 1_577_872_800 + 3600,
+            ..ReviewState::default()
+        };
+        assert!(is_due(&state, end_of_day));
+        let state = ReviewState {
+            due_at: end_of_day + 1,
+            ..ReviewState::default()
+        };
+        assert!(!is_due(&state, end_of_day));
+    }
+}
@@ -0,0 +1,53 @@
+// RFC 4180 CSV export of the gradebook
+
+use super::handlers::Gradebook;
+
+const LINE_BREAK: &str = "\r\n";
+
+// quotes the field if it contains characters that have special meaning in CSV
+fn escape_field(field: &str) -> String {
+    if field.contains(|character| {
+        character == ',' || character == '"' || character == '\r' || character == '\n'
+    }) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn format_score(score: Option<f64>) -> String {
+    match score {
+        Some(score) => format!("{:.2}", score),
+        None => String::new(),
+    }
+}
+
+fn push_record(fields: Vec<String>, output: &mut String) {
+    let escaped_fields: Vec<String> = fields.iter().map(|field| escape_field(field)).collect();
+    output.push_str(&escaped_fields.join(","));
+    output.push_str(LINE_BREAK);
+}
+
+// renders gradebook as CSV with a header row. Columns are the student address,
+// one column per category in the order of Gradebook.categories and the total
+pub fn render_gradebook(gradebook: &Gradebook) -> String {
+    let mut output = String::new();
+
+    let mut header = vec!["student_address".to_owned()];
+    header.extend(
+        gradebook
+            .categories
+            .iter()
+            .map(|category| category.name().to_owned()),
+    );
+    header.push("total".to_owned());
+    push_record(header, &mut output);
+
+    for row in gradebook.rows.iter() {
+        let mut fields = vec![row.student_address.to_string()];
+        fields.extend(row.scores.iter().map(|score| format_score(*score)));
+        fields.push(format_score(row.total));
+        push_record(fields, &mut output);
+    }
+    output
+}
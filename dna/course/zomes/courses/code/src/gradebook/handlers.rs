@@ -0,0 +1,188 @@
+use hdk::prelude::*;
+use std::collections::HashMap;
+
+use super::csv;
+use crate::assignment;
+use crate::cohort;
+use crate::course;
+use crate::course::entry::GradeWeights;
+use crate::live_session;
+use crate::live_session::handlers::StudentAttendance;
+use crate::quiz;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum GradeCategory {
+    Quizzes,
+    Assignments,
+    Progress,
+    Attendance,
+}
+
+// order of columns in the gradebook. Keep it stable since CSV exports depend on it
+pub const GRADE_CATEGORIES: [GradeCategory; 4] = [
+    GradeCategory::Quizzes,
+    GradeCategory::Assignments,
+    GradeCategory::Progress,
+    GradeCategory::Attendance,
+];
+
+impl GradeCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            GradeCategory::Quizzes => "quizzes",
+            GradeCategory::Assignments => "assignments",
+            GradeCategory::Progress => "progress",
+            GradeCategory::Attendance => "attendance",
+        }
+    }
+
+    pub fn weight(&self, grade_weights: &GradeWeights) -> u32 {
+        match self {
+            GradeCategory::Quizzes => grade_weights.quizzes,
+            GradeCategory::Assignments => grade_weights.assignments,
+            GradeCategory::Progress => grade_weights.progress,
+            GradeCategory::Attendance => grade_weights.attendance,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct GradebookRow {
+    pub student_address: Address,
+    // score in percents for every category in the order of Gradebook.categories.
+    // It's None if there's nothing to grade in this category for the student yet
+    pub scores: Vec<Option<f64>>,
+    // weighted average of the scores that aren't None
+    pub total: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Gradebook {
+    pub course_anchor_address: Address,
+    // if gradebook is scoped to a cohort, only students of this cohort are included
+    pub cohort_address: Option<Address>,
+    pub categories: Vec<GradeCategory>,
+    pub grade_weights: GradeWeights,
+    pub rows: Vec<GradebookRow>,
+}
+
+// context that is the same for all students of the gradebook, so we only load it once
+struct GradingContext {
+    course_anchor_address: Address,
+    // attendance of every student of the course, see live_session::handlers::get_attendance_summary
+    attendance: HashMap<Address, StudentAttendance>,
+}
+
+fn get_category_score(
+    category: &GradeCategory,
+    context: &GradingContext,
+    student_address: &Address,
+) -> ZomeApiResult<Option<f64>> {
+    match category {
+        GradeCategory::Quizzes => {
+            quiz::handlers::get_student_score(&context.course_anchor_address, student_address)
+        }
+        GradeCategory::Assignments => {
+            assignment::handlers::get_student_score(&context.course_anchor_address, student_address)
+        }
+        GradeCategory::Progress => {
+            if course::handlers::has_completed(&context.course_anchor_address, student_address)? {
+                Ok(Some(100.0))
+            } else {
+                Ok(Some(0.0))
+            }
+        }
+        GradeCategory::Attendance => match context.attendance.get(student_address) {
+            Some(attendance) if attendance.total_sessions > 0 => Ok(Some(
+                attendance.attended_sessions as f64 * 100.0 / attendance.total_sessions as f64,
+            )),
+            _ => Ok(None),
+        },
+    }
+}
+
+// weighted average of the scores. Categories without a score are left out
+// so that they don't drag the total down
+fn get_total(scores: &[Option<f64>], grade_weights: &GradeWeights) -> Option<f64> {
+    let mut weighted_sum = 0.0;
+    // summing weights as f64 so that entries committed before weights were checked can't overflow
+    let mut total_weight = 0.0;
+    for (category, score) in GRADE_CATEGORIES.iter().zip(scores.iter()) {
+        if let Some(score) = score {
+            let weight = category.weight(grade_weights) as f64;
+            weighted_sum += score * weight;
+            total_weight += weight;
+        }
+    }
+    if total_weight == 0.0 {
+        None
+    } else {
+        Some(weighted_sum / total_weight)
+    }
+}
+
+// returns scores of all students of the course (or only of one it's cohort) at the given moment
+pub fn get_gradebook(
+    course_anchor_address: Address,
+    cohort_address: Option<Address>,
+    timestamp: u64,
+) -> ZomeApiResult<Gradebook> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't get gradebook of a deleted course".to_owned(),
+            ));
+        }
+    };
+
+    let mut students = match &cohort_address {
+        Some(cohort_address) => cohort::handlers::get_students(cohort_address)?,
+        None => course::handlers::get_students(course_anchor_address.clone())?,
+    };
+    // sort students so that rows are always in the same order
+    students.sort_by_key(|student_address| student_address.to_string());
+
+    let attendance =
+        live_session::handlers::get_attendance_summary(course_anchor_address.clone(), timestamp)?
+            .into_iter()
+            .map(|attendance| (attendance.student_address.clone(), attendance))
+            .collect();
+    let context = GradingContext {
+        course_anchor_address: course_anchor_address.clone(),
+        attendance: attendance,
+    };
+
+    let mut rows = Vec::new();
+    for student_address in students {
+        let mut scores = Vec::new();
+        for category in GRADE_CATEGORIES.iter() {
+            scores.push(get_category_score(category, &context, &student_address)?);
+        }
+        let total = get_total(&scores, &course.grade_weights);
+        rows.push(GradebookRow {
+            student_address: student_address,
+            scores: scores,
+            total: total,
+        });
+    }
+
+    Ok(Gradebook {
+        course_anchor_address: course_anchor_address,
+        cohort_address: cohort_address,
+        categories: GRADE_CATEGORIES.to_vec(),
+        grade_weights: course.grade_weights,
+        rows: rows,
+    })
+}
+
+// same as get_gradebook but rendered as RFC 4180 CSV
+pub fn export_gradebook_csv(
+    course_anchor_address: Address,
+    cohort_address: Option<Address>,
+    timestamp: u64,
+) -> ZomeApiResult<String> {
+    let gradebook = get_gradebook(course_anchor_address, cohort_address, timestamp)?;
+    Ok(csv::render_gradebook(&gradebook))
+}
@@ -1,5 +1,7 @@
 use hdk::{
-    error::ZomeApiResult, holochain_persistence_api::cas::content::Address, prelude::LinkMatch,
+    error::{ZomeApiError, ZomeApiResult},
+    holochain_persistence_api::cas::content::Address,
+    prelude::{LinkMatch, QueryArgsOptions, QueryResult},
 };
 use holochain_entry_utils::HolochainEntry;
 
@@ -37,3 +39,26 @@ pub fn get_latest_data_entry<T: HolochainEntry>(
         None => return Ok(None),
     }
 }
+
+// gets all entries of type T from the source chain of the agent that calls this function.
+// This is the only way to read private entries, because they never leave the source chain
+pub fn query_my_entries<T: HolochainEntry>() -> ZomeApiResult<Vec<(T, Address)>> {
+    let query_result = hdk::query_result(
+        T::entry_type().into(),
+        QueryArgsOptions {
+            entries: true,
+            ..QueryArgsOptions::default()
+        },
+    )?;
+    match query_result {
+        QueryResult::Entries(entries) => Ok(entries
+            .iter()
+            .filter_map(|(address, entry)| {
+                T::from_entry(entry).map(|native_entry| (native_entry, address.clone()))
+            })
+            .collect()),
+        _ => Err(ZomeApiError::from(
+            "Unexpected query result type".to_owned(),
+        )),
+    }
+}
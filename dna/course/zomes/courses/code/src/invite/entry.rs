@@ -0,0 +1,155 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_INVITE_LINK: &str = "course_anchor->invite";
+pub const STUDENT_TO_INVITE_LINK: &str = "student->invite";
+
+// Personal invite of a student into an invite-only course
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Invite {
+    pub course_anchor_address: Address,
+    pub invitee_address: Address,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Invite {
+    pub fn new(
+        course_anchor_address: Address,
+        invitee_address: Address,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Invite {
+            course_anchor_address: course_anchor_address,
+            invitee_address: invitee_address,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Invite {
+    fn entry_type() -> String {
+        String::from("invite")
+    }
+}
+
+// NOTE: InviteCode is never committed. We only use it to calculate a hash of the invite code.
+// Salt is a signature that only the teacher of the course can make, so nobody else can
+// brute-force the code from it's hash in the public Course entry
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct InviteCode {
+    pub salt: String,
+    pub code: String,
+}
+
+impl InviteCode {
+    pub fn new(salt: String, code: String) -> Self {
+        InviteCode {
+            salt: salt,
+            code: code,
+        }
+    }
+}
+
+impl HolochainEntry for InviteCode {
+    fn entry_type() -> String {
+        String::from("invite_code")
+    }
+}
+
+// invites are created by the teacher of the course they invite into
+fn validate_invite(invite: &Invite, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&invite.teacher_address) {
+        return Err("Invite can only be created by the teacher it belongs to".to_owned());
+    }
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(invite.course_anchor_address.clone())
+        .map_err(|_| "Can't find the course of this invite".to_owned())?;
+    if invite.teacher_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can invite students into it".to_owned());
+    }
+    Ok(())
+}
+
+// invite links are made by the teacher who created the invite
+fn validate_invite_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let invite: Invite = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked invite".to_owned())?;
+    if !sources.contains(&invite.teacher_address) {
+        return Err("Only the teacher who created the invite can link it".to_owned());
+    }
+    let base = link_data.link.base();
+    if *base != invite.course_anchor_address && *base != invite.invitee_address {
+        return Err("Invite can only be linked from it's course and it's invitee".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_invite_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_invite_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_invite_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for Invite
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Invite::entry_type(),
+        description: "this is the definition of an invite of a student into a course",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Invite>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_invite(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Invites can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course student is invited into.
+            // Tag of this link is an address of the invited student
+            // so we can quickly check if a particular student was invited
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_INVITE_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_invite_link_change(validation_data)
+                }
+            ),
+            // link from the invited student so they can find their invites
+            from!(
+                "%agent_id",
+                link_type: STUDENT_TO_INVITE_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_invite_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
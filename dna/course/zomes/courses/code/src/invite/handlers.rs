@@ -0,0 +1,211 @@
+use hdk::holochain_core_types::time::Timeout;
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+use std::convert::TryFrom;
+
+use super::entry::{Invite, InviteCode, COURSE_ANCHOR_TO_INVITE_LINK, STUDENT_TO_INVITE_LINK};
+use crate::course;
+use crate::course::entry::Course;
+
+// invite codes with less entropy than this could be guessed by trying codes against the teacher
+const MIN_INVITE_CODE_ENTROPY_BITS: f64 = 60.0;
+// how long student waits for the teacher to accept their invite code, in milliseconds
+const INVITE_CODE_TIMEOUT_MS: usize = 5000;
+
+// direct message that student sends to the teacher to exchange an invite code for a personal invite.
+// Only the teacher can check the code, since nobody else can calculate the salt of it's hash
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct InviteCodeRedemption {
+    pub course_anchor_address: Address,
+    pub code: String,
+    pub timestamp: u64,
+}
+
+impl InviteCodeRedemption {
+    pub fn new(course_anchor_address: Address, code: String, timestamp: u64) -> Self {
+        InviteCodeRedemption {
+            course_anchor_address: course_anchor_address,
+            code: code,
+            timestamp: timestamp,
+        }
+    }
+}
+
+// hashes the code with the signature of the agent that calls this function as a salt.
+// Signatures are deterministic, so the teacher gets the same hash every time they check a code
+pub fn hash_invite_code(course_anchor_address: &Address, code: String) -> ZomeApiResult<Address> {
+    let salt = hdk::sign(format!("invite_code:{}", course_anchor_address))?;
+    InviteCode::new(salt, code).address()
+}
+
+// rough estimate of the code's entropy based on the kinds of characters it uses
+fn estimate_entropy_bits(code: &str) -> f64 {
+    let mut alphabet_size = 0;
+    if code.chars().any(|c| c.is_ascii_lowercase()) {
+        alphabet_size += 26;
+    }
+    if code.chars().any(|c| c.is_ascii_uppercase()) {
+        alphabet_size += 26;
+    }
+    if code.chars().any(|c| c.is_ascii_digit()) {
+        alphabet_size += 10;
+    }
+    if code.chars().any(|c| !c.is_ascii_alphanumeric()) {
+        alphabet_size += 33;
+    }
+    if alphabet_size == 0 {
+        return 0.0;
+    }
+    code.chars().count() as f64 * f64::from(alphabet_size).log2()
+}
+
+pub fn ensure_strong_invite_code(code: &str) -> ZomeApiResult<()> {
+    if estimate_entropy_bits(code) < MIN_INVITE_CODE_ENTROPY_BITS {
+        return Err(ZomeApiError::from(
+            "Invite code is too easy to guess. Use a longer code with letters of both cases and digits"
+                .to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn invite_student(
+    course_anchor_address: Address,
+    invitee_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let invitee_tag = invitee_address.to_string();
+    let existing_invites = hdk::get_links(
+        &course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_INVITE_LINK),
+        LinkMatch::Exactly(&invitee_tag),
+    )?
+    .addresses();
+    if let Some(invite_address) = existing_invites.first() {
+        return Ok(invite_address.clone());
+    }
+
+    let invite = Invite::new(
+        course_anchor_address.clone(),
+        invitee_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let invite_address = hdk::commit_entry(&invite.entry())?;
+
+    hdk::link_entries(
+        &course_anchor_address,
+        &invite_address,
+        COURSE_ANCHOR_TO_INVITE_LINK,
+        invitee_tag.as_str(),
+    )?;
+    hdk::link_entries(
+        &invitee_address,
+        &invite_address,
+        STUDENT_TO_INVITE_LINK,
+        "",
+    )?;
+
+    Ok(invite_address)
+}
+
+pub fn get_my_invites() -> ZomeApiResult<Vec<Invite>> {
+    let invite_addresses = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(STUDENT_TO_INVITE_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    invite_addresses
+        .into_iter()
+        .map(|invite_address| hdk::utils::get_as_type(invite_address))
+        .collect()
+}
+
+// checks if student at student_address was personally invited into the course
+pub fn is_invited(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<bool> {
+    let invite_count = hdk::get_links_count(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_INVITE_LINK),
+        LinkMatch::Exactly(&student_address.to_string()),
+    )?
+    .count;
+    Ok(invite_count > 0)
+}
+
+// asks the teacher to exchange the invite code for a personal invite of the agent that calls this function.
+// Teacher has to be online for this, since only they can check the code
+fn redeem_invite_code(course: &Course, code: String, timestamp: u64) -> ZomeApiResult<Address> {
+    let redemption = InviteCodeRedemption::new(course.anchor_address.clone(), code, timestamp);
+    let response = hdk::send(
+        course.teacher_address.clone(),
+        String::from(JsonString::from(redemption)),
+        Timeout::new(INVITE_CODE_TIMEOUT_MS),
+    )
+    .map_err(|_| {
+        ZomeApiError::from(
+            "Teacher of this course needs to be online to accept invite codes. Try again later"
+                .to_owned(),
+        )
+    })?;
+    if response.starts_with("error: ") {
+        return Err(ZomeApiError::from(response));
+    }
+    Ok(Address::from(response))
+}
+
+// checks if student can enroll in an invite-only course: either they were personally invited
+// or the teacher accepts their invite code and invites them
+pub fn can_join(
+    course: &Course,
+    invite_code: Option<String>,
+    timestamp: u64,
+) -> ZomeApiResult<bool> {
+    if is_invited(&course.anchor_address, &AGENT_ADDRESS)? {
+        return Ok(true);
+    }
+    match (invite_code, &course.invite_code_hash) {
+        (Some(code), Some(_invite_code_hash)) => {
+            redeem_invite_code(course, code, timestamp)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+// returns redemption if the direct message is one, so it isn't treated as a signal
+pub fn parse_invite_code_redemption(payload: &str) -> Option<InviteCodeRedemption> {
+    InviteCodeRedemption::try_from(JsonString::from_json(payload)).ok()
+}
+
+fn accept_invite_code(from: Address, redemption: InviteCodeRedemption) -> ZomeApiResult<Address> {
+    let course_anchor_address = redemption.course_anchor_address;
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Err(ZomeApiError::from("Can't join a deleted course".to_owned())),
+    };
+    match &course.invite_code_hash {
+        Some(invite_code_hash)
+            if hash_invite_code(&course_anchor_address, redemption.code)? == *invite_code_hash =>
+        {
+            invite_student(course_anchor_address, from, redemption.timestamp)
+        }
+        _ => Err(ZomeApiError::from("Invite code isn't valid".to_owned())),
+    }
+}
+
+// handles invite code that a student has sent to us as the teacher of the course.
+// Returns address of the student's new invite, or an error that starts with "error: "
+pub fn receive_invite_code(from: Address, redemption: InviteCodeRedemption) -> String {
+    match accept_invite_code(from, redemption) {
+        Ok(invite_address) => invite_address.to_string(),
+        Err(error) => format!("error: {}", error),
+    }
+}
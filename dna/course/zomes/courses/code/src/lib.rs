@@ -22,11 +22,35 @@ use hdk::prelude::*;
 
 use hdk_proc_macros::zome;
 
+mod analytics;
 mod anchor_trait;
+mod announcement;
+mod assignment;
+mod bundle;
+mod calendar;
+mod cohort;
+mod comment;
 mod content;
 mod course;
+mod dashboard;
+mod enrollment_request;
+mod flashcard;
+mod gradebook;
 mod helper;
+mod invite;
+mod live_session;
+mod moderation;
+mod note;
+mod pathway;
+mod peer_review;
+mod prerequisite;
+mod profile;
+mod question;
+mod quiz;
+mod review;
+mod rubric;
 mod section;
+mod signal;
 
 #[zome]
 mod courses {
@@ -43,6 +67,16 @@ mod courses {
         Ok(())
     }
 
+    // Direct messages from other agents. They carry LeapSignals about courses we follow
+    // which are passed on to our UI, or invite codes that students ask us to accept as a teacher
+    #[receive]
+    pub fn receive(from: Address, msg_json: String) {
+        match invite::handlers::parse_invite_code_redemption(&msg_json) {
+            Some(redemption) => invite::handlers::receive_invite_code(from, redemption),
+            None => signal::handlers::receive(from, msg_json),
+        }
+    }
+
     //  ====================== Course definitions
     #[entry_def]
     fn course_catalog_anchor_entry_definition() -> ValidatingEntryType {
@@ -60,8 +94,13 @@ mod courses {
     }
 
     #[zome_fn("hc_public")]
-    fn create_course(title: String, timestamp: u64) -> ZomeApiResult<Address> {
-        course::handlers::create(title, timestamp)
+    fn create_course(
+        title: String,
+        timestamp: u64,
+        visibility: Option<course::entry::CourseVisibility>,
+    ) -> ZomeApiResult<Address> {
+        // courses are public unless UI asks otherwise
+        course::handlers::create(title, timestamp, visibility.unwrap_or_default())
     }
 
     #[zome_fn("hc_public")]
@@ -77,6 +116,49 @@ mod courses {
         }
     }
 
+    #[zome_fn("hc_public")]
+    fn get_course_tree(
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Option<course::handlers::CourseTree>> {
+        course::handlers::get_course_tree(course_anchor_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_course_summaries(
+        course_anchor_addresses: Vec<Address>,
+    ) -> ZomeApiResult<Vec<course::handlers::CourseSummary>> {
+        course::handlers::get_course_summaries(course_anchor_addresses)
+    }
+
+    #[zome_fn("hc_public")]
+    fn export_course(
+        course_anchor_address: Address,
+        include_quizzes: bool,
+        attachments: Option<Vec<bundle::schema::BundleAttachment>>,
+        timestamp: u64,
+    ) -> ZomeApiResult<String> {
+        bundle::export::export_course(
+            course_anchor_address,
+            include_quizzes,
+            attachments,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn import_course(
+        bundle_json: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<bundle::import::ImportResult> {
+        bundle::import::import_course(bundle_json, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn export_course_calendar(course_anchor_address: Address) -> ZomeApiResult<String> {
+        calendar::handlers::export_course_calendar(course_anchor_address)
+    }
+
     #[zome_fn("hc_public")]
     fn update_course(
         title: String,
@@ -96,6 +178,11 @@ mod courses {
         course::handlers::list_all_courses()
     }
 
+    #[zome_fn("hc_public")]
+    fn get_all_courses_by_rating() -> ZomeApiResult<Vec<Address>> {
+        course::handlers::list_all_courses_by_rating()
+    }
+
     #[zome_fn("hc_public")]
     fn get_my_courses() -> ZomeApiResult<Vec<Address>> {
         course::handlers::get_my_courses()
@@ -106,80 +193,1263 @@ mod courses {
         course::handlers::get_my_enrolled_courses()
     }
 
-    //  ====================== Section definitions
+    #[zome_fn("hc_public")]
+    fn enroll_in_course(
+        course_anchor_address: Address,
+        timestamp: u64,
+        invite_code: Option<String>,
+    ) -> ZomeApiResult<course::handlers::EnrollmentResult> {
+        course::handlers::enroll(course_anchor_address, timestamp, invite_code)
+    }
+
+    #[zome_fn("hc_public")]
+    fn unenroll_from_course(
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::unenroll(course_anchor_address, timestamp)
+    }
+
+    // enrolls students from the waitlist into seats that are free. Students that leave the course
+    // already do this, so it's only needed if their promotion didn't get through
+    #[zome_fn("hc_public")]
+    fn promote_waitlisted_students(
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::promote_waitlisted(&course_anchor_address, timestamp)?;
+        Ok(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_student_count(course_anchor_address: Address) -> ZomeApiResult<usize> {
+        course::handlers::get_student_count(&course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_waitlist_position(course_anchor_address: Address) -> ZomeApiResult<Option<usize>> {
+        course::handlers::get_my_waitlist_position(&course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_waitlisted_courses() -> ZomeApiResult<Vec<Address>> {
+        course::handlers::get_my_waitlisted_courses()
+    }
+
+    // teacher marks that the student has completed the course
+    #[zome_fn("hc_public")]
+    fn complete_course(
+        course_anchor_address: Address,
+        student_address: Address,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::complete(course_anchor_address, student_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_completed_courses() -> ZomeApiResult<Vec<Address>> {
+        course::handlers::get_my_completed_courses()
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_prerequisite_policy(
+        course_anchor_address: Address,
+        prerequisite_policy: course::entry::PrerequisitePolicy,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::set_prerequisite_policy(&course_anchor_address, prerequisite_policy)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_enrollment_mode(
+        course_anchor_address: Address,
+        enrollment_mode: course::entry::EnrollmentMode,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::set_enrollment_mode(&course_anchor_address, enrollment_mode)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_grade_weights(
+        course_anchor_address: Address,
+        grade_weights: course::entry::GradeWeights,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::set_grade_weights(&course_anchor_address, grade_weights)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_max_students(
+        course_anchor_address: Address,
+        max_students: Option<u32>,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::set_max_students(&course_anchor_address, max_students, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_course_visibility(
+        course_anchor_address: Address,
+        visibility: course::entry::CourseVisibility,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::set_visibility(&course_anchor_address, visibility)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_invite_code(
+        course_anchor_address: Address,
+        invite_code: Option<String>,
+    ) -> ZomeApiResult<Address> {
+        course::handlers::set_invite_code(&course_anchor_address, invite_code)
+    }
 
+    //  ====================== Profile definitions
     #[entry_def]
-    fn section_anchor_definition() -> ValidatingEntryType {
-        section::anchor::section_anchor_def()
+    fn agent_profile_entry_definition() -> ValidatingEntryType {
+        profile::entry::entry_def()
     }
 
     #[entry_def]
-    fn section_entry_definition() -> ValidatingEntryType {
-        section::entry::section_entry_def()
+    fn handle_anchor_entry_definition() -> ValidatingEntryType {
+        profile::handle_anchor::handle_anchor_def()
     }
 
     #[zome_fn("hc_public")]
-    fn create_section(
-        title: String,
-        course_anchor_address: Address,
+    fn create_my_profile(
+        handle: String,
+        display_name: String,
+        bio: String,
+        avatar: Option<String>,
         timestamp: u64,
     ) -> ZomeApiResult<Address> {
-        section::handlers::create(title, &course_anchor_address, timestamp)
+        profile::handlers::create_my_profile(handle, display_name, bio, avatar, timestamp)
     }
 
     #[zome_fn("hc_public")]
-    fn get_latest_section_entry(
-        section_anchor_address: Address,
-    ) -> ZomeApiResult<Option<section::entry::Section>> {
-        section::handlers::get_latest_section_entry(section_anchor_address)
+    fn update_my_profile(
+        handle: String,
+        display_name: String,
+        bio: String,
+        avatar: Option<String>,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        profile::handlers::update_my_profile(handle, display_name, bio, avatar, timestamp)
     }
 
     #[zome_fn("hc_public")]
-    fn update_section(title: String, section_anchor_address: Address) -> ZomeApiResult<Address> {
-        section::handlers::update(title, &section_anchor_address)
+    fn get_my_profile() -> ZomeApiResult<Option<profile::entry::AgentProfile>> {
+        profile::handlers::get_my_profile()
     }
 
     #[zome_fn("hc_public")]
-    fn delete_section(section_anchor_address: Address) -> ZomeApiResult<Address> {
-        section::handlers::delete(section_anchor_address)
+    fn get_profile(agent_address: Address) -> ZomeApiResult<Option<profile::entry::AgentProfile>> {
+        profile::handlers::get_profile(agent_address)
     }
 
-    //  ====================== Content definitions
+    #[zome_fn("hc_public")]
+    fn get_profile_by_handle(
+        handle: String,
+    ) -> ZomeApiResult<Option<profile::entry::AgentProfile>> {
+        profile::handlers::get_profile_by_handle(handle)
+    }
+
+    #[zome_fn("hc_public")]
+    fn resolve_agents(
+        agent_addresses: Vec<Address>,
+    ) -> ZomeApiResult<Vec<profile::handlers::ResolvedAgent>> {
+        profile::handlers::resolve_agents(agent_addresses)
+    }
 
+    //  ====================== Cohort definitions
     #[entry_def]
-    fn content_entry_definition() -> ValidatingEntryType {
-        content::entry::entry_def()
+    fn cohort_entry_definition() -> ValidatingEntryType {
+        cohort::entry::entry_def()
     }
 
     #[zome_fn("hc_public")]
-    fn create_content(
+    fn create_cohort(
+        course_anchor_address: Address,
         name: String,
-        url: String,
+        start_date: u64,
+        end_date: u64,
+        max_students: Option<u32>,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        cohort::handlers::create(
+            course_anchor_address,
+            name,
+            start_date,
+            end_date,
+            max_students,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_cohort(cohort_address: Address) -> ZomeApiResult<Option<cohort::entry::Cohort>> {
+        cohort::handlers::get_cohort(cohort_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_cohorts(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        cohort::handlers::list_cohorts(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn enroll_in_cohort(
+        cohort_address: Address,
+        timestamp: u64,
+        invite_code: Option<String>,
+    ) -> ZomeApiResult<course::handlers::EnrollmentResult> {
+        cohort::handlers::enroll(cohort_address, timestamp, invite_code)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_cohort_students(cohort_address: Address) -> ZomeApiResult<Vec<Address>> {
+        cohort::handlers::get_students(&cohort_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_cohort(course_anchor_address: Address) -> ZomeApiResult<Option<Address>> {
+        cohort::handlers::get_my_cohort(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_cohort_progress(
+        cohort_address: Address,
+    ) -> ZomeApiResult<Vec<cohort::handlers::CohortStudentProgress>> {
+        cohort::handlers::get_progress(cohort_address)
+    }
+
+    //  ====================== Enrollment request definitions
+    #[entry_def]
+    fn enrollment_request_entry_definition() -> ValidatingEntryType {
+        enrollment_request::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_enrollment_request(
+        enrollment_request_address: Address,
+    ) -> ZomeApiResult<Option<enrollment_request::entry::EnrollmentRequest>> {
+        enrollment_request::handlers::get_enrollment_request(enrollment_request_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_enrollment_requests(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        enrollment_request::handlers::get_enrollment_requests(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_pending_enrollment_requests() -> ZomeApiResult<Vec<Address>> {
+        enrollment_request::handlers::get_my_pending_enrollment_requests()
+    }
+
+    #[zome_fn("hc_public")]
+    fn approve_enrollment(
+        enrollment_request_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        enrollment_request::handlers::approve(enrollment_request_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn reject_enrollment(enrollment_request_address: Address) -> ZomeApiResult<Address> {
+        enrollment_request::handlers::reject(enrollment_request_address)
+    }
+
+    //  ====================== Gradebook definitions
+    // NOTE: gradebook doesn't have entries of it's own, it's computed from scores in other parts of the zome
+
+    #[zome_fn("hc_public")]
+    fn get_gradebook(
+        course_anchor_address: Address,
+        cohort_address: Option<Address>,
+        timestamp: u64,
+    ) -> ZomeApiResult<gradebook::handlers::Gradebook> {
+        gradebook::handlers::get_gradebook(course_anchor_address, cohort_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn export_gradebook_csv(
+        course_anchor_address: Address,
+        cohort_address: Option<Address>,
+        timestamp: u64,
+    ) -> ZomeApiResult<String> {
+        gradebook::handlers::export_gradebook_csv(course_anchor_address, cohort_address, timestamp)
+    }
+
+    //  ====================== Dashboard definitions
+    #[entry_def]
+    fn course_stats_entry_definition() -> ValidatingEntryType {
+        dashboard::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_teacher_dashboard(timestamp: u64) -> ZomeApiResult<Vec<dashboard::entry::CourseStats>> {
+        dashboard::handlers::get_teacher_dashboard(timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn refresh_course_stats(
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<dashboard::entry::CourseStats> {
+        dashboard::handlers::refresh_course_stats(course_anchor_address, timestamp)
+    }
+
+    // refreshes stats that get_teacher_dashboard had to compute because the cached ones were stale
+    #[zome_fn("hc_public")]
+    fn refresh_stale_course_stats(
+        timestamp: u64,
+    ) -> ZomeApiResult<Vec<dashboard::entry::CourseStats>> {
+        dashboard::handlers::refresh_stale_course_stats(timestamp)
+    }
+
+    //  ====================== Invite definitions
+    #[entry_def]
+    fn invite_entry_definition() -> ValidatingEntryType {
+        invite::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn invite_student(
+        course_anchor_address: Address,
+        invitee_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        invite::handlers::invite_student(course_anchor_address, invitee_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_invites() -> ZomeApiResult<Vec<invite::entry::Invite>> {
+        invite::handlers::get_my_invites()
+    }
+
+    //  ====================== Prerequisite definitions
+    // NOTE: prerequisites are links between CourseAnchors so their definitions are in course::anchor
+
+    #[zome_fn("hc_public")]
+    fn add_prerequisite(
+        course_anchor_address: Address,
+        prerequisite_address: Address,
+    ) -> ZomeApiResult<Address> {
+        prerequisite::handlers::add_prerequisite(course_anchor_address, prerequisite_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn remove_prerequisite(
+        course_anchor_address: Address,
+        prerequisite_address: Address,
+    ) -> ZomeApiResult<Address> {
+        prerequisite::handlers::remove_prerequisite(course_anchor_address, prerequisite_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_prerequisites(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        prerequisite::handlers::get_prerequisites(&course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_pathway(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<prerequisite::handlers::PrerequisitePathway> {
+        prerequisite::handlers::get_pathway(course_anchor_address)
+    }
+
+    //  ====================== Pathway definitions
+
+    #[entry_def]
+    fn pathway_catalog_anchor_entry_definition() -> ValidatingEntryType {
+        pathway::catalog_anchor::catalog_anchor_entry_def()
+    }
+
+    #[entry_def]
+    fn pathway_anchor_definition() -> ValidatingEntryType {
+        pathway::anchor::pathway_anchor_def()
+    }
+
+    #[entry_def]
+    fn pathway_entry_definition() -> ValidatingEntryType {
+        pathway::entry::pathway_entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_pathway(
+        title: String,
         description: String,
+        steps: Vec<pathway::entry::PathwayStep>,
         timestamp: u64,
-        section_anchor_address: Address
     ) -> ZomeApiResult<Address> {
-        content::handlers::create(name, url, description, timestamp, section_anchor_address)
+        pathway::handlers::create(title, description, steps, timestamp)
     }
 
     #[zome_fn("hc_public")]
-    fn get_contents(section_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
-        content::handlers::get_contents(&section_anchor_address)
+    fn get_latest_pathway_entry(
+        pathway_anchor_address: Address,
+    ) -> ZomeApiResult<Option<pathway::entry::Pathway>> {
+        pathway::handlers::get_latest_pathway_entry(pathway_anchor_address)
     }
 
     #[zome_fn("hc_public")]
-    fn update_content(
-        content_address: Address,
-        name: String,
-        url: String,
+    fn update_pathway(
+        title: String,
         description: String,
-        section_anchor_address: Address
+        steps: Vec<pathway::entry::PathwayStep>,
+        pathway_anchor_address: Address,
     ) -> ZomeApiResult<Address> {
-        content::handlers::update(content_address, name, url, description, section_anchor_address)
+        pathway::handlers::update(title, description, steps, &pathway_anchor_address)
     }
 
     #[zome_fn("hc_public")]
-    fn delete_content(content_address: Address,section_anchor_address:Address) -> ZomeApiResult<Address> {
-        content::handlers::delete(content_address,section_anchor_address)
+    fn delete_pathway(pathway_anchor_address: Address) -> ZomeApiResult<Address> {
+        pathway::handlers::delete(pathway_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_all_pathways() -> ZomeApiResult<Vec<Address>> {
+        pathway::handlers::list_all_pathways()
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_pathways() -> ZomeApiResult<Vec<Address>> {
+        pathway::handlers::get_my_pathways()
+    }
+
+    #[zome_fn("hc_public")]
+    fn follow_pathway(pathway_anchor_address: Address) -> ZomeApiResult<Address> {
+        pathway::handlers::follow(pathway_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn unfollow_pathway(pathway_anchor_address: Address) -> ZomeApiResult<Address> {
+        pathway::handlers::unfollow(pathway_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_followed_pathways() -> ZomeApiResult<Vec<Address>> {
+        pathway::handlers::get_my_followed_pathways()
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_pathway_progress(
+        pathway_anchor_address: Address,
+    ) -> ZomeApiResult<pathway::handlers::PathwayProgress> {
+        pathway::handlers::get_progress(pathway_anchor_address)
+    }
+
+    //  ====================== Section definitions
+
+    #[entry_def]
+    fn section_anchor_definition() -> ValidatingEntryType {
+        section::anchor::section_anchor_def()
+    }
+
+    #[entry_def]
+    fn section_entry_definition() -> ValidatingEntryType {
+        section::entry::section_entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_section(
+        title: String,
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        section::handlers::create(title, &course_anchor_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_latest_section_entry(
+        section_anchor_address: Address,
+    ) -> ZomeApiResult<Option<section::entry::Section>> {
+        section::handlers::get_latest_section_entry(section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_section(
+        section_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Option<course::handlers::SectionTreeNode>> {
+        section::handlers::get_section(section_anchor_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_section_release(
+        section_anchor_address: Address,
+        release: section::entry::SectionRelease,
+    ) -> ZomeApiResult<Address> {
+        section::handlers::set_release(&section_anchor_address, release)
+    }
+
+    #[zome_fn("hc_public")]
+    fn update_section(title: String, section_anchor_address: Address) -> ZomeApiResult<Address> {
+        section::handlers::update(title, &section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn delete_section(section_anchor_address: Address) -> ZomeApiResult<Address> {
+        section::handlers::delete(section_anchor_address)
+    }
+
+    //  ====================== Content definitions
+
+    #[entry_def]
+    fn content_entry_definition() -> ValidatingEntryType {
+        content::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_content(
+        name: String,
+        url: String,
+        description: String,
+        timestamp: u64,
+        section_anchor_address: Address
+    ) -> ZomeApiResult<Address> {
+        content::handlers::create(name, url, description, timestamp, section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_contents(
+        section_anchor_address: Address,
+        timestamp: Option<u64>,
+    ) -> ZomeApiResult<Vec<Address>> {
+        content::handlers::get_released_contents(&section_anchor_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn update_content(
+        content_address: Address,
+        name: String,
+        url: String,
+        description: String,
+        section_anchor_address: Address
+    ) -> ZomeApiResult<Address> {
+        content::handlers::update(content_address, name, url, description, section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn delete_content(content_address: Address,section_anchor_address:Address) -> ZomeApiResult<Address> {
+        content::handlers::delete(content_address,section_anchor_address)
+    }
+
+    //  ====================== Live session definitions
+    #[entry_def]
+    fn live_session_entry_definition() -> ValidatingEntryType {
+        live_session::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn attendance_entry_definition() -> ValidatingEntryType {
+        live_session::attendance::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn schedule_live_session(
+        section_anchor_address: Address,
+        title: String,
+        start_time: u64,
+        duration: u64,
+        meeting_url: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        live_session::handlers::schedule(
+            section_anchor_address,
+            title,
+            start_time,
+            duration,
+            meeting_url,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_live_session(
+        live_session_address: Address,
+    ) -> ZomeApiResult<Option<live_session::entry::LiveSession>> {
+        live_session::handlers::get_live_session(live_session_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_live_sessions(section_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        live_session::handlers::list_section_sessions(section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn check_in(live_session_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+        live_session::handlers::check_in(live_session_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_attendance(
+        live_session_address: Address,
+    ) -> ZomeApiResult<Vec<live_session::attendance::Attendance>> {
+        live_session::handlers::get_attendance(live_session_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_attendance_summary(
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Vec<live_session::handlers::StudentAttendance>> {
+        live_session::handlers::get_attendance_summary(course_anchor_address, timestamp)
+    }
+
+    //  ====================== Assignment definitions
+    #[entry_def]
+    fn assignment_entry_definition() -> ValidatingEntryType {
+        assignment::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn submission_entry_definition() -> ValidatingEntryType {
+        assignment::submission::entry_def()
+    }
+
+    #[entry_def]
+    fn grade_entry_definition() -> ValidatingEntryType {
+        assignment::grade::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_assignment(
+        course_anchor_address: Address,
+        title: String,
+        description: String,
+        due_date: u64,
+        max_points: u32,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        assignment::handlers::create(
+            course_anchor_address,
+            title,
+            description,
+            due_date,
+            max_points,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_assignment(
+        assignment_address: Address,
+    ) -> ZomeApiResult<Option<assignment::entry::Assignment>> {
+        assignment::handlers::get_assignment(assignment_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_assignments(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        assignment::handlers::list_assignments(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn attach_rubric(
+        assignment_address: Address,
+        rubric_address: Option<Address>,
+    ) -> ZomeApiResult<Address> {
+        assignment::handlers::attach_rubric(assignment_address, rubric_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn submit_assignment(
+        assignment_address: Address,
+        text: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        assignment::handlers::submit(assignment_address, text, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_submissions(assignment_address: Address) -> ZomeApiResult<Vec<Address>> {
+        assignment::handlers::get_submissions(assignment_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn grade_submission(
+        submission_address: Address,
+        selected_levels: Vec<usize>,
+        points: Option<u32>,
+        feedback: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        assignment::handlers::grade(
+            submission_address,
+            selected_levels,
+            points,
+            feedback,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_grade(submission_address: Address) -> ZomeApiResult<Option<assignment::grade::Grade>> {
+        assignment::handlers::get_grade(submission_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_grade(
+        assignment_address: Address,
+    ) -> ZomeApiResult<Option<assignment::grade::Grade>> {
+        assignment::handlers::get_my_grade(assignment_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_peer_review_count(
+        assignment_address: Address,
+        peer_review_count: u32,
+    ) -> ZomeApiResult<Address> {
+        assignment::handlers::set_peer_review_count(assignment_address, peer_review_count)
+    }
+
+    //  ====================== Peer review definitions
+    #[entry_def]
+    fn peer_review_entry_definition() -> ValidatingEntryType {
+        peer_review::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn peer_review_round_entry_definition() -> ValidatingEntryType {
+        peer_review::round::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn start_peer_review(assignment_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+        peer_review::handlers::start(assignment_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_review_assignments(
+        assignment_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Vec<Address>> {
+        peer_review::handlers::get_my_review_assignments(assignment_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn submit_peer_review(
+        submission_address: Address,
+        score: u32,
+        feedback: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        peer_review::handlers::submit(submission_address, score, feedback, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_received_peer_reviews(
+        assignment_address: Address,
+    ) -> ZomeApiResult<Option<peer_review::handlers::ReceivedPeerReviews>> {
+        peer_review::handlers::get_received_reviews(assignment_address)
+    }
+
+    //  ====================== Rubric definitions
+    #[entry_def]
+    fn rubric_entry_definition() -> ValidatingEntryType {
+        rubric::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_rubric(
+        title: String,
+        criteria: Vec<rubric::entry::RubricCriterion>,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        rubric::handlers::create(title, criteria, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn clone_rubric(
+        rubric_address: Address,
+        title: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        rubric::handlers::clone(rubric_address, title, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_rubric(rubric_address: Address) -> ZomeApiResult<Option<rubric::entry::Rubric>> {
+        rubric::handlers::get_rubric(rubric_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_rubrics() -> ZomeApiResult<Vec<Address>> {
+        rubric::handlers::get_my_rubrics()
+    }
+
+    //  ====================== Question definitions
+    #[entry_def]
+    fn question_entry_definition() -> ValidatingEntryType {
+        question::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_question(
+        text: String,
+        options: Vec<String>,
+        correct_option: usize,
+        topics: Vec<String>,
+        difficulty: question::entry::QuestionDifficulty,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        question::handlers::create(text, options, correct_option, topics, difficulty, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_question(question_address: Address) -> ZomeApiResult<Option<question::entry::Question>> {
+        question::handlers::get_question(question_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_questions() -> ZomeApiResult<Vec<Address>> {
+        question::handlers::get_my_questions()
+    }
+
+    //  ====================== Quiz definitions
+    #[entry_def]
+    fn quiz_entry_definition() -> ValidatingEntryType {
+        quiz::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn quiz_attempt_entry_definition() -> ValidatingEntryType {
+        quiz::attempt::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_quiz(
+        course_anchor_address: Address,
+        title: String,
+        topics: Vec<String>,
+        difficulty: Option<question::entry::QuestionDifficulty>,
+        question_count: u32,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        quiz::handlers::create(
+            course_anchor_address,
+            title,
+            topics,
+            difficulty,
+            question_count,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_quiz(quiz_address: Address) -> ZomeApiResult<Option<quiz::entry::Quiz>> {
+        quiz::handlers::get_quiz(quiz_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_quizzes(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        quiz::handlers::list_quizzes(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn preview_quiz(
+        quiz_address: Address,
+        student_address: Address,
+    ) -> ZomeApiResult<Vec<quiz::handlers::GeneratedQuestion>> {
+        quiz::handlers::preview(quiz_address, student_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_quiz_questions(
+        quiz_address: Address,
+    ) -> ZomeApiResult<Vec<quiz::handlers::QuizQuestion>> {
+        quiz::handlers::get_my_questions(quiz_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn submit_quiz_attempt(
+        quiz_address: Address,
+        answers: Vec<usize>,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        quiz::handlers::submit_attempt(quiz_address, answers, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_quiz_attempt(
+        quiz_address: Address,
+    ) -> ZomeApiResult<Option<quiz::attempt::QuizAttempt>> {
+        quiz::handlers::get_my_attempt(quiz_address)
+    }
+
+    //  ====================== Flashcard definitions
+    #[entry_def]
+    fn flashcard_entry_definition() -> ValidatingEntryType {
+        flashcard::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn flashcard_review_entry_definition() -> ValidatingEntryType {
+        flashcard::review::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_flashcard(
+        section_anchor_address: Address,
+        front: String,
+        back: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        flashcard::handlers::create(section_anchor_address, front, back, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_flashcard(
+        flashcard_address: Address,
+    ) -> ZomeApiResult<Option<flashcard::entry::Flashcard>> {
+        flashcard::handlers::get_flashcard(flashcard_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_section_flashcards(section_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+        flashcard::handlers::get_section_flashcards(section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_due_flashcards(
+        timestamp: u64,
+        utc_offset: i64,
+    ) -> ZomeApiResult<Vec<flashcard::handlers::DueFlashcard>> {
+        flashcard::handlers::get_due(timestamp, utc_offset)
+    }
+
+    #[zome_fn("hc_public")]
+    fn review_flashcard(
+        flashcard_address: Address,
+        grade: u8,
+        timestamp: u64,
+    ) -> ZomeApiResult<flashcard::scheduler::ReviewState> {
+        flashcard::handlers::review(flashcard_address, grade, timestamp)
+    }
+
+    //  ====================== Note definitions
+    #[entry_def]
+    fn note_entry_definition() -> ValidatingEntryType {
+        note::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn bookmark_entry_definition() -> ValidatingEntryType {
+        note::bookmark::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_note(
+        target: note::entry::AnnotationTarget,
+        range: Option<note::entry::TextRange>,
+        text: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        note::handlers::create_note(target, range, text, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn edit_note(
+        note_address: Address,
+        range: Option<note::entry::TextRange>,
+        text: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        note::handlers::edit_note(note_address, range, text, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn delete_note(note_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+        note::handlers::delete_note(note_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_notes(course_anchor_address: Address) -> ZomeApiResult<Vec<note::handlers::MyNote>> {
+        note::handlers::get_my_notes(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn export_my_notes(course_anchor_address: Address) -> ZomeApiResult<String> {
+        note::handlers::export_notes_markdown(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_bookmark(
+        target: note::entry::AnnotationTarget,
+        range: Option<note::entry::TextRange>,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        note::handlers::create_bookmark(target, range, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn delete_bookmark(bookmark_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+        note::handlers::delete_bookmark(bookmark_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_bookmarks(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<Vec<note::handlers::MyBookmark>> {
+        note::handlers::get_my_bookmarks(course_anchor_address)
+    }
+
+    //  ====================== Analytics definitions
+    #[entry_def]
+    fn learning_event_entry_definition() -> ValidatingEntryType {
+        analytics::event::entry_def()
+    }
+
+    #[entry_def]
+    fn analytics_consent_entry_definition() -> ValidatingEntryType {
+        analytics::consent::entry_def()
+    }
+
+    #[entry_def]
+    fn section_activity_entry_definition() -> ValidatingEntryType {
+        analytics::activity::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn set_analytics_consent(
+        course_anchor_address: Address,
+        tracking: bool,
+        share_identity: bool,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        analytics::handlers::set_consent(course_anchor_address, tracking, share_identity, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_analytics_consent(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<Option<analytics::consent::AnalyticsConsent>> {
+        analytics::handlers::get_my_consent(&course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn record_learning_event(
+        kind: analytics::event::LearningEventKind,
+        section_anchor_address: Address,
+        timestamp: u64,
+    ) -> ZomeApiResult<Option<Address>> {
+        analytics::handlers::record_event(kind, section_anchor_address, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_section_activity(
+        section_anchor_address: Address,
+    ) -> ZomeApiResult<Vec<analytics::activity::SectionActivity>> {
+        analytics::handlers::get_section_activity(section_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_section_drop_off(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<Vec<analytics::handlers::SectionDropOff>> {
+        analytics::handlers::get_drop_off(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_time_on_content_histogram(
+        section_anchor_address: Address,
+        bucket_size: u64,
+    ) -> ZomeApiResult<Vec<analytics::aggregate::HistogramBucket>> {
+        analytics::handlers::get_time_on_content_histogram(section_anchor_address, bucket_size)
+    }
+
+    //  ====================== Signal definitions
+
+    #[entry_def]
+    fn signal_outbox_entry_definition() -> ValidatingEntryType {
+        signal::outbox::outbox_entry_def()
+    }
+
+    #[entry_def]
+    fn signal_delivery_entry_definition() -> ValidatingEntryType {
+        signal::outbox::delivery_entry_def()
+    }
+
+    // sends signals about our changes to the next few agents that haven't got them yet.
+    // Returns how many agents are still waiting, UI keeps calling it while that's more than 0
+    #[zome_fn("hc_public")]
+    fn send_pending_signals() -> ZomeApiResult<usize> {
+        signal::handlers::send_pending()
+    }
+
+    //  ====================== Review definitions
+
+    #[entry_def]
+    fn review_entry_definition() -> ValidatingEntryType {
+        review::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn review_course(
+        course_anchor_address: Address,
+        rating: u8,
+        text: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        review::handlers::review_course(course_anchor_address, rating, text, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_my_review(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<Option<review::entry::Review>> {
+        review::handlers::get_my_review(course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_reviews(
+        course_anchor_address: Address,
+        page_number: usize,
+        page_size: usize,
+    ) -> ZomeApiResult<Vec<review::entry::Review>> {
+        review::handlers::get_reviews(course_anchor_address, page_number, page_size)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_course_rating(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<review::handlers::CourseRating> {
+        review::handlers::get_course_rating(&course_anchor_address)
+    }
+
+    //  ====================== Announcement definitions
+
+    #[entry_def]
+    fn announcement_entry_definition() -> ValidatingEntryType {
+        announcement::entry::entry_def()
+    }
+
+    #[entry_def]
+    fn announcement_read_marker_entry_definition() -> ValidatingEntryType {
+        announcement::read_marker::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn post_announcement(
+        course_anchor_address: Address,
+        title: String,
+        body: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        announcement::handlers::post(course_anchor_address, title, body, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn list_announcements(
+        course_anchor_address: Address,
+        since: u64,
+    ) -> ZomeApiResult<Vec<announcement::entry::Announcement>> {
+        announcement::handlers::list(&course_anchor_address, since)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_unread_announcements(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<Vec<announcement::entry::Announcement>> {
+        announcement::handlers::get_unread(&course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn mark_announcements_read(
+        course_anchor_address: Address,
+        read_until: u64,
+    ) -> ZomeApiResult<Address> {
+        announcement::handlers::mark_read(course_anchor_address, read_until)
+    }
+
+    //  ====================== Comment definitions
+
+    #[entry_def]
+    fn comment_entry_definition() -> ValidatingEntryType {
+        comment::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn create_comment(
+        target_address: Address,
+        section_anchor_address: Address,
+        parent_address: Option<Address>,
+        text: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        comment::handlers::create(
+            target_address,
+            section_anchor_address,
+            parent_address,
+            text,
+            timestamp,
+        )
+    }
+
+    #[zome_fn("hc_public")]
+    fn edit_comment(comment_address: Address, text: String) -> ZomeApiResult<Address> {
+        comment::handlers::edit(comment_address, text)
+    }
+
+    #[zome_fn("hc_public")]
+    fn delete_comment(comment_address: Address) -> ZomeApiResult<Address> {
+        comment::handlers::delete(comment_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn mark_comment_as_answer(comment_address: Address) -> ZomeApiResult<Address> {
+        comment::handlers::mark_as_answer(comment_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn upvote_comment(comment_address: Address) -> ZomeApiResult<Address> {
+        comment::handlers::upvote(comment_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn get_thread(
+        target_address: Address,
+        page_number: usize,
+        page_size: usize,
+    ) -> ZomeApiResult<Vec<comment::handlers::CommentNode>> {
+        comment::handlers::get_thread(target_address, page_number, page_size)
+    }
+
+    //  ====================== Moderation definitions
+
+    #[entry_def]
+    fn moderation_anchor_entry_definition() -> ValidatingEntryType {
+        moderation::anchor::moderation_anchor_entry_def()
+    }
+
+    #[entry_def]
+    fn flag_entry_definition() -> ValidatingEntryType {
+        moderation::entry::entry_def()
+    }
+
+    #[zome_fn("hc_public")]
+    fn flag(
+        target: moderation::entry::FlagTarget,
+        reason: moderation::entry::FlagReason,
+        details: String,
+        timestamp: u64,
+    ) -> ZomeApiResult<Address> {
+        moderation::handlers::flag(target, reason, details, timestamp)
+    }
+
+    #[zome_fn("hc_public")]
+    fn list_course_flags(
+        course_anchor_address: Address,
+    ) -> ZomeApiResult<Vec<moderation::entry::Flag>> {
+        moderation::handlers::list_course_flags(&course_anchor_address)
+    }
+
+    #[zome_fn("hc_public")]
+    fn list_all_flags() -> ZomeApiResult<Vec<moderation::entry::Flag>> {
+        moderation::handlers::list_all_flags()
+    }
+
+    #[zome_fn("hc_public")]
+    fn hide(target: moderation::entry::FlagTarget) -> ZomeApiResult<Address> {
+        moderation::handlers::hide(target)
+    }
+
+    #[zome_fn("hc_public")]
+    fn unhide(target: moderation::entry::FlagTarget) -> ZomeApiResult<Address> {
+        moderation::handlers::unhide(target)
     }
 }
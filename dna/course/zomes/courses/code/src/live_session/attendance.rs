@@ -0,0 +1,103 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{LiveSession, CHECK_IN_OPENS_BEFORE_START};
+use crate::calendar::ical::parse_date_time;
+
+pub const LIVE_SESSION_TO_ATTENDANCE_LINK: &str = "live_session->attendance";
+
+// Record of a student checking in to a live session
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Attendance {
+    pub live_session_address: Address,
+    pub student_address: Address,
+    pub timestamp: u64,
+}
+
+impl Attendance {
+    pub fn new(live_session_address: Address, student_address: Address, timestamp: u64) -> Self {
+        Attendance {
+            live_session_address: live_session_address,
+            student_address: student_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Attendance {
+    fn entry_type() -> String {
+        String::from("attendance")
+    }
+}
+
+// how far the clock of the student can be off when we compare it with the session time, in seconds
+const MAX_CLOCK_SKEW: u64 = 5 * 60;
+
+// checks that student checked in while the session was running. Timestamp in the entry comes
+// from the student's UI, so we also check the time of the commit in the student's source chain.
+// It's still set by the student's device, but it's signed and can't go back past their previous
+// commits, which makes backdating a check-in much harder
+fn validate_check_in(attendance: &Attendance, committed_at: &str) -> Result<(), String> {
+    let live_session: LiveSession =
+        hdk::utils::get_as_type(attendance.live_session_address.clone())
+            .map_err(|_| "Can't find the live session".to_owned())?;
+    if !live_session.is_check_in_open(attendance.timestamp) {
+        return Err("Check-in is only open while the session is running".to_owned());
+    }
+    let committed_at = parse_date_time(committed_at)
+        .ok_or_else(|| "Can't read time of the check-in".to_owned())?;
+    let opens_at = live_session
+        .start_time
+        .saturating_sub(CHECK_IN_OPENS_BEFORE_START + MAX_CLOCK_SKEW);
+    let closes_at = live_session.end_time().saturating_add(MAX_CLOCK_SKEW);
+    if committed_at < opens_at || committed_at > closes_at {
+        return Err("Check-in was committed while the session wasn't running".to_owned());
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Attendance
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Attendance::entry_type(),
+        description: "this is the definition of a student's attendance of a live session",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Attendance>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    // only the student themselves can check in
+                    if !validation_data.sources().contains(&entry.student_address) {
+                        return Err("Students can only check in themselves".to_owned());
+                    }
+                    validate_check_in(
+                        &entry,
+                        &validation_data.package.chain_header.timestamp().to_string(),
+                    )
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Attendance can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the session that student has attended.
+            // Tag of this link is an address of the student so we can quickly check their attendance
+            from!(
+                LiveSession::entry_type(),
+                link_type: LIVE_SESSION_TO_ATTENDANCE_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
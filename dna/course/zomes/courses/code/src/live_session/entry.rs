@@ -0,0 +1,184 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+use crate::section::anchor::SectionAnchor;
+
+pub const SECTION_ANCHOR_TO_LIVE_SESSION_LINK: &str = "section_anchor->live_session";
+// live sessions can't be longer than this, in seconds
+pub const MAX_DURATION: u64 = 24 * 60 * 60;
+// how long before the start of a session students can check in, in seconds
+pub const CHECK_IN_OPENS_BEFORE_START: u64 = 15 * 60;
+
+// Scheduled live lesson of a section
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct LiveSession {
+    pub title: String,
+    pub start_time: u64,
+    // in seconds
+    pub duration: u64,
+    pub meeting_url: String,
+    pub section_anchor_address: Address,
+    pub course_anchor_address: Address,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl LiveSession {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        title: String,
+        start_time: u64,
+        duration: u64,
+        meeting_url: String,
+        section_anchor_address: Address,
+        course_anchor_address: Address,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        LiveSession {
+            title: title,
+            start_time: start_time,
+            duration: duration,
+            meeting_url: meeting_url,
+            section_anchor_address: section_anchor_address,
+            course_anchor_address: course_anchor_address,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+
+    pub fn end_time(&self) -> u64 {
+        self.start_time.saturating_add(self.duration)
+    }
+
+    // students can check in from a bit before the start of the session until it ends
+    pub fn is_check_in_open(&self, timestamp: u64) -> bool {
+        timestamp.saturating_add(CHECK_IN_OPENS_BEFORE_START) >= self.start_time
+            && timestamp <= self.end_time()
+    }
+}
+
+impl HolochainEntry for LiveSession {
+    fn entry_type() -> String {
+        String::from("live_session")
+    }
+}
+
+pub fn validate_schedule(start_time: u64, duration: u64) -> Result<(), String> {
+    if duration == 0 {
+        return Err("Live session can't be empty".to_owned());
+    }
+    if duration > MAX_DURATION {
+        return Err("Live session can't be longer than a day".to_owned());
+    }
+    if start_time.checked_add(duration).is_none() {
+        return Err("Live session ends too far in the future".to_owned());
+    }
+    Ok(())
+}
+
+// meeting link is shown to students and exported to their calendars,
+// so it has to be a plain web address
+pub fn validate_meeting_url(meeting_url: &str) -> Result<(), String> {
+    let lowercase_url = meeting_url.to_lowercase();
+    if !lowercase_url.starts_with("http://") && !lowercase_url.starts_with("https://") {
+        return Err("Meeting URL should start with http:// or https://".to_owned());
+    }
+    if meeting_url
+        .chars()
+        .any(|c| c.is_control() || c.is_whitespace())
+    {
+        return Err("Meeting URL can't contain spaces or control characters".to_owned());
+    }
+    Ok(())
+}
+
+// checks that the session was scheduled by the teacher of the course it's section belongs to
+fn validate_author(live_session: &LiveSession, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&live_session.teacher_address) {
+        return Err("Live session can only be scheduled by the teacher of the course".to_owned());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(live_session.course_anchor_address.clone())
+            .map_err(|_| "Can't find the course of this live session".to_owned())?;
+    if live_session.teacher_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can schedule it's live sessions".to_owned());
+    }
+    let section_anchor: SectionAnchor =
+        hdk::utils::get_as_type(live_session.section_anchor_address.clone())
+            .map_err(|_| "Can't find the section of this live session".to_owned())?;
+    if section_anchor.course_anchor_address != live_session.course_anchor_address {
+        return Err("Section doesn't belong to the course of this live session".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_live_session_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let live_session: LiveSession = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked live session".to_owned())?;
+    if !sources.contains(&live_session.teacher_address) {
+        return Err("Only the teacher of the course can link it's live sessions".to_owned());
+    }
+    if *link_data.link.base() != live_session.section_anchor_address {
+        return Err("Live session can only be linked from it's section".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_live_session_link_change(
+    validation_data: hdk::LinkValidationData,
+) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_live_session_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_live_session_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for LiveSession
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: LiveSession::entry_type(),
+        description: "this is the definition of a scheduled live session of a section",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<LiveSession>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_schedule(entry.start_time, entry.duration)?;
+                    validate_meeting_url(&entry.meeting_url)?;
+                    validate_author(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Live sessions can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the section this session is a part of
+            from!(
+                SectionAnchor::entry_type(),
+                link_type: SECTION_ANCHOR_TO_LIVE_SESSION_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_live_session_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,206 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::attendance::{Attendance, LIVE_SESSION_TO_ATTENDANCE_LINK};
+use super::entry::{
+    validate_meeting_url, validate_schedule, LiveSession, SECTION_ANCHOR_TO_LIVE_SESSION_LINK,
+};
+use crate::course;
+use crate::section;
+use crate::section::anchor::SectionAnchor;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct StudentAttendance {
+    pub student_address: Address,
+    pub attended_sessions: usize,
+    // number of sessions of the course that have already started
+    pub total_sessions: usize,
+}
+
+pub fn schedule(
+    section_anchor_address: Address,
+    title: String,
+    start_time: u64,
+    duration: u64,
+    meeting_url: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    course::handlers::ensure_teacher(&section_anchor.course_anchor_address)?;
+    validate_schedule(start_time, duration).map_err(ZomeApiError::from)?;
+    validate_meeting_url(&meeting_url).map_err(ZomeApiError::from)?;
+
+    let live_session = LiveSession::new(
+        title,
+        start_time,
+        duration,
+        meeting_url,
+        section_anchor_address.clone(),
+        section_anchor.course_anchor_address,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let live_session_address = hdk::commit_entry(&live_session.entry())?;
+
+    hdk::link_entries(
+        &section_anchor_address,
+        &live_session_address,
+        SECTION_ANCHOR_TO_LIVE_SESSION_LINK,
+        "",
+    )?;
+
+    Ok(live_session_address)
+}
+
+pub fn get_live_session(live_session_address: Address) -> ZomeApiResult<Option<LiveSession>> {
+    match hdk::get_entry(&live_session_address)? {
+        Some(entry) => Ok(LiveSession::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+// returns live sessions of the section ordered by their start time
+pub fn get_section_sessions(
+    section_anchor_address: &Address,
+) -> ZomeApiResult<Vec<(LiveSession, Address)>> {
+    let live_session_addresses = hdk::get_links(
+        section_anchor_address,
+        LinkMatch::Exactly(SECTION_ANCHOR_TO_LIVE_SESSION_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut live_sessions = Vec::new();
+    for live_session_address in live_session_addresses {
+        let live_session: LiveSession = hdk::utils::get_as_type(live_session_address.clone())?;
+        live_sessions.push((live_session, live_session_address));
+    }
+    live_sessions.sort_by_key(|(live_session, _live_session_address)| live_session.start_time);
+    Ok(live_sessions)
+}
+
+// wrapper for the get_section_sessions that only returns addresses of sessions
+pub fn list_section_sessions(section_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+    let live_sessions = get_section_sessions(&section_anchor_address)?;
+    Ok(live_sessions
+        .into_iter()
+        .map(|(_live_session, live_session_address)| live_session_address)
+        .collect())
+}
+
+// returns live sessions of all sections of the course
+pub fn get_course_sessions(
+    course_anchor_address: &Address,
+) -> ZomeApiResult<Vec<(LiveSession, Address)>> {
+    let course = match course::handlers::get_latest_course(course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut live_sessions = Vec::new();
+    for section_anchor_address in course.sections.iter() {
+        // skip sessions of sections that were deleted
+        if section::handlers::get_latest_section(section_anchor_address)?.is_none() {
+            continue;
+        }
+        live_sessions.extend(get_section_sessions(section_anchor_address)?);
+    }
+    Ok(live_sessions)
+}
+
+pub fn has_attended(
+    live_session_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<bool> {
+    let attendance_count = hdk::get_links_count(
+        live_session_address,
+        LinkMatch::Exactly(LIVE_SESSION_TO_ATTENDANCE_LINK),
+        LinkMatch::Exactly(&student_address.to_string()),
+    )?
+    .count;
+    Ok(attendance_count > 0)
+}
+
+// records that the agent that calls this function attends the session
+pub fn check_in(live_session_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+    let live_session: LiveSession = hdk::utils::get_as_type(live_session_address.clone())?;
+    if !course::handlers::is_enrolled(&live_session.course_anchor_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "Only students enrolled in this course can check in".to_owned(),
+        ));
+    }
+    if !live_session.is_check_in_open(timestamp) {
+        return Err(ZomeApiError::from(
+            "Check-in is only open while the session is running".to_owned(),
+        ));
+    }
+    if has_attended(&live_session_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "You've already checked in to this session".to_owned(),
+        ));
+    }
+
+    let attendance = Attendance::new(
+        live_session_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let attendance_address = hdk::commit_entry(&attendance.entry())?;
+
+    hdk::link_entries(
+        &live_session_address,
+        &attendance_address,
+        LIVE_SESSION_TO_ATTENDANCE_LINK,
+        AGENT_ADDRESS.to_string().as_str(),
+    )?;
+
+    Ok(attendance_address)
+}
+
+pub fn get_attendance(live_session_address: Address) -> ZomeApiResult<Vec<Attendance>> {
+    let live_session: LiveSession = hdk::utils::get_as_type(live_session_address.clone())?;
+    course::handlers::ensure_teacher(&live_session.course_anchor_address)?;
+
+    let attendance_addresses = hdk::get_links(
+        &live_session_address,
+        LinkMatch::Exactly(LIVE_SESSION_TO_ATTENDANCE_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    attendance_addresses
+        .into_iter()
+        .map(|attendance_address| hdk::utils::get_as_type(attendance_address))
+        .collect()
+}
+
+// returns how many of the sessions that have started before timestamp each student of the course attended
+pub fn get_attendance_summary(
+    course_anchor_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<Vec<StudentAttendance>> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let started_sessions: Vec<Address> = get_course_sessions(&course_anchor_address)?
+        .into_iter()
+        .filter(|(live_session, _live_session_address)| live_session.start_time <= timestamp)
+        .map(|(_live_session, live_session_address)| live_session_address)
+        .collect();
+
+    let mut summary = Vec::new();
+    for student_address in course::handlers::get_students(course_anchor_address.clone())? {
+        let mut attended_sessions = 0;
+        for live_session_address in started_sessions.iter() {
+            if has_attended(live_session_address, &student_address)? {
+                attended_sessions += 1;
+            }
+        }
+        summary.push(StudentAttendance {
+            student_address: student_address,
+            attended_sessions: attended_sessions,
+            total_sessions: started_sessions.len(),
+        });
+    }
+    Ok(summary)
+}
@@ -0,0 +1,3 @@
+pub mod attendance;
+pub mod entry;
+pub mod handlers;
@@ -0,0 +1,168 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::Flag;
+use crate::anchor_trait::AnchorTrait;
+use crate::comment::entry::Comment;
+use crate::content::entry::Content;
+use crate::course::anchor::CourseAnchor;
+use crate::moderation;
+use crate::section::anchor::{SectionAnchor, SECTION_TO_CONTENT_LINK};
+
+pub const HIDDEN_COURSE_ANCHOR_LINK: &str = "hidden_course_anchor";
+pub const HIDDEN_CONTENT_LINK: &str = "hidden_content";
+pub const HIDDEN_COMMENT_LINK: &str = "hidden_comment";
+
+#[derive(Serialize, Deserialize, Debug, self::DefaultJson, Clone)]
+pub struct ModerationAnchor {
+    name: String,
+}
+
+impl AnchorTrait for ModerationAnchor {
+    fn entry_type() -> String {
+        String::from("moderation_anchor")
+    }
+    fn link_to() -> String {
+        Flag::entry_type()
+    }
+    fn link_type() -> String {
+        // there'll be a single link per every flag
+        "moderation_anchor->flag".to_owned()
+    }
+}
+
+impl ModerationAnchor {
+    pub fn new() -> Self {
+        ModerationAnchor {
+            name: ModerationAnchor::entry_type(),
+        }
+    }
+}
+
+// returns address of the course that the item behind a hidden link belongs to
+fn get_hidden_item_course(link_data: &LinkData, link_type: &str) -> Result<Address, String> {
+    let target_address = link_data.link.target();
+    match link_type {
+        HIDDEN_COURSE_ANCHOR_LINK => Ok(target_address.clone()),
+        HIDDEN_COMMENT_LINK => {
+            let comment: Comment = hdk::utils::get_as_type(target_address.clone())
+                .map_err(|_| "Can't find the hidden comment".to_owned())?;
+            Ok(comment.course_anchor_address)
+        }
+        _ => {
+            // hidden content is tagged with the section it was hidden in
+            let section_anchor_address = Address::from(link_data.link.tag().clone());
+            let section_anchor: SectionAnchor =
+                hdk::utils::get_as_type(section_anchor_address.clone())
+                    .map_err(|_| "Can't find the section of hidden content".to_owned())?;
+            let section_contents = hdk::get_links(
+                &section_anchor_address,
+                LinkMatch::Exactly(SECTION_TO_CONTENT_LINK),
+                LinkMatch::Any,
+            )
+            .map_err(|_| "Can't check if content is a part of the section".to_owned())?
+            .addresses();
+            if !section_contents.contains(target_address) {
+                return Err(
+                    "Hidden content isn't a part of the section it's tagged with".to_owned(),
+                );
+            }
+            Ok(section_anchor.course_anchor_address)
+        }
+    }
+}
+
+// items can only be hidden and unhidden by moderators or by the teacher of the course they belong to
+fn validate_hidden_link(
+    link_data: &LinkData,
+    link_type: &str,
+    sources: &[Address],
+) -> Result<(), String> {
+    let moderators = moderation::handlers::get_moderators()
+        .map_err(|_| "Can't find moderators of this DNA".to_owned())?;
+    if sources.iter().any(|source| moderators.contains(source)) {
+        return Ok(());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(get_hidden_item_course(link_data, link_type)?)
+            .map_err(|_| "Can't find the course of the hidden item".to_owned())?;
+    if !sources.contains(&course_anchor.teacher_address) {
+        return Err("Only moderators and the teacher of the course can hide it's items".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_hidden_link_change(
+    validation_data: hdk::LinkValidationData,
+    link_type: &str,
+) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_hidden_link(&link, link_type, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_hidden_link(&link, link_type, &validation_data.sources()),
+    }
+}
+
+//// Anchor Definition : This Anchor will be used to query all flags and hidden items
+pub fn moderation_anchor_entry_def() -> ValidatingEntryType {
+    entry!(
+        name: ModerationAnchor::entry_type(),
+        description:"Anchor that keeps track of all flags and of items hidden by moderators",
+        sharing: Sharing::Public,
+        validation_package:||{
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation:|_validation_data: hdk::EntryValidationData<ModerationAnchor>|{
+            Ok(())
+        },
+        links:[
+            to!(
+                ModerationAnchor::link_to(),
+                link_type: ModerationAnchor::link_type(),
+                validation_package:||{
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation:|_validation_data: hdk::LinkValidationData|{
+                    Ok(())
+                }
+            ),
+            // links to every item that was hidden, one link type per kind of item
+            to!(
+                CourseAnchor::entry_type(),
+                link_type: HIDDEN_COURSE_ANCHOR_LINK,
+                validation_package:||{
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation:|validation_data: hdk::LinkValidationData|{
+                    validate_hidden_link_change(validation_data, HIDDEN_COURSE_ANCHOR_LINK)
+                }
+            ),
+            to!(
+                Content::entry_type(),
+                link_type: HIDDEN_CONTENT_LINK,
+                validation_package:||{
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation:|validation_data: hdk::LinkValidationData|{
+                    validate_hidden_link_change(validation_data, HIDDEN_CONTENT_LINK)
+                }
+            ),
+            to!(
+                Comment::entry_type(),
+                link_type: HIDDEN_COMMENT_LINK,
+                validation_package:||{
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation:|validation_data: hdk::LinkValidationData|{
+                    validate_hidden_link_change(validation_data, HIDDEN_COMMENT_LINK)
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,111 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_FLAG_LINK: &str = "course_anchor->flag";
+
+// item that a flag is raised against
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum FlagTarget {
+    Course {
+        course_anchor_address: Address,
+    },
+    // content is reusable in other sections, so we need to know which one it was flagged in
+    Content {
+        content_address: Address,
+        section_anchor_address: Address,
+    },
+    Comment {
+        comment_address: Address,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum FlagReason {
+    Spam,
+    Abuse,
+    Harassment,
+    Copyright,
+    OffTopic,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Flag {
+    pub target: FlagTarget,
+    pub reason: FlagReason,
+    pub details: String,
+    // course that flagged item belongs to, so that it's teacher could review the flag
+    pub course_anchor_address: Address,
+    pub author_address: Address,
+    pub timestamp: u64,
+}
+
+impl Flag {
+    pub fn new(
+        target: FlagTarget,
+        reason: FlagReason,
+        details: String,
+        course_anchor_address: Address,
+        author_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Flag {
+            target: target,
+            reason: reason,
+            details: details,
+            course_anchor_address: course_anchor_address,
+            author_address: author_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Flag {
+    fn entry_type() -> String {
+        String::from("flag")
+    }
+}
+
+// Holochain entry definition for Flag
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Flag::entry_type(),
+        description: "this is the definition of a flag that reports inappropriate content",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Flag>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if !validation_data.sources().contains(&entry.author_address) {
+                        return Err("Flag can only be raised by it's author".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Flags can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course that flagged item belongs to
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_FLAG_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,241 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::anchor::{
+    ModerationAnchor, HIDDEN_COMMENT_LINK, HIDDEN_CONTENT_LINK, HIDDEN_COURSE_ANCHOR_LINK,
+};
+use super::entry::{Flag, FlagReason, FlagTarget, COURSE_ANCHOR_TO_FLAG_LINK};
+use crate::anchor_trait::AnchorTrait;
+use crate::comment::entry::Comment;
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+use crate::section::anchor::SectionAnchor;
+
+// name of the DNA property with the list of agent addresses that can moderate any course.
+// See properties in app.json
+pub const MODERATORS_PROPERTY: &str = "moderators";
+
+pub fn get_moderators() -> ZomeApiResult<Vec<Address>> {
+    let properties: serde_json::Value = serde_json::from_str(&hdk::api::PROPERTIES.to_string())
+        .map_err(|_| ZomeApiError::from("DNA properties could not be parsed as JSON".to_owned()))?;
+    match properties.get(MODERATORS_PROPERTY) {
+        Some(moderators_json) => serde_json::from_value(moderators_json.clone()).map_err(|_| {
+            ZomeApiError::from(format!(
+                "DNA property {} should be a list of agent addresses",
+                MODERATORS_PROPERTY
+            ))
+        }),
+        // DNA doesn't designate any moderators, so only teachers can moderate their courses
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn is_moderator(agent_address: &Address) -> ZomeApiResult<bool> {
+    Ok(get_moderators()?.contains(agent_address))
+}
+
+// returns address of the course that flagged item belongs to
+fn get_target_course(target: &FlagTarget) -> ZomeApiResult<Address> {
+    match target {
+        FlagTarget::Course {
+            course_anchor_address,
+        } => {
+            // retrieving course anchor to make sure that it exists
+            let _course_anchor: CourseAnchor =
+                hdk::utils::get_as_type(course_anchor_address.clone())?;
+            Ok(course_anchor_address.clone())
+        }
+        FlagTarget::Content {
+            section_anchor_address,
+            ..
+        } => {
+            let section_anchor: SectionAnchor =
+                hdk::utils::get_as_type(section_anchor_address.clone())?;
+            Ok(section_anchor.course_anchor_address)
+        }
+        FlagTarget::Comment { comment_address } => {
+            let comment: Comment = hdk::utils::get_as_type(comment_address.clone())?;
+            Ok(comment.course_anchor_address)
+        }
+    }
+}
+
+// returns type of the link from ModerationAnchor that marks the target as hidden, target's address
+// and the tag of that link. Content doesn't know which course it's a part of, so hidden content
+// is tagged with the section it was hidden in for validation to find the course
+fn get_hidden_link(target: &FlagTarget) -> (&'static str, Address, String) {
+    match target {
+        FlagTarget::Course {
+            course_anchor_address,
+        } => (
+            HIDDEN_COURSE_ANCHOR_LINK,
+            course_anchor_address.clone(),
+            String::default(),
+        ),
+        FlagTarget::Content {
+            section_anchor_address,
+            content_address,
+        } => (
+            HIDDEN_CONTENT_LINK,
+            content_address.clone(),
+            section_anchor_address.to_string(),
+        ),
+        FlagTarget::Comment { comment_address } => (
+            HIDDEN_COMMENT_LINK,
+            comment_address.clone(),
+            String::default(),
+        ),
+    }
+}
+
+// moderators can moderate every course and teachers can moderate their own courses
+fn ensure_can_moderate(course_anchor_address: &Address) -> ZomeApiResult<()> {
+    if is_moderator(&AGENT_ADDRESS)? {
+        return Ok(());
+    }
+    course::handlers::ensure_teacher(course_anchor_address)?;
+    Ok(())
+}
+
+pub fn flag(
+    target: FlagTarget,
+    reason: FlagReason,
+    details: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let course_anchor_address = get_target_course(&target)?;
+
+    // if moderation anchor already exists, this function would just return it's address without actually writing anything
+    // new to the DHT.
+    let moderation_anchor_address = hdk::commit_entry(&ModerationAnchor::new().entry())?;
+
+    let new_flag = Flag::new(
+        target,
+        reason,
+        details,
+        course_anchor_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let new_flag_address = hdk::commit_entry(&new_flag.entry())?;
+
+    // link flag to the course for it's teacher to see
+    hdk::link_entries(
+        &course_anchor_address,
+        &new_flag_address,
+        COURSE_ANCHOR_TO_FLAG_LINK,
+        "",
+    )?;
+
+    // link flag to the moderation anchor for moderators to see
+    hdk::link_entries(
+        &moderation_anchor_address,
+        &new_flag_address,
+        ModerationAnchor::link_type(),
+        "".to_owned(),
+    )?;
+
+    Ok(new_flag_address)
+}
+
+fn get_flags(base_address: &Address, link_type: &str) -> ZomeApiResult<Vec<Flag>> {
+    hdk::get_links(base_address, LinkMatch::Exactly(link_type), LinkMatch::Any)?
+        .addresses()
+        .into_iter()
+        .map(|flag_address| hdk::utils::get_as_type(flag_address))
+        .collect()
+}
+
+pub fn list_course_flags(course_anchor_address: &Address) -> ZomeApiResult<Vec<Flag>> {
+    ensure_can_moderate(course_anchor_address)?;
+    get_flags(course_anchor_address, COURSE_ANCHOR_TO_FLAG_LINK)
+}
+
+// flags of the course which targets aren't hidden yet, so teacher or moderators still have to look at them
+pub fn list_pending_course_flags(course_anchor_address: &Address) -> ZomeApiResult<Vec<Flag>> {
+    let mut pending_flags = Vec::new();
+    for flag in list_course_flags(course_anchor_address)? {
+        let (hidden_link_type, target_address, _tag) = get_hidden_link(&flag.target);
+        if !get_hidden(hidden_link_type)?.contains(&target_address) {
+            pending_flags.push(flag);
+        }
+    }
+    Ok(pending_flags)
+}
+
+pub fn list_all_flags() -> ZomeApiResult<Vec<Flag>> {
+    if !is_moderator(&AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "Only moderators can see flags of all courses".to_owned(),
+        ));
+    }
+    get_flags(
+        &ModerationAnchor::new().address()?,
+        &ModerationAnchor::link_type(),
+    )
+}
+
+pub fn hide(target: FlagTarget) -> ZomeApiResult<Address> {
+    ensure_can_moderate(&get_target_course(&target)?)?;
+
+    let moderation_anchor_address = hdk::commit_entry(&ModerationAnchor::new().entry())?;
+    let (hidden_link_type, target_address, tag) = get_hidden_link(&target);
+    if !get_hidden(hidden_link_type)?.contains(&target_address) {
+        hdk::link_entries(
+            &moderation_anchor_address,
+            &target_address,
+            hidden_link_type,
+            tag.as_str(),
+        )?;
+    }
+
+    Ok(target_address)
+}
+
+pub fn unhide(target: FlagTarget) -> ZomeApiResult<Address> {
+    ensure_can_moderate(&get_target_course(&target)?)?;
+
+    let (hidden_link_type, target_address, _tag) = get_hidden_link(&target);
+    let moderation_anchor_address = ModerationAnchor::new().address()?;
+    let links = hdk::get_links(
+        &moderation_anchor_address,
+        LinkMatch::Exactly(hidden_link_type),
+        LinkMatch::Any,
+    )?;
+    // content could've been hidden from another section, so we remove links with any tag
+    for link in links.links() {
+        if link.address == target_address {
+            hdk::remove_link(
+                &moderation_anchor_address,
+                &target_address,
+                hidden_link_type,
+                link.tag.as_str(),
+            )?;
+        }
+    }
+
+    Ok(target_address)
+}
+
+// returns addresses of all hidden items of the kind that hidden_link_type stands for
+pub fn get_hidden(hidden_link_type: &str) -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &ModerationAnchor::new().address()?,
+        LinkMatch::Exactly(hidden_link_type),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+// returns hidden items whose links are tagged with tag, like content hidden in a particular section
+pub fn get_hidden_with_tag(hidden_link_type: &str, tag: &str) -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &ModerationAnchor::new().address()?,
+        LinkMatch::Exactly(hidden_link_type),
+        LinkMatch::Exactly(tag),
+    )?;
+
+    Ok(links.addresses())
+}
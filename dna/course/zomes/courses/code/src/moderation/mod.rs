@@ -0,0 +1,3 @@
+pub mod anchor;
+pub mod entry;
+pub mod handlers;
@@ -0,0 +1,61 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{validate_range, AnnotationTarget, TextRange};
+
+// Private bookmark of a student. Just like notes, bookmarks only live on the student's source chain
+// and removing a bookmark commits a new version of it with deleted set to true
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Bookmark {
+    // address of the first version of this bookmark. It's None for the first version itself
+    pub bookmark_address: Option<Address>,
+    pub target: AnnotationTarget,
+    pub course_anchor_address: Address,
+    pub range: Option<TextRange>,
+    pub deleted: bool,
+    pub timestamp: u64,
+}
+
+impl Bookmark {
+    pub fn new(
+        target: AnnotationTarget,
+        course_anchor_address: Address,
+        range: Option<TextRange>,
+        timestamp: u64,
+    ) -> Self {
+        Bookmark {
+            bookmark_address: None,
+            target: target,
+            course_anchor_address: course_anchor_address,
+            range: range,
+            deleted: false,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Bookmark {
+    fn entry_type() -> String {
+        String::from("bookmark")
+    }
+}
+
+// Holochain entry definition for Bookmark
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Bookmark::entry_type(),
+        description: "this is the definition of a private bookmark of a student",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Bookmark>| {
+            match validation_data {
+                EntryValidationData::Create { entry, .. } => {
+                    validate_range(&entry.range)
+                },
+                _ => Ok(())
+            }
+        }
+    )
+}
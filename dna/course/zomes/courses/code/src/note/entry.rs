@@ -0,0 +1,107 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+// Part of the text that note or bookmark is attached to, in characters from the start
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+// What note or bookmark is attached to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum AnnotationTarget {
+    Section {
+        section_anchor_address: Address,
+    },
+    // Content doesn't know it's section, so we keep it here to find the course
+    Content {
+        section_anchor_address: Address,
+        content_address: Address,
+    },
+}
+
+impl AnnotationTarget {
+    pub fn section_anchor_address(&self) -> &Address {
+        match self {
+            AnnotationTarget::Section {
+                section_anchor_address,
+            } => section_anchor_address,
+            AnnotationTarget::Content {
+                section_anchor_address,
+                ..
+            } => section_anchor_address,
+        }
+    }
+}
+
+// Private note of a student. It's only stored on the student's source chain and never linked from
+// public entries, so nobody else can find it.
+// Private entries can't be looked up through the DHT, so notes are never updated in place:
+// every edit or deletion commits a new version with the address of the first one in note_address
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Note {
+    // address of the first version of this note. It's None for the first version itself
+    pub note_address: Option<Address>,
+    pub target: AnnotationTarget,
+    pub course_anchor_address: Address,
+    pub range: Option<TextRange>,
+    pub text: String,
+    pub deleted: bool,
+    pub timestamp: u64,
+}
+
+impl Note {
+    pub fn new(
+        target: AnnotationTarget,
+        course_anchor_address: Address,
+        range: Option<TextRange>,
+        text: String,
+        timestamp: u64,
+    ) -> Self {
+        Note {
+            note_address: None,
+            target: target,
+            course_anchor_address: course_anchor_address,
+            range: range,
+            text: text,
+            deleted: false,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Note {
+    fn entry_type() -> String {
+        String::from("note")
+    }
+}
+
+pub fn validate_range(range: &Option<TextRange>) -> Result<(), String> {
+    match range {
+        Some(range) if range.start > range.end => {
+            Err("Text range can't end before it starts".to_owned())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Holochain entry definition for Note
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Note::entry_type(),
+        description: "this is the definition of a private note of a student",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Note>| {
+            match validation_data {
+                EntryValidationData::Create { entry, .. } => {
+                    validate_range(&entry.range)
+                },
+                _ => Ok(())
+            }
+        }
+    )
+}
@@ -0,0 +1,220 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::bookmark::Bookmark;
+use super::entry::{AnnotationTarget, Note, TextRange};
+use super::markdown::{self, NotesSection};
+use crate::content::entry::Content;
+use crate::course;
+use crate::helper;
+use crate::section;
+use crate::section::anchor::SectionAnchor;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct MyNote {
+    // address of the first version of the note. This is what edit_note and delete_note expect
+    pub note_address: Address,
+    pub note: Note,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct MyBookmark {
+    // address of the first version of the bookmark. This is what delete_bookmark expects
+    pub bookmark_address: Address,
+    pub bookmark: Bookmark,
+}
+
+fn get_course_anchor_address(target: &AnnotationTarget) -> ZomeApiResult<Address> {
+    let section_anchor: SectionAnchor =
+        hdk::utils::get_as_type(target.section_anchor_address().clone())?;
+    Ok(section_anchor.course_anchor_address)
+}
+
+// keeps only the latest version of every note or bookmark together with the address of it's first version.
+// Versions come from query_result, which walks the source chain from it's top, so the newest one is first.
+// We go through them from the oldest one, so the later ones replace the earlier ones.
+// Timestamps come from the UI and can go back if the clock changes, so they aren't used here
+fn get_latest_versions<T>(
+    versions: Vec<(T, Address)>,
+    get_first_version_address: impl Fn(&T) -> Option<Address>,
+) -> Vec<(T, Address)> {
+    let mut latest_versions: Vec<(T, Address)> = Vec::new();
+    for (version, address) in versions.into_iter().rev() {
+        let first_version_address = get_first_version_address(&version).unwrap_or(address);
+        match latest_versions
+            .iter_mut()
+            .find(|(_latest_version, latest_address)| *latest_address == first_version_address)
+        {
+            Some((latest_version, _latest_address)) => *latest_version = version,
+            None => latest_versions.push((version, first_version_address)),
+        }
+    }
+    latest_versions
+}
+
+fn get_my_current_notes() -> ZomeApiResult<Vec<MyNote>> {
+    let versions = helper::query_my_entries::<Note>()?;
+    Ok(
+        get_latest_versions(versions, |note| note.note_address.clone())
+            .into_iter()
+            .filter(|(note, _note_address)| !note.deleted)
+            .map(|(note, note_address)| MyNote {
+                note_address: note_address,
+                note: note,
+            })
+            .collect(),
+    )
+}
+
+fn get_my_current_note(note_address: &Address) -> ZomeApiResult<Note> {
+    match get_my_current_notes()?
+        .into_iter()
+        .find(|my_note| my_note.note_address == *note_address)
+    {
+        Some(my_note) => Ok(my_note.note),
+        None => Err(ZomeApiError::from("Can't find this note".to_owned())),
+    }
+}
+
+pub fn create_note(
+    target: AnnotationTarget,
+    range: Option<TextRange>,
+    text: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let course_anchor_address = get_course_anchor_address(&target)?;
+    let note = Note::new(target, course_anchor_address, range, text, timestamp);
+    hdk::commit_entry(&note.entry())
+}
+
+pub fn edit_note(
+    note_address: Address,
+    range: Option<TextRange>,
+    text: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let mut note = get_my_current_note(&note_address)?;
+    note.note_address = Some(note_address.clone());
+    note.range = range;
+    note.text = text;
+    note.timestamp = timestamp;
+    hdk::commit_entry(&note.entry())?;
+
+    Ok(note_address)
+}
+
+pub fn delete_note(note_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+    let mut note = get_my_current_note(&note_address)?;
+    note.note_address = Some(note_address.clone());
+    note.deleted = true;
+    note.timestamp = timestamp;
+    hdk::commit_entry(&note.entry())?;
+
+    Ok(note_address)
+}
+
+pub fn get_my_notes(course_anchor_address: Address) -> ZomeApiResult<Vec<MyNote>> {
+    Ok(get_my_current_notes()?
+        .into_iter()
+        .filter(|my_note| my_note.note.course_anchor_address == course_anchor_address)
+        .collect())
+}
+
+// exports notes of the agent that calls this function as a markdown document
+// ordered the same way as sections of the course
+pub fn export_notes_markdown(course_anchor_address: Address) -> ZomeApiResult<String> {
+    let course = match course::handlers::get_latest_course(&course_anchor_address)? {
+        Some((course, _course_address)) => course,
+        None => return Err(ZomeApiError::from("Can't find this course".to_owned())),
+    };
+    let mut notes = get_my_notes(course_anchor_address)?;
+
+    let mut sections = Vec::new();
+    for section_anchor_address in course.sections.iter() {
+        let title = match section::handlers::get_latest_section(section_anchor_address)? {
+            Some((section, _section_address)) => section.title,
+            // notes of deleted sections end up in "Other notes" below
+            None => continue,
+        };
+        let (section_notes, other_notes): (Vec<MyNote>, Vec<MyNote>) =
+            notes.into_iter().partition(|my_note| {
+                my_note.note.target.section_anchor_address() == section_anchor_address
+            });
+        notes = other_notes;
+
+        let mut notes_section = NotesSection {
+            title: title,
+            notes: Vec::new(),
+        };
+        for my_note in section_notes {
+            let content_name = match &my_note.note.target {
+                AnnotationTarget::Content {
+                    content_address, ..
+                } => match hdk::get_entry(content_address)? {
+                    Some(entry) => Content::from_entry(&entry).map(|content| content.name),
+                    None => None,
+                },
+                AnnotationTarget::Section { .. } => None,
+            };
+            notes_section.notes.push((content_name, my_note.note));
+        }
+        sections.push(notes_section);
+    }
+    // notes of sections that were removed from the course are kept at the end so they aren't lost
+    sections.push(NotesSection {
+        title: "Other notes".to_owned(),
+        notes: notes
+            .into_iter()
+            .map(|my_note| (None, my_note.note))
+            .collect(),
+    });
+
+    Ok(markdown::render_notes(&course.title, &sections))
+}
+
+fn get_my_current_bookmarks() -> ZomeApiResult<Vec<MyBookmark>> {
+    let versions = helper::query_my_entries::<Bookmark>()?;
+    Ok(
+        get_latest_versions(versions, |bookmark| bookmark.bookmark_address.clone())
+            .into_iter()
+            .filter(|(bookmark, _bookmark_address)| !bookmark.deleted)
+            .map(|(bookmark, bookmark_address)| MyBookmark {
+                bookmark_address: bookmark_address,
+                bookmark: bookmark,
+            })
+            .collect(),
+    )
+}
+
+pub fn create_bookmark(
+    target: AnnotationTarget,
+    range: Option<TextRange>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let course_anchor_address = get_course_anchor_address(&target)?;
+    let bookmark = Bookmark::new(target, course_anchor_address, range, timestamp);
+    hdk::commit_entry(&bookmark.entry())
+}
+
+pub fn delete_bookmark(bookmark_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+    let mut bookmark = match get_my_current_bookmarks()?
+        .into_iter()
+        .find(|my_bookmark| my_bookmark.bookmark_address == bookmark_address)
+    {
+        Some(my_bookmark) => my_bookmark.bookmark,
+        None => return Err(ZomeApiError::from("Can't find this bookmark".to_owned())),
+    };
+    bookmark.bookmark_address = Some(bookmark_address.clone());
+    bookmark.deleted = true;
+    bookmark.timestamp = timestamp;
+    hdk::commit_entry(&bookmark.entry())?;
+
+    Ok(bookmark_address)
+}
+
+pub fn get_my_bookmarks(course_anchor_address: Address) -> ZomeApiResult<Vec<MyBookmark>> {
+    Ok(get_my_current_bookmarks()?
+        .into_iter()
+        .filter(|my_bookmark| my_bookmark.bookmark.course_anchor_address == course_anchor_address)
+        .collect())
+}
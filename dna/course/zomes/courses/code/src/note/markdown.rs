@@ -0,0 +1,41 @@
+use super::entry::Note;
+
+// notes of one section of the course, with the name of the content each note is attached to
+pub struct NotesSection {
+    pub title: String,
+    pub notes: Vec<(Option<String>, Note)>,
+}
+
+// markdown heading ends at the end of the line, so line breaks in titles are replaced with spaces
+fn heading_text(title: &str) -> String {
+    title
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>()
+        .trim()
+        .to_owned()
+}
+
+// renders notes of a course as a markdown document: a heading for every section
+// and a subheading for every note that says what it's attached to
+pub fn render_notes(course_title: &str, sections: &[NotesSection]) -> String {
+    let mut markdown = format!("# {}\n", heading_text(course_title));
+    for section in sections.iter() {
+        if section.notes.is_empty() {
+            continue;
+        }
+        markdown.push_str(&format!("\n## {}\n", heading_text(&section.title)));
+        for (content_name, note) in section.notes.iter() {
+            let note_title = match content_name {
+                Some(content_name) => content_name.as_str(),
+                None => "Section note",
+            };
+            markdown.push_str(&format!("\n### {}\n", heading_text(note_title)));
+            if let Some(range) = &note.range {
+                markdown.push_str(&format!("\n_Characters {}-{}_\n", range.start, range.end));
+            }
+            markdown.push_str(&format!("\n{}\n", note.text.trim_end()));
+        }
+    }
+    markdown
+}
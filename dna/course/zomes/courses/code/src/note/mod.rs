@@ -0,0 +1,4 @@
+pub mod bookmark;
+pub mod entry;
+pub mod handlers;
+pub mod markdown;
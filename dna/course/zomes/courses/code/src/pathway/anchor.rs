@@ -0,0 +1,101 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::Pathway;
+use crate::anchor_trait::AnchorTrait;
+
+pub const CURATOR_TO_PATHWAY_ANCHOR_LINK: &str = "curator->pathway_anchor";
+pub const STUDENT_TO_PATHWAY_ANCHOR_LINK: &str = "student->pathway_anchor";
+
+#[derive(Serialize, Deserialize, Debug, self::DefaultJson, Clone)]
+pub struct PathwayAnchor {
+    // NOTE: these fields are here to ensure the uniqueness of every particular anchor
+    //  and wouldn't be used to display data about pathway to a user
+    pub title: String,
+    pub curator_address: Address,
+    pub timestamp: u64,
+}
+
+impl AnchorTrait for PathwayAnchor {
+    fn entry_type() -> String {
+        String::from("pathway_anchor")
+    }
+    fn link_to() -> String {
+        Pathway::entry_type()
+    }
+    fn link_type() -> String {
+        "pathway_anchor->pathway".to_owned()
+    }
+}
+
+impl PathwayAnchor {
+    pub fn new(title: String, curator_address: Address, timestamp: u64) -> Self {
+        PathwayAnchor {
+            title: title,
+            curator_address: curator_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+pub fn pathway_anchor_def() -> ValidatingEntryType {
+    entry!(
+        name: PathwayAnchor::entry_type(),
+        description: "Anchor to the valid pathway",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<PathwayAnchor>| {
+            match validation_data{
+                EntryValidationData::Create { .. } => {
+                    Ok(())
+                 },
+                 EntryValidationData::Modify { .. } => {
+                    Ok(())
+                 },
+                 EntryValidationData::Delete { .. } => {
+                    Ok(())
+                 }
+            }
+        },
+        links:[
+            // link that connects PathwayAnchor to the latest Pathway entry
+            // This is a necessary link that allows access to pathway data
+            to!(
+                PathwayAnchor::link_to(),
+                link_type: PathwayAnchor::link_type(),
+                validation_package:||{
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation:|_validation_data: hdk::LinkValidationData|{
+                   Ok(())
+                }
+            ),
+            // link from agent that curates this pathway
+            // This is for curator to keep track of all the pathways they've published
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: CURATOR_TO_PATHWAY_ANCHOR_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            ),
+            // link from agent that is a student who follows this pathway
+            // This is for student to keep track of all the pathways they're following
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: STUDENT_TO_PATHWAY_ANCHOR_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,58 @@
+use hdk::prelude::*;
+use hdk::{entry_definition::ValidatingEntryType, holochain_core_types::dna::entry_types::Sharing};
+
+use super::anchor::PathwayAnchor;
+use crate::anchor_trait::AnchorTrait;
+
+#[derive(Serialize, Deserialize, Debug, self::DefaultJson, Clone)]
+pub struct PathwayCatalogAnchor {
+    name: String,
+}
+
+impl AnchorTrait for PathwayCatalogAnchor {
+    fn entry_type() -> String {
+        String::from("pathway_catalog_anchor")
+    }
+    fn link_to() -> String {
+        PathwayAnchor::entry_type()
+    }
+    fn link_type() -> String {
+        // there'll be a single link per every pathway
+        "pathway_list".to_owned()
+    }
+}
+
+impl PathwayCatalogAnchor {
+    pub fn new() -> Self {
+        PathwayCatalogAnchor {
+            name: PathwayCatalogAnchor::entry_type(),
+        }
+    }
+}
+
+//// Anchor Definition : This Anchor will be used to query all pathways
+pub fn catalog_anchor_entry_def() -> ValidatingEntryType {
+    entry!(
+        name: PathwayCatalogAnchor::entry_type(),
+        description:"Anchor that serves as a catalog for all Pathway entries",
+        sharing: Sharing::Public,
+        validation_package:||{
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation:|_validation_data: hdk::EntryValidationData<PathwayCatalogAnchor>|{
+            Ok(())
+        },
+        links:[
+            to!(
+                PathwayCatalogAnchor::link_to(),
+                link_type: PathwayCatalogAnchor::link_type(),
+                validation_package:||{
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation:|_validation_data: hdk::LinkValidationData|{
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
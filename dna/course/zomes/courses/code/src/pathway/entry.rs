@@ -0,0 +1,94 @@
+use hdk::{
+    entry_definition::ValidatingEntryType,
+    holochain_core_types::{dna::entry_types::Sharing, validation::EntryValidationData},
+    holochain_json_api::{error::JsonError, json::JsonString},
+    holochain_persistence_api::cas::content::Address,
+};
+use holochain_entry_utils::HolochainEntry;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PathwayStep {
+    pub course_anchor_address: Address,
+    // courses that students can take instead of the course at course_anchor_address
+    // to complete this step. Empty if this step doesn't branch
+    pub alternatives: Vec<Address>,
+}
+
+impl PathwayStep {
+    // all courses that complete this step, starting with the main one
+    pub fn options(&self) -> Vec<Address> {
+        let mut options = vec![self.course_anchor_address.clone()];
+        options.extend(self.alternatives.iter().cloned());
+        options
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Pathway {
+    pub title: String,
+    pub description: String,
+    // ordered list of steps that students should take one after another
+    pub steps: Vec<PathwayStep>,
+    pub curator_address: Address,
+    pub timestamp: u64,
+    pub anchor_address: Address,
+}
+
+impl HolochainEntry for Pathway {
+    fn entry_type() -> String {
+        String::from("pathway")
+    }
+}
+
+impl Pathway {
+    pub fn new(
+        title: String,
+        description: String,
+        steps: Vec<PathwayStep>,
+        curator_address: Address,
+        timestamp: u64,
+        anchor_address: Address,
+    ) -> Self {
+        Pathway {
+            title: title,
+            description: description,
+            steps: steps,
+            curator_address: curator_address,
+            timestamp: timestamp,
+            anchor_address: anchor_address,
+        }
+    }
+}
+
+// Holochain entry definition for Pathway
+pub fn pathway_entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Pathway::entry_type(),
+        description: "this is the definition of a curated learning pathway",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Pathway>| {
+            match validation_data {
+                EntryValidationData::Create { entry, .. } => {
+                    if entry.steps.is_empty() {
+                        return Err("Pathway should have at least one step".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { new_entry, .. } => {
+                    if new_entry.steps.is_empty() {
+                        return Err("Pathway should have at least one step".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        // All links that pathway should have are defined for PathwayAnchor and so this entry doesn't have any
+        links: []
+    )
+}
@@ -0,0 +1,319 @@
+use hdk::holochain_json_api::{error::JsonError, json::JsonString};
+use hdk::holochain_persistence_api::cas::content::Address;
+use hdk::prelude::LinkMatch;
+use hdk::{
+    error::{ZomeApiError, ZomeApiResult},
+    AGENT_ADDRESS,
+};
+use holochain_entry_utils::HolochainEntry;
+
+use super::anchor::{
+    PathwayAnchor, CURATOR_TO_PATHWAY_ANCHOR_LINK, STUDENT_TO_PATHWAY_ANCHOR_LINK,
+};
+use super::catalog_anchor::PathwayCatalogAnchor;
+use super::entry::{Pathway, PathwayStep};
+use crate::anchor_trait::AnchorTrait;
+use crate::course;
+use crate::helper;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct StepProgress {
+    // course of this step that student has chosen: the one they've completed or enrolled in.
+    // If student hasn't started this step yet, it's the main course of the step
+    pub course_anchor_address: Address,
+    pub enrolled: bool,
+    pub completed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct PathwayProgress {
+    pub steps: Vec<StepProgress>,
+    pub completed_steps: usize,
+    pub total_steps: usize,
+}
+
+// checks that all courses referenced by steps exist
+fn validate_steps(steps: &[PathwayStep]) -> ZomeApiResult<()> {
+    for step in steps {
+        for course_anchor_address in step.options() {
+            if course::handlers::get_latest_course(&course_anchor_address)?.is_none() {
+                return Err(ZomeApiError::from(format!(
+                    "Pathway can't include a deleted course {}",
+                    course_anchor_address
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+// retrieves PathwayAnchor and checks that agent that calls this function is the curator of this pathway
+fn ensure_curator(pathway_anchor_address: &Address) -> ZomeApiResult<PathwayAnchor> {
+    let pathway_anchor: PathwayAnchor = hdk::utils::get_as_type(pathway_anchor_address.clone())?;
+    if pathway_anchor.curator_address != *AGENT_ADDRESS {
+        return Err(ZomeApiError::from(
+            "Only the curator of this pathway can do that".to_owned(),
+        ));
+    }
+    Ok(pathway_anchor)
+}
+
+pub fn create(
+    title: String,
+    description: String,
+    steps: Vec<PathwayStep>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    validate_steps(&steps)?;
+
+    // if catalog anchor already exists, this function would just return it's address without actually writing anything
+    // new to the DHT.
+    let catalog_anchor_address = hdk::commit_entry(&PathwayCatalogAnchor::new().entry())?;
+
+    // initialize PathwayAnchor instance to represent this particular pathway
+    let pathway_anchor = PathwayAnchor::new(title.clone(), AGENT_ADDRESS.clone(), timestamp);
+    let pathway_anchor_address = hdk::commit_entry(&pathway_anchor.entry())?;
+
+    let new_pathway = Pathway::new(
+        title,
+        description,
+        steps,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+        pathway_anchor_address.clone(),
+    );
+    let new_pathway_address = hdk::commit_entry(&new_pathway.entry())?;
+
+    // link PathwayAnchor to Pathway entry
+    hdk::link_entries(
+        &pathway_anchor_address,
+        &new_pathway_address,
+        PathwayAnchor::link_type(),
+        "".to_owned(),
+    )?;
+
+    // link PathwayCatalogAnchor to PathwayAnchor entry for this pathway to be findable
+    hdk::link_entries(
+        &catalog_anchor_address,
+        &pathway_anchor_address,
+        PathwayCatalogAnchor::link_type(),
+        "".to_owned(),
+    )?;
+
+    // link curator to PathwayAnchor for this pathway to be findable in the list of pathways they curate
+    hdk::link_entries(
+        &AGENT_ADDRESS,
+        &pathway_anchor_address,
+        CURATOR_TO_PATHWAY_ANCHOR_LINK,
+        "",
+    )?;
+
+    Ok(pathway_anchor_address)
+}
+
+pub fn get_latest_pathway(
+    pathway_anchor_address: &Address,
+) -> ZomeApiResult<Option<(Pathway, Address)>> {
+    helper::get_latest_data_entry::<Pathway>(pathway_anchor_address, &PathwayAnchor::link_type())
+}
+
+// wrapper for the get_latest_pathway that only returns Pathway entry
+// and disregards it's address
+pub fn get_latest_pathway_entry(pathway_anchor_address: Address) -> ZomeApiResult<Option<Pathway>> {
+    let latest_pathway_result = get_latest_pathway(&pathway_anchor_address)?;
+    Ok(latest_pathway_result.map(|(pathway_entry, _pathway_entry_address)| pathway_entry))
+}
+
+pub fn update(
+    title: String,
+    description: String,
+    steps: Vec<PathwayStep>,
+    pathway_anchor_address: &Address,
+) -> ZomeApiResult<Address> {
+    ensure_curator(pathway_anchor_address)?;
+    validate_steps(&steps)?;
+
+    let latest_pathway_result = get_latest_pathway(pathway_anchor_address)?;
+    match latest_pathway_result {
+        Some((mut previous_pathway, previous_pathway_address)) => {
+            previous_pathway.title = title;
+            previous_pathway.description = description;
+            previous_pathway.steps = steps;
+
+            // commit updated pathway to DHT and get it's new address
+            let new_pathway_address =
+                hdk::update_entry(previous_pathway.entry(), &previous_pathway_address)?;
+
+            // remove link to previous version of pathway
+            hdk::remove_link(
+                pathway_anchor_address,
+                &previous_pathway_address,
+                PathwayAnchor::link_type(),
+                "".to_owned(),
+            )?;
+
+            // create link to new version of pathway
+            hdk::link_entries(
+                pathway_anchor_address,
+                &new_pathway_address,
+                PathwayAnchor::link_type(),
+                "".to_owned(),
+            )?;
+
+            Ok(pathway_anchor_address.clone())
+        }
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't update a deleted pathway".to_owned(),
+            ));
+        }
+    }
+}
+
+pub fn delete(pathway_anchor_address: Address) -> ZomeApiResult<Address> {
+    let pathway_anchor = ensure_curator(&pathway_anchor_address)?;
+
+    // remove link from PathwayCatalogAnchor to PathwayAnchor
+    hdk::remove_link(
+        &PathwayCatalogAnchor::new().address()?,
+        &pathway_anchor_address,
+        PathwayCatalogAnchor::link_type(),
+        "".to_owned(),
+    )?;
+
+    hdk::remove_link(
+        &pathway_anchor.curator_address,
+        &pathway_anchor_address,
+        CURATOR_TO_PATHWAY_ANCHOR_LINK,
+        "",
+    )?;
+
+    // NOTE: just like with courses, we're only deleting an anchor and leave Pathway entries as is.
+    // Links from students who follow this pathway would point to a deleted anchor, so
+    // get_my_followed_pathways filters them out
+    hdk::remove_entry(&pathway_anchor_address)
+}
+
+pub fn list_all_pathways() -> ZomeApiResult<Vec<Address>> {
+    let addresses = hdk::get_links(
+        &PathwayCatalogAnchor::new().address()?,
+        LinkMatch::Exactly(&PathwayCatalogAnchor::link_type()),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    Ok(addresses)
+}
+
+pub fn get_my_pathways() -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(CURATOR_TO_PATHWAY_ANCHOR_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+pub fn follow(pathway_anchor_address: Address) -> ZomeApiResult<Address> {
+    if get_latest_pathway(&pathway_anchor_address)?.is_none() {
+        return Err(ZomeApiError::from(
+            "Can't follow a deleted pathway".to_owned(),
+        ));
+    }
+    if get_my_followed_pathways()?.contains(&pathway_anchor_address) {
+        return Ok(pathway_anchor_address);
+    }
+
+    hdk::link_entries(
+        &AGENT_ADDRESS,
+        &pathway_anchor_address,
+        STUDENT_TO_PATHWAY_ANCHOR_LINK,
+        "",
+    )?;
+
+    Ok(pathway_anchor_address)
+}
+
+pub fn unfollow(pathway_anchor_address: Address) -> ZomeApiResult<Address> {
+    hdk::remove_link(
+        &AGENT_ADDRESS,
+        &pathway_anchor_address,
+        STUDENT_TO_PATHWAY_ANCHOR_LINK,
+        "",
+    )?;
+
+    Ok(pathway_anchor_address)
+}
+
+pub fn get_my_followed_pathways() -> ZomeApiResult<Vec<Address>> {
+    let pathway_anchor_addresses = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(STUDENT_TO_PATHWAY_ANCHOR_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut followed_pathways = Vec::new();
+    for pathway_anchor_address in pathway_anchor_addresses {
+        // get_entry won't return anything if the pathway anchor was deleted
+        if hdk::get_entry(&pathway_anchor_address)?.is_some() {
+            followed_pathways.push(pathway_anchor_address);
+        }
+    }
+    Ok(followed_pathways)
+}
+
+// progress of the agent that calls this function, based on the courses they've enrolled in and completed
+pub fn get_progress(pathway_anchor_address: Address) -> ZomeApiResult<PathwayProgress> {
+    let pathway = match get_latest_pathway(&pathway_anchor_address)? {
+        Some((pathway, _pathway_address)) => pathway,
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't get progress of a deleted pathway".to_owned(),
+            ));
+        }
+    };
+
+    let enrolled_courses = course::handlers::get_my_enrolled_courses()?;
+    let completed_courses = course::handlers::get_my_completed_courses()?;
+
+    let mut steps = Vec::new();
+    for step in pathway.steps.iter() {
+        let options = step.options();
+        let step_progress = match options
+            .iter()
+            .find(|option| completed_courses.contains(*option))
+        {
+            Some(completed_course) => StepProgress {
+                course_anchor_address: completed_course.clone(),
+                enrolled: true,
+                completed: true,
+            },
+            None => match options
+                .iter()
+                .find(|option| enrolled_courses.contains(*option))
+            {
+                Some(enrolled_course) => StepProgress {
+                    course_anchor_address: enrolled_course.clone(),
+                    enrolled: true,
+                    completed: false,
+                },
+                None => StepProgress {
+                    course_anchor_address: step.course_anchor_address.clone(),
+                    enrolled: false,
+                    completed: false,
+                },
+            },
+        };
+        steps.push(step_progress);
+    }
+
+    let completed_steps = steps.iter().filter(|step| step.completed).count();
+    let total_steps = steps.len();
+    Ok(PathwayProgress {
+        steps: steps,
+        completed_steps: completed_steps,
+        total_steps: total_steps,
+    })
+}
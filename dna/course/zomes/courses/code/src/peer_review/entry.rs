@@ -0,0 +1,197 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::round::PeerReviewRound;
+use crate::assignment;
+use crate::assignment::entry::Assignment;
+use crate::assignment::submission::Submission;
+use crate::peer_review;
+
+pub const SUBMISSION_TO_PEER_REVIEW_LINK: &str = "submission->peer_review";
+
+// Review of a submission by another student of the course
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct PeerReview {
+    pub assignment_address: Address,
+    // snapshot of submissions that review assignments were computed from
+    pub round_address: Address,
+    // submission that is being reviewed
+    pub submission_address: Address,
+    pub reviewer_address: Address,
+    pub score: u32,
+    pub feedback: String,
+    pub timestamp: u64,
+}
+
+impl PeerReview {
+    pub fn new(
+        assignment_address: Address,
+        round_address: Address,
+        submission_address: Address,
+        reviewer_address: Address,
+        score: u32,
+        feedback: String,
+        timestamp: u64,
+    ) -> Self {
+        PeerReview {
+            assignment_address: assignment_address,
+            round_address: round_address,
+            submission_address: submission_address,
+            reviewer_address: reviewer_address,
+            score: score,
+            feedback: feedback,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for PeerReview {
+    fn entry_type() -> String {
+        String::from("peer_review")
+    }
+}
+
+// checks that reviewer was assigned to this submission and that the score is within the limits.
+// Review assignments are derived from the round that teacher has committed, so every validator can recompute them
+fn validate_peer_review(peer_review: &PeerReview) -> Result<(), String> {
+    let assignment: Assignment = hdk::utils::get_as_type(peer_review.assignment_address.clone())
+        .map_err(|_| "Can't find the assignment of this review".to_owned())?;
+    let submission: Submission = hdk::utils::get_as_type(peer_review.submission_address.clone())
+        .map_err(|_| "Can't find the submission of this review".to_owned())?;
+    if submission.assignment_address != peer_review.assignment_address {
+        return Err("Reviewed submission doesn't belong to this assignment".to_owned());
+    }
+
+    let max_points = assignment::handlers::get_max_points(&assignment)
+        .map_err(|_| "Can't find max points of the assignment".to_owned())?;
+    if peer_review.score > max_points {
+        return Err(format!("Score can't be higher than {}", max_points));
+    }
+
+    let round: PeerReviewRound = hdk::utils::get_as_type(peer_review.round_address.clone())
+        .map_err(|_| "Can't find the peer review round of this review".to_owned())?;
+    if round.assignment_address != peer_review.assignment_address
+        || round.teacher_address != assignment.teacher_address
+    {
+        return Err("Peer review round doesn't belong to this assignment".to_owned());
+    }
+    let review_assignments = peer_review::handlers::get_review_assignments(
+        &round,
+        assignment.peer_review_count,
+        &peer_review.reviewer_address,
+    );
+    if !review_assignments.contains(&peer_review.submission_address) {
+        return Err("This submission wasn't assigned to the reviewer".to_owned());
+    }
+
+    let peer_review_address = peer_review
+        .address()
+        .map_err(|_| "Can't get address of the review".to_owned())?;
+    validate_single_review(
+        &peer_review.submission_address,
+        &peer_review.reviewer_address,
+        &peer_review_address,
+    )
+}
+
+// checks that reviewer hasn't linked any other review to the submission.
+// Reviews can't be modified, so every reviewer has at most one review of a submission
+fn validate_single_review(
+    submission_address: &Address,
+    reviewer_address: &Address,
+    peer_review_address: &Address,
+) -> Result<(), String> {
+    let reviewer_tag = reviewer_address.to_string();
+    let linked_reviews = hdk::get_links(
+        submission_address,
+        LinkMatch::Exactly(SUBMISSION_TO_PEER_REVIEW_LINK),
+        LinkMatch::Exactly(&reviewer_tag),
+    )
+    .map_err(|_| "Can't get reviews of the submission".to_owned())?
+    .addresses();
+    if linked_reviews
+        .iter()
+        .any(|linked_review_address| linked_review_address != peer_review_address)
+    {
+        return Err("Reviewer has already reviewed this submission".to_owned());
+    }
+    Ok(())
+}
+
+// checks that review is linked from it's submission by it's reviewer and tagged with the reviewer address
+fn validate_peer_review_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let peer_review: PeerReview = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked review".to_owned())?;
+    if !sources.contains(&peer_review.reviewer_address) {
+        return Err("Only the reviewer can link their review".to_owned());
+    }
+    if *link_data.link.base() != peer_review.submission_address
+        || *link_data.link.tag() != peer_review.reviewer_address.to_string()
+    {
+        return Err(
+            "Review should be linked from it's submission and tagged with the reviewer".to_owned(),
+        );
+    }
+    validate_single_review(
+        &peer_review.submission_address,
+        &peer_review.reviewer_address,
+        link_data.link.target(),
+    )
+}
+
+fn validate_peer_review_link_change(
+    validation_data: hdk::LinkValidationData,
+) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_peer_review_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove { .. } => {
+            Err("Peer reviews can't be unlinked".to_owned())
+        }
+    }
+}
+
+// Holochain entry definition for PeerReview
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: PeerReview::entry_type(),
+        description: "this is the definition of a student's review of another student's submission",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<PeerReview>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if !validation_data.sources().contains(&entry.reviewer_address) {
+                        return Err("Peer review can only be written by the reviewer it belongs to".to_owned());
+                    }
+                    validate_peer_review(&entry)
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Peer reviews can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the submission that was reviewed.
+            // Tag of this link is an address of the reviewer so we can check if they've already reviewed it
+            from!(
+                Submission::entry_type(),
+                link_type: SUBMISSION_TO_PEER_REVIEW_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_peer_review_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,292 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{PeerReview, SUBMISSION_TO_PEER_REVIEW_LINK};
+use super::round::{PeerReviewRound, ReviewedSubmission, ASSIGNMENT_TO_PEER_REVIEW_ROUND_LINK};
+use crate::assignment;
+use crate::assignment::entry::Assignment;
+use crate::assignment::submission::{Submission, ASSIGNMENT_TO_SUBMISSION_LINK};
+use crate::course;
+use crate::quiz::generator;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct ReceivedPeerReviews {
+    pub submission_address: Address,
+    pub reviews: Vec<PeerReview>,
+    // median of review scores. It's None if submission wasn't reviewed yet
+    pub peer_score: Option<f64>,
+}
+
+// Returns submissions that student at reviewer_address has to review.
+// Students of the round are shuffled by a seed derived from the assignment address and the set of students,
+// and every student reviews peer_review_count submissions of students that follow them in this order,
+// wrapping around at the end. Nobody can choose who they review: the order doesn't depend on
+// submission contents, and everyone can verify it because the round is a public entry
+pub fn get_review_assignments(
+    round: &PeerReviewRound,
+    peer_review_count: u32,
+    reviewer_address: &Address,
+) -> Vec<Address> {
+    let students: Vec<Address> = round
+        .submissions
+        .iter()
+        .map(|submission| submission.student_address.clone())
+        .collect();
+    let seed = generator::get_pool_seed(&round.assignment_address, &students);
+    let ring = generator::draw(&students, students.len(), seed);
+
+    let reviewer_index = match ring
+        .iter()
+        .position(|student_address| student_address == reviewer_address)
+    {
+        Some(reviewer_index) => reviewer_index,
+        // only students who've submitted the assignment before the round started review others
+        None => return Vec::new(),
+    };
+
+    // nobody reviews their own submission
+    let review_count = std::cmp::min(peer_review_count as usize, ring.len() - 1);
+    (1..=review_count)
+        .filter_map(|offset| {
+            let student_address = &ring[(reviewer_index + offset) % ring.len()];
+            round
+                .submissions
+                .iter()
+                .find(|submission| submission.student_address == *student_address)
+                .map(|submission| submission.submission_address.clone())
+        })
+        .collect()
+}
+
+// returns the peer review round of the assignment together with it's address.
+// Only the teacher can commit a round and we don't let them commit a second one,
+// but if it happens anyway we take the earliest round so that everyone picks the same one
+pub fn get_round(
+    assignment_address: &Address,
+) -> ZomeApiResult<Option<(PeerReviewRound, Address)>> {
+    let round_addresses = hdk::get_links(
+        assignment_address,
+        LinkMatch::Exactly(ASSIGNMENT_TO_PEER_REVIEW_ROUND_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut rounds = Vec::new();
+    for round_address in round_addresses {
+        let round: PeerReviewRound = hdk::utils::get_as_type(round_address.clone())?;
+        rounds.push((round, round_address));
+    }
+    rounds.sort_by_key(|(round, round_address)| (round.timestamp, round_address.to_string()));
+    Ok(rounds.into_iter().next())
+}
+
+// freezes the set of submissions that take part in the peer review. Teacher calls it after the due date
+pub fn start(assignment_address: Address, timestamp: u64) -> ZomeApiResult<Address> {
+    let assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+    course::handlers::ensure_teacher(&assignment.course_anchor_address)?;
+    ensure_peer_review_started(&assignment, timestamp)?;
+    if get_round(&assignment_address)?.is_some() {
+        return Err(ZomeApiError::from(
+            "Peer review of this assignment has already started".to_owned(),
+        ));
+    }
+
+    let submission_addresses = hdk::get_links(
+        &assignment_address,
+        LinkMatch::Exactly(ASSIGNMENT_TO_SUBMISSION_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+    let mut submissions: Vec<ReviewedSubmission> = Vec::new();
+    for submission_address in submission_addresses {
+        let submission: Submission = hdk::utils::get_as_type(submission_address.clone())?;
+        // every student has a single submission, but we don't want to fail the round if it's linked twice
+        if submissions
+            .iter()
+            .any(|reviewed| reviewed.student_address == submission.student_address)
+        {
+            continue;
+        }
+        submissions.push(ReviewedSubmission {
+            student_address: submission.student_address,
+            submission_address: submission_address,
+        });
+    }
+
+    let round = PeerReviewRound::new(
+        assignment_address.clone(),
+        submissions,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let round_address = hdk::commit_entry(&round.entry())?;
+    hdk::link_entries(
+        &assignment_address,
+        &round_address,
+        ASSIGNMENT_TO_PEER_REVIEW_ROUND_LINK,
+        "",
+    )?;
+
+    Ok(round_address)
+}
+
+// returns the round of the assignment or an error if teacher hasn't started peer review yet
+fn get_started_round(assignment_address: &Address) -> ZomeApiResult<(PeerReviewRound, Address)> {
+    get_round(assignment_address)?.ok_or_else(|| {
+        ZomeApiError::from("Teacher hasn't started peer review of this assignment yet".to_owned())
+    })
+}
+
+fn ensure_peer_review_started(assignment: &Assignment, timestamp: u64) -> ZomeApiResult<()> {
+    if assignment.peer_review_count == 0 {
+        return Err(ZomeApiError::from(
+            "This assignment doesn't have peer review".to_owned(),
+        ));
+    }
+    if timestamp <= assignment.due_date {
+        return Err(ZomeApiError::from(
+            "Peer review starts after the due date of the assignment".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+// returns submissions that the agent that calls this function has to review
+pub fn get_my_review_assignments(
+    assignment_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<Vec<Address>> {
+    let assignment: Assignment = hdk::utils::get_as_type(assignment_address.clone())?;
+    ensure_peer_review_started(&assignment, timestamp)?;
+    let (round, _round_address) = get_started_round(&assignment_address)?;
+    Ok(get_review_assignments(
+        &round,
+        assignment.peer_review_count,
+        &AGENT_ADDRESS,
+    ))
+}
+
+// returns reviews of the submission, one per reviewer. Validation rejects a second review
+// of the same reviewer, but if one got through we only count the earliest of them
+fn get_reviews(submission_address: &Address) -> ZomeApiResult<Vec<PeerReview>> {
+    let peer_review_addresses = hdk::get_links(
+        submission_address,
+        LinkMatch::Exactly(SUBMISSION_TO_PEER_REVIEW_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut all_reviews = Vec::new();
+    for peer_review_address in peer_review_addresses {
+        let peer_review: PeerReview = hdk::utils::get_as_type(peer_review_address.clone())?;
+        all_reviews.push((peer_review, peer_review_address));
+    }
+    all_reviews.sort_by_key(|(peer_review, peer_review_address)| {
+        (peer_review.timestamp, peer_review_address.to_string())
+    });
+
+    let mut reviews: Vec<PeerReview> = Vec::new();
+    for (peer_review, _peer_review_address) in all_reviews {
+        if !reviews
+            .iter()
+            .any(|review| review.reviewer_address == peer_review.reviewer_address)
+        {
+            reviews.push(peer_review);
+        }
+    }
+    Ok(reviews)
+}
+
+pub fn submit(
+    submission_address: Address,
+    score: u32,
+    feedback: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let submission: Submission = hdk::utils::get_as_type(submission_address.clone())?;
+    let assignment: Assignment = hdk::utils::get_as_type(submission.assignment_address.clone())?;
+    ensure_peer_review_started(&assignment, timestamp)?;
+    let (round, round_address) = get_started_round(&submission.assignment_address)?;
+
+    if !get_review_assignments(&round, assignment.peer_review_count, &AGENT_ADDRESS)
+        .contains(&submission_address)
+    {
+        return Err(ZomeApiError::from(
+            "This submission wasn't assigned to you for review".to_owned(),
+        ));
+    }
+    let reviewer_tag = AGENT_ADDRESS.to_string();
+    let previous_review_count = hdk::get_links_count(
+        &submission_address,
+        LinkMatch::Exactly(SUBMISSION_TO_PEER_REVIEW_LINK),
+        LinkMatch::Exactly(&reviewer_tag),
+    )?
+    .count;
+    if previous_review_count > 0 {
+        return Err(ZomeApiError::from(
+            "You've already reviewed this submission".to_owned(),
+        ));
+    }
+    let max_points = assignment::handlers::get_max_points(&assignment)?;
+    if score > max_points {
+        return Err(ZomeApiError::from(format!(
+            "Score can't be higher than {}",
+            max_points
+        )));
+    }
+
+    let peer_review = PeerReview::new(
+        submission.assignment_address,
+        round_address,
+        submission_address.clone(),
+        AGENT_ADDRESS.clone(),
+        score,
+        feedback,
+        timestamp,
+    );
+    let peer_review_address = hdk::commit_entry(&peer_review.entry())?;
+
+    hdk::link_entries(
+        &submission_address,
+        &peer_review_address,
+        SUBMISSION_TO_PEER_REVIEW_LINK,
+        reviewer_tag.as_str(),
+    )?;
+
+    Ok(peer_review_address)
+}
+
+// median of the scores. It's None if there are no scores
+pub fn get_median(scores: &[u32]) -> Option<f64> {
+    if scores.is_empty() {
+        return None;
+    }
+    let mut sorted_scores = scores.to_vec();
+    sorted_scores.sort();
+    let middle = sorted_scores.len() / 2;
+    if sorted_scores.len() % 2 == 0 {
+        Some((sorted_scores[middle - 1] as f64 + sorted_scores[middle] as f64) / 2.0)
+    } else {
+        Some(sorted_scores[middle] as f64)
+    }
+}
+
+// reviews that other students wrote for the submission of the agent that calls this function
+pub fn get_received_reviews(
+    assignment_address: Address,
+) -> ZomeApiResult<Option<ReceivedPeerReviews>> {
+    let submission_address =
+        match assignment::handlers::get_submission(&assignment_address, &AGENT_ADDRESS)? {
+            Some((_submission, submission_address)) => submission_address,
+            None => return Ok(None),
+        };
+
+    let reviews = get_reviews(&submission_address)?;
+    let scores: Vec<u32> = reviews.iter().map(|review| review.score).collect();
+    Ok(Some(ReceivedPeerReviews {
+        submission_address: submission_address,
+        peer_score: get_median(&scores),
+        reviews: reviews,
+    }))
+}
@@ -0,0 +1,158 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::assignment::entry::Assignment;
+use crate::assignment::submission::Submission;
+use crate::calendar::ical::parse_date_time;
+
+pub const ASSIGNMENT_TO_PEER_REVIEW_ROUND_LINK: &str = "assignment->peer_review_round";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReviewedSubmission {
+    pub student_address: Address,
+    pub submission_address: Address,
+}
+
+// Snapshot of the submissions that take part in the peer review of an assignment.
+// Teacher commits it after the due date, so review assignments don't change
+// when submissions are linked later and every validator computes them from the same set
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct PeerReviewRound {
+    pub assignment_address: Address,
+    // sorted by the student address, one submission per student
+    pub submissions: Vec<ReviewedSubmission>,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl PeerReviewRound {
+    pub fn new(
+        assignment_address: Address,
+        mut submissions: Vec<ReviewedSubmission>,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        submissions.sort_by_key(|submission| submission.student_address.to_string());
+        PeerReviewRound {
+            assignment_address: assignment_address,
+            submissions: submissions,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for PeerReviewRound {
+    fn entry_type() -> String {
+        String::from("peer_review_round")
+    }
+}
+
+// checks that the round was committed by the teacher after the due date
+// and only contains submissions of this assignment
+fn validate_round(
+    round: &PeerReviewRound,
+    sources: &[Address],
+    committed_at: &str,
+) -> Result<(), String> {
+    if !sources.contains(&round.teacher_address) {
+        return Err("Peer review round can only be committed by it's teacher".to_owned());
+    }
+    let assignment: Assignment = hdk::utils::get_as_type(round.assignment_address.clone())
+        .map_err(|_| "Can't find the assignment of this peer review round".to_owned())?;
+    if round.teacher_address != assignment.teacher_address {
+        return Err("Only the teacher of the assignment can start it's peer review".to_owned());
+    }
+    let committed_at = parse_date_time(committed_at)
+        .ok_or_else(|| "Can't read time of the peer review round".to_owned())?;
+    if round.timestamp <= assignment.due_date || committed_at <= assignment.due_date {
+        return Err("Peer review can only start after the due date of the assignment".to_owned());
+    }
+
+    for (index, reviewed_submission) in round.submissions.iter().enumerate() {
+        if index > 0
+            && round.submissions[index - 1].student_address.to_string()
+                >= reviewed_submission.student_address.to_string()
+        {
+            return Err("Submissions of the round should be sorted by unique students".to_owned());
+        }
+        let submission: Submission =
+            hdk::utils::get_as_type(reviewed_submission.submission_address.clone())
+                .map_err(|_| "Can't find a submission of this peer review round".to_owned())?;
+        if submission.assignment_address != round.assignment_address
+            || submission.student_address != reviewed_submission.student_address
+        {
+            return Err("Submission doesn't belong to this assignment or student".to_owned());
+        }
+    }
+    Ok(())
+}
+
+fn validate_round_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let round: PeerReviewRound = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked peer review round".to_owned())?;
+    if !sources.contains(&round.teacher_address) {
+        return Err(
+            "Only the teacher of the assignment can link it's peer review round".to_owned(),
+        );
+    }
+    if *link_data.link.base() != round.assignment_address {
+        return Err("Peer review round can only be linked from it's assignment".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_round_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_round_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove { .. } => {
+            Err("Peer review round can't be unlinked".to_owned())
+        }
+    }
+}
+
+// Holochain entry definition for PeerReviewRound
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: PeerReviewRound::entry_type(),
+        description: "this is the definition of a snapshot of submissions that take part in peer review",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<PeerReviewRound>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_round(
+                        &entry,
+                        &validation_data.sources(),
+                        &validation_data.package.chain_header.timestamp().to_string(),
+                    )
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Peer review rounds can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Err("Peer review rounds can't be deleted since reviews reference them".to_owned())
+                }
+            }
+        },
+        links: [
+            // link from the assignment this round is for
+            from!(
+                Assignment::entry_type(),
+                link_type: ASSIGNMENT_TO_PEER_REVIEW_ROUND_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_round_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
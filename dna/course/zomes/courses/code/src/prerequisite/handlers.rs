@@ -0,0 +1,169 @@
+use hdk::error::{ZomeApiError, ZomeApiResult};
+use hdk::holochain_json_api::{error::JsonError, json::JsonString};
+use hdk::holochain_persistence_api::cas::content::Address;
+use hdk::prelude::LinkMatch;
+use std::collections::HashSet;
+
+use crate::course;
+use crate::course::anchor::COURSE_ANCHOR_TO_PREREQUISITE_LINK;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct PrerequisiteEdge {
+    pub course_anchor_address: Address,
+    pub prerequisite_address: Address,
+}
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct PrerequisitePathway {
+    // all courses of the pathway in the order they should be taken: every course comes
+    // after all of it's prerequisites, so the requested course is always the last one
+    pub courses: Vec<Address>,
+    pub prerequisites: Vec<PrerequisiteEdge>,
+}
+
+// returns direct prerequisites of the course, skipping courses that were deleted
+pub fn get_prerequisites(course_anchor_address: &Address) -> ZomeApiResult<Vec<Address>> {
+    let prerequisite_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_PREREQUISITE_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut prerequisites = Vec::new();
+    for prerequisite_address in prerequisite_addresses {
+        // get_entry won't return anything if the course anchor was deleted
+        if hdk::get_entry(&prerequisite_address)?.is_some() {
+            prerequisites.push(prerequisite_address);
+        }
+    }
+    Ok(prerequisites)
+}
+
+// checks if target_address can be reached from course_anchor_address by following prerequisite links
+fn depends_on(course_anchor_address: &Address, target_address: &Address) -> ZomeApiResult<bool> {
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![course_anchor_address.clone()];
+    while let Some(current_address) = to_visit.pop() {
+        if &current_address == target_address {
+            return Ok(true);
+        }
+        if visited.insert(current_address.clone()) {
+            to_visit.extend(get_prerequisites(&current_address)?);
+        }
+    }
+    Ok(false)
+}
+
+pub fn add_prerequisite(
+    course_anchor_address: Address,
+    prerequisite_address: Address,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    if course_anchor_address == prerequisite_address {
+        return Err(ZomeApiError::from(
+            "Course can't be a prerequisite of itself".to_owned(),
+        ));
+    }
+    if course::handlers::get_latest_course(&prerequisite_address)?.is_none() {
+        return Err(ZomeApiError::from(
+            "Can't add a deleted course as a prerequisite".to_owned(),
+        ));
+    }
+    if get_prerequisites(&course_anchor_address)?.contains(&prerequisite_address) {
+        return Ok(course_anchor_address);
+    }
+    // if this course is already a (possibly indirect) prerequisite of the new prerequisite,
+    // adding the link would make the prerequisite graph cyclic
+    if depends_on(&prerequisite_address, &course_anchor_address)? {
+        return Err(ZomeApiError::from(
+            "Can't add this prerequisite because it would create a cycle".to_owned(),
+        ));
+    }
+
+    hdk::link_entries(
+        &course_anchor_address,
+        &prerequisite_address,
+        COURSE_ANCHOR_TO_PREREQUISITE_LINK,
+        "",
+    )?;
+
+    Ok(course_anchor_address)
+}
+
+pub fn remove_prerequisite(
+    course_anchor_address: Address,
+    prerequisite_address: Address,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    hdk::remove_link(
+        &course_anchor_address,
+        &prerequisite_address,
+        COURSE_ANCHOR_TO_PREREQUISITE_LINK,
+        "",
+    )?;
+
+    Ok(course_anchor_address)
+}
+
+// depth-first traversal that adds course to the pathway only after all of it's prerequisites were added
+fn visit_course(
+    course_anchor_address: &Address,
+    in_progress: &mut HashSet<Address>,
+    visited: &mut HashSet<Address>,
+    pathway: &mut PrerequisitePathway,
+) -> ZomeApiResult<()> {
+    if visited.contains(course_anchor_address) {
+        return Ok(());
+    }
+    // add_prerequisite doesn't allow cycles, but two teachers could still create one
+    // by adding prerequisites at the same time
+    if !in_progress.insert(course_anchor_address.clone()) {
+        return Err(ZomeApiError::from(
+            "Prerequisite graph of this course contains a cycle".to_owned(),
+        ));
+    }
+
+    for prerequisite_address in get_prerequisites(course_anchor_address)? {
+        pathway.prerequisites.push(PrerequisiteEdge {
+            course_anchor_address: course_anchor_address.clone(),
+            prerequisite_address: prerequisite_address.clone(),
+        });
+        visit_course(&prerequisite_address, in_progress, visited, pathway)?;
+    }
+
+    in_progress.remove(course_anchor_address);
+    visited.insert(course_anchor_address.clone());
+    pathway.courses.push(course_anchor_address.clone());
+    Ok(())
+}
+
+pub fn get_pathway(course_anchor_address: Address) -> ZomeApiResult<PrerequisitePathway> {
+    let mut pathway = PrerequisitePathway {
+        courses: Vec::new(),
+        prerequisites: Vec::new(),
+    };
+    visit_course(
+        &course_anchor_address,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+        &mut pathway,
+    )?;
+    Ok(pathway)
+}
+
+// returns direct prerequisites of the course that student at student_address hasn't completed
+pub fn get_missing_prerequisites(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Vec<Address>> {
+    let mut missing_prerequisites = Vec::new();
+    for prerequisite_address in get_prerequisites(course_anchor_address)? {
+        if !course::handlers::has_completed(&prerequisite_address, student_address)? {
+            missing_prerequisites.push(prerequisite_address);
+        }
+    }
+    Ok(missing_prerequisites)
+}
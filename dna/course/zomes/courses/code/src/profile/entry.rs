@@ -0,0 +1,134 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::handle_anchor::{validate_handle, validate_handle_owner};
+
+pub const AGENT_TO_PROFILE_LINK: &str = "agent->profile";
+
+// Public profile of an agent, both teachers and students have them.
+// Every agent has at most one profile and it's identified by the address of it's first version
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct AgentProfile {
+    // unique name of the agent, see profile::handle_anchor
+    pub handle: String,
+    pub display_name: String,
+    pub bio: String,
+    // url or address of the avatar image
+    pub avatar: Option<String>,
+    pub agent_address: Address,
+    pub timestamp: u64,
+}
+
+impl AgentProfile {
+    pub fn new(
+        handle: String,
+        display_name: String,
+        bio: String,
+        avatar: Option<String>,
+        agent_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        AgentProfile {
+            handle: handle,
+            display_name: display_name,
+            bio: bio,
+            avatar: avatar,
+            agent_address: agent_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for AgentProfile {
+    fn entry_type() -> String {
+        String::from("agent_profile")
+    }
+}
+
+fn validate_profile(profile: &AgentProfile) -> Result<(), String> {
+    validate_handle(&profile.handle)?;
+    if profile.display_name.trim().is_empty() {
+        return Err("Display name can't be empty".to_owned());
+    }
+    Ok(())
+}
+
+// agent can only link their own profile from themselves
+fn validate_profile_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let profile: AgentProfile = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked profile".to_owned())?;
+    let base = link_data.link.base();
+    if *base != profile.agent_address || !sources.contains(base) {
+        return Err("Profile can only be linked by it's agent from themselves".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_profile_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_profile_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_profile_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for AgentProfile
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: AgentProfile::entry_type(),
+        description: "this is the definition of a public profile of an agent",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<AgentProfile>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_profile(&entry)?;
+                    if !validation_data.sources().contains(&entry.agent_address) {
+                        return Err("Profile can only be created by the agent it belongs to".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    validate_profile(&new_entry)?;
+                    if new_entry.agent_address != old_entry.agent_address {
+                        return Err("Profile can't be moved to another agent".to_owned());
+                    }
+                    if !validation_data.sources().contains(&old_entry.agent_address) {
+                        return Err("Profile can only be updated by the agent it belongs to".to_owned());
+                    }
+                    // anchor of the new handle is linked to the profile right after this update,
+                    // since the link is only valid once the profile has the handle.
+                    // Here we check that nobody else holds it
+                    if new_entry.handle != old_entry.handle {
+                        validate_handle_owner(&new_entry.handle, &new_entry.agent_address)?;
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Err("Profiles can't be deleted".to_owned())
+                }
+            }
+        },
+        links: [
+            // link from the agent this profile belongs to
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: AGENT_TO_PROFILE_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_profile_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
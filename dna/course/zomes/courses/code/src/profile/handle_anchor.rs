@@ -0,0 +1,159 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::AgentProfile;
+use crate::anchor_trait::AnchorTrait;
+
+const MIN_HANDLE_LENGTH: usize = 3;
+const MAX_HANDLE_LENGTH: usize = 30;
+
+// Anchor with a handle. It's address only depends on the handle, so everyone who
+// looks for the handle finds the same anchor, and the profile linked from it owns the handle
+#[derive(Serialize, Deserialize, Debug, self::DefaultJson, Clone)]
+pub struct HandleAnchor {
+    pub handle: String,
+}
+
+impl AnchorTrait for HandleAnchor {
+    fn entry_type() -> String {
+        String::from("handle_anchor")
+    }
+    fn link_to() -> String {
+        AgentProfile::entry_type()
+    }
+    fn link_type() -> String {
+        "handle_anchor->profile".to_owned()
+    }
+}
+
+impl HandleAnchor {
+    pub fn new(handle: String) -> Self {
+        HandleAnchor { handle: handle }
+    }
+}
+
+// handles are lowercase, so that "Alice" and "alice" can't belong to different agents
+pub fn validate_handle(handle: &str) -> Result<(), String> {
+    if handle.len() < MIN_HANDLE_LENGTH || handle.len() > MAX_HANDLE_LENGTH {
+        return Err(format!(
+            "Handle should be from {} to {} characters long",
+            MIN_HANDLE_LENGTH, MAX_HANDLE_LENGTH
+        ));
+    }
+    if !handle
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(
+            "Handle can only have lowercase latin letters, digits and underscores".to_owned(),
+        );
+    }
+    Ok(())
+}
+
+// checks that the handle isn't held by a profile of another agent
+pub fn validate_handle_owner(handle: &str, agent_address: &Address) -> Result<(), String> {
+    let handle_anchor_address = HandleAnchor::new(handle.to_owned())
+        .address()
+        .map_err(|_| "Can't find the anchor of the handle".to_owned())?;
+    let profile_addresses = hdk::get_links(
+        &handle_anchor_address,
+        LinkMatch::Exactly(&HandleAnchor::link_type()),
+        LinkMatch::Any,
+    )
+    .map_err(|_| "Can't check if handle is taken".to_owned())?
+    .addresses();
+
+    for profile_address in profile_addresses {
+        let profile: AgentProfile = hdk::utils::get_as_type(profile_address)
+            .map_err(|_| "Can't find the profile that holds the handle".to_owned())?;
+        if profile.agent_address != *agent_address {
+            return Err("This handle is already taken".to_owned());
+        }
+    }
+    Ok(())
+}
+
+// checks that agent links their own profile from the anchor of it's handle
+// and that handle isn't taken by another profile yet
+fn validate_handle_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let handle_anchor: HandleAnchor = hdk::utils::get_as_type(link_data.link.base().clone())
+        .map_err(|_| "Can't find the anchor of the handle".to_owned())?;
+    let profile: AgentProfile = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked profile".to_owned())?;
+    if !sources.contains(&profile.agent_address) {
+        return Err("Only the owner of the profile can claim a handle for it".to_owned());
+    }
+    if profile.handle != handle_anchor.handle {
+        return Err("Profile can only be linked from the anchor of it's handle".to_owned());
+    }
+
+    let profile_addresses = hdk::get_links(
+        link_data.link.base(),
+        LinkMatch::Exactly(&HandleAnchor::link_type()),
+        LinkMatch::Any,
+    )
+    .map_err(|_| "Can't check if handle is taken".to_owned())?
+    .addresses();
+
+    if profile_addresses
+        .iter()
+        .any(|profile_address| profile_address != link_data.link.target())
+    {
+        return Err("This handle is already taken".to_owned());
+    }
+    Ok(())
+}
+
+// only the agent who holds the handle can release it
+fn validate_handle_release(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let profile: AgentProfile = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked profile".to_owned())?;
+    if !sources.contains(&profile.agent_address) {
+        return Err("Only the owner of the handle can release it".to_owned());
+    }
+    Ok(())
+}
+
+pub fn handle_anchor_def() -> ValidatingEntryType {
+    entry!(
+        name: HandleAnchor::entry_type(),
+        description: "Anchor to the profile that owns the handle",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<HandleAnchor>| {
+            match validation_data {
+                EntryValidationData::Create { entry, .. } => {
+                    validate_handle(&entry.handle)
+                },
+                _ => Ok(())
+            }
+        },
+        links: [
+            // link to the profile that owns the handle.
+            // Handle anchor can only have one link at a time, that's what makes handles unique.
+            // NOTE: two agents taking the same handle at the same moment may both pass validation
+            //  before their links are gossiped, this is a limitation of eventual consistency
+            to!(
+                HandleAnchor::link_to(),
+                link_type: HandleAnchor::link_type(),
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    match validation_data {
+                        hdk::LinkValidationData::LinkAdd { link, validation_data } => {
+                            validate_handle_link(&link, &validation_data.sources())
+                        },
+                        hdk::LinkValidationData::LinkRemove { link, validation_data } => {
+                            validate_handle_release(&link, &validation_data.sources())
+                        }
+                    }
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,186 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{AgentProfile, AGENT_TO_PROFILE_LINK};
+use super::handle_anchor::{validate_handle_owner, HandleAnchor};
+use crate::anchor_trait::AnchorTrait;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct ResolvedAgent {
+    pub agent_address: Address,
+    // it's None if agent hasn't created a profile yet
+    pub profile: Option<AgentProfile>,
+}
+
+// address of the first version of the agent's profile
+fn get_profile_address(agent_address: &Address) -> ZomeApiResult<Option<Address>> {
+    let profile_addresses = hdk::get_links(
+        agent_address,
+        LinkMatch::Exactly(AGENT_TO_PROFILE_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    Ok(profile_addresses.first().cloned())
+}
+
+// NOTE: get_entry follows updates, so this returns the latest version of the profile
+pub fn get_profile(agent_address: Address) -> ZomeApiResult<Option<AgentProfile>> {
+    match get_profile_address(&agent_address)? {
+        Some(profile_address) => match hdk::get_entry(&profile_address)? {
+            Some(entry) => Ok(AgentProfile::from_entry(&entry)),
+            None => Ok(None),
+        },
+        None => Ok(None),
+    }
+}
+
+pub fn get_my_profile() -> ZomeApiResult<Option<AgentProfile>> {
+    get_profile(AGENT_ADDRESS.clone())
+}
+
+// returns profiles of all given agents in the same order, so UI can show names in one call
+pub fn resolve_agents(agent_addresses: Vec<Address>) -> ZomeApiResult<Vec<ResolvedAgent>> {
+    agent_addresses
+        .into_iter()
+        .map(|agent_address| {
+            Ok(ResolvedAgent {
+                profile: get_profile(agent_address.clone())?,
+                agent_address: agent_address,
+            })
+        })
+        .collect()
+}
+
+// display name of the agent or None if they don't have a profile
+pub fn get_display_name(agent_address: &Address) -> ZomeApiResult<Option<String>> {
+    Ok(get_profile(agent_address.clone())?.map(|profile| profile.display_name))
+}
+
+pub fn get_profile_by_handle(handle: String) -> ZomeApiResult<Option<AgentProfile>> {
+    let handle_anchor = HandleAnchor::new(handle.to_lowercase());
+    let profile_addresses = hdk::get_links(
+        &handle_anchor.address()?,
+        LinkMatch::Exactly(&HandleAnchor::link_type()),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    match profile_addresses.first() {
+        Some(profile_address) => match hdk::get_entry(profile_address)? {
+            Some(entry) => Ok(AgentProfile::from_entry(&entry)),
+            None => Ok(None),
+        },
+        None => Ok(None),
+    }
+}
+
+// links profile from the anchor of the handle. Fails if another profile already owns the handle
+fn claim_handle(handle: &str, profile_address: &Address) -> ZomeApiResult<()> {
+    let handle_anchor = HandleAnchor::new(handle.to_owned());
+    let handle_anchor_address = hdk::commit_entry(&handle_anchor.entry())?;
+
+    let owner_addresses = hdk::get_links(
+        &handle_anchor_address,
+        LinkMatch::Exactly(&HandleAnchor::link_type()),
+        LinkMatch::Any,
+    )?
+    .addresses();
+    if owner_addresses
+        .iter()
+        .any(|owner_address| owner_address != profile_address)
+    {
+        return Err(ZomeApiError::from(format!(
+            "Handle '{}' is already taken",
+            handle
+        )));
+    }
+
+    hdk::link_entries(
+        &handle_anchor_address,
+        profile_address,
+        HandleAnchor::link_type(),
+        "".to_owned(),
+    )?;
+    Ok(())
+}
+
+fn release_handle(handle: &str, profile_address: &Address) -> ZomeApiResult<()> {
+    let handle_anchor = HandleAnchor::new(handle.to_owned());
+    hdk::remove_link(
+        &handle_anchor.address()?,
+        profile_address,
+        HandleAnchor::link_type(),
+        "".to_owned(),
+    )
+}
+
+pub fn create_my_profile(
+    handle: String,
+    display_name: String,
+    bio: String,
+    avatar: Option<String>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    if get_profile_address(&AGENT_ADDRESS)?.is_some() {
+        return Err(ZomeApiError::from(
+            "You already have a profile, update it instead".to_owned(),
+        ));
+    }
+
+    let profile = AgentProfile::new(
+        handle.to_lowercase(),
+        display_name,
+        bio,
+        avatar,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let profile_address = hdk::commit_entry(&profile.entry())?;
+
+    claim_handle(&profile.handle, &profile_address)?;
+    hdk::link_entries(&AGENT_ADDRESS, &profile_address, AGENT_TO_PROFILE_LINK, "")?;
+
+    Ok(profile_address)
+}
+
+pub fn update_my_profile(
+    handle: String,
+    display_name: String,
+    bio: String,
+    avatar: Option<String>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let profile_address = match get_profile_address(&AGENT_ADDRESS)? {
+        Some(profile_address) => profile_address,
+        None => {
+            return Err(ZomeApiError::from(
+                "You don't have a profile yet, create it first".to_owned(),
+            ));
+        }
+    };
+    let mut profile: AgentProfile = hdk::utils::get_as_type(profile_address.clone())?;
+    let handle = handle.to_lowercase();
+    if handle != profile.handle {
+        validate_handle_owner(&handle, &AGENT_ADDRESS).map_err(ZomeApiError::from)?;
+    }
+
+    // profile is referenced by the address of it's first version, but we have to update the latest one
+    let latest_profile_address = profile.address()?;
+    let previous_handle = profile.handle.clone();
+    profile.handle = handle;
+    profile.display_name = display_name;
+    profile.bio = bio;
+    profile.avatar = avatar;
+    profile.timestamp = timestamp;
+    hdk::update_entry(profile.entry(), &latest_profile_address)?;
+
+    // handle anchor can only link a profile that already has it's handle, so we claim it after the update
+    if profile.handle != previous_handle {
+        claim_handle(&profile.handle, &profile_address)?;
+        release_handle(&previous_handle, &profile_address)?;
+    }
+
+    Ok(profile_address)
+}
@@ -0,0 +1,3 @@
+pub mod entry;
+pub mod handle_anchor;
+pub mod handlers;
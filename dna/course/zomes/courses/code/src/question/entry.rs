@@ -0,0 +1,124 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+pub const TEACHER_TO_QUESTION_LINK: &str = "teacher->question";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum QuestionDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+// Multiple choice question in the teacher's question bank. Quizzes draw their questions from here.
+// Questions can't be changed, so attempts of quizzes that used them can always be re-graded
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Question {
+    pub text: String,
+    pub options: Vec<String>,
+    // index of the correct option in options
+    pub correct_option: usize,
+    pub topics: Vec<String>,
+    pub difficulty: QuestionDifficulty,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Question {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        text: String,
+        options: Vec<String>,
+        correct_option: usize,
+        topics: Vec<String>,
+        difficulty: QuestionDifficulty,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Question {
+            text: text,
+            options: options,
+            correct_option: correct_option,
+            topics: topics,
+            difficulty: difficulty,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+
+    // checks if question belongs to any of the topics and, if difficulty is given, has this difficulty
+    pub fn matches(&self, topics: &[String], difficulty: &Option<QuestionDifficulty>) -> bool {
+        if let Some(difficulty) = difficulty {
+            if self.difficulty != *difficulty {
+                return false;
+            }
+        }
+        self.topics.iter().any(|topic| topics.contains(topic))
+    }
+}
+
+impl HolochainEntry for Question {
+    fn entry_type() -> String {
+        String::from("question")
+    }
+}
+
+fn validate_question(question: &Question) -> Result<(), String> {
+    if question.options.len() < 2 {
+        return Err("Question should have at least two options".to_owned());
+    }
+    if question.correct_option >= question.options.len() {
+        return Err(format!(
+            "Question has {} options, so correct option can't be {}",
+            question.options.len(),
+            question.correct_option
+        ));
+    }
+    if question.topics.is_empty() {
+        return Err("Question should have at least one topic".to_owned());
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Question
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Question::entry_type(),
+        description: "this is the definition of a question in the teacher's question bank",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Question>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_question(&entry)?;
+                    if !validation_data.sources().contains(&entry.teacher_address) {
+                        return Err("Question can only be created by the teacher it belongs to".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Questions can't be modified, create a new one instead".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the teacher who owns the question
+            // This is the teacher's question bank
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: TEACHER_TO_QUESTION_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,75 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{Question, QuestionDifficulty, TEACHER_TO_QUESTION_LINK};
+
+pub fn create(
+    text: String,
+    options: Vec<String>,
+    correct_option: usize,
+    topics: Vec<String>,
+    difficulty: QuestionDifficulty,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let question = Question::new(
+        text,
+        options,
+        correct_option,
+        topics,
+        difficulty,
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let question_address = hdk::commit_entry(&question.entry())?;
+
+    hdk::link_entries(
+        &AGENT_ADDRESS,
+        &question_address,
+        TEACHER_TO_QUESTION_LINK,
+        "",
+    )?;
+
+    Ok(question_address)
+}
+
+pub fn get_question(question_address: Address) -> ZomeApiResult<Option<Question>> {
+    match hdk::get_entry(&question_address)? {
+        Some(entry) => Ok(Question::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+pub fn get_my_questions() -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(TEACHER_TO_QUESTION_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
+
+// returns addresses of the teacher's questions that belong to any of the topics
+// and have the given difficulty (or any difficulty if it's None)
+pub fn get_matching_questions(
+    teacher_address: &Address,
+    topics: &[String],
+    difficulty: &Option<QuestionDifficulty>,
+) -> ZomeApiResult<Vec<Address>> {
+    let question_addresses = hdk::get_links(
+        teacher_address,
+        LinkMatch::Exactly(TEACHER_TO_QUESTION_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut matching_question_addresses = Vec::new();
+    for question_address in question_addresses {
+        let question: Question = hdk::utils::get_as_type(question_address.clone())?;
+        if question.matches(topics, difficulty) {
+            matching_question_addresses.push(question_address);
+        }
+    }
+    Ok(matching_question_addresses)
+}
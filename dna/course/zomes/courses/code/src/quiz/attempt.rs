@@ -0,0 +1,176 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::Quiz;
+use crate::quiz;
+
+pub const QUIZ_TO_ATTEMPT_LINK: &str = "quiz->attempt";
+
+// Answers of a student to the questions that were generated for them
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct QuizAttempt {
+    pub quiz_address: Address,
+    pub student_address: Address,
+    // index of the selected option for each generated question, in the order they were generated
+    pub answers: Vec<usize>,
+    pub correct_count: u32,
+    pub question_count: u32,
+    pub timestamp: u64,
+}
+
+impl QuizAttempt {
+    pub fn new(
+        quiz_address: Address,
+        student_address: Address,
+        answers: Vec<usize>,
+        correct_count: u32,
+        question_count: u32,
+        timestamp: u64,
+    ) -> Self {
+        QuizAttempt {
+            quiz_address: quiz_address,
+            student_address: student_address,
+            answers: answers,
+            correct_count: correct_count,
+            question_count: question_count,
+            timestamp: timestamp,
+        }
+    }
+
+    // percentage of questions the student has answered correctly
+    pub fn score(&self) -> f64 {
+        if self.question_count == 0 {
+            return 0.0;
+        }
+        self.correct_count as f64 * 100.0 / self.question_count as f64
+    }
+}
+
+impl HolochainEntry for QuizAttempt {
+    fn entry_type() -> String {
+        String::from("quiz_attempt")
+    }
+}
+
+// generates the student's questions again and checks that the attempt was graded by them
+fn validate_attempt(attempt: &QuizAttempt) -> Result<(), String> {
+    let quiz: Quiz = hdk::utils::get_as_type(attempt.quiz_address.clone())
+        .map_err(|_| "Can't find the quiz of this attempt".to_owned())?;
+    let (correct_count, question_count) = quiz::handlers::count_correct_answers(
+        &attempt.quiz_address,
+        &quiz,
+        &attempt.student_address,
+        &attempt.answers,
+    )
+    .map_err(|_| "Can't grade this attempt".to_owned())?;
+    if correct_count != attempt.correct_count || question_count != attempt.question_count {
+        return Err("Attempt score doesn't match it's answers".to_owned());
+    }
+    let attempt_address = attempt
+        .address()
+        .map_err(|_| "Can't get address of the attempt".to_owned())?;
+    validate_single_attempt(
+        &attempt.quiz_address,
+        &attempt.student_address,
+        &attempt_address,
+    )
+}
+
+// every student can take a quiz once, so there should be no other attempt of the student linked to the quiz
+fn validate_single_attempt(
+    quiz_address: &Address,
+    student_address: &Address,
+    attempt_address: &Address,
+) -> Result<(), String> {
+    let student_tag = student_address.to_string();
+    let linked_attempts = hdk::get_links(
+        quiz_address,
+        LinkMatch::Exactly(QUIZ_TO_ATTEMPT_LINK),
+        LinkMatch::Exactly(&student_tag),
+    )
+    .map_err(|_| "Can't get attempts of the quiz".to_owned())?
+    .addresses();
+    if linked_attempts
+        .iter()
+        .any(|linked_attempt_address| linked_attempt_address != attempt_address)
+    {
+        return Err("Student has already taken this quiz".to_owned());
+    }
+    Ok(())
+}
+
+// checks that attempt is linked from it's quiz by it's student and tagged with the student address
+fn validate_attempt_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let attempt: QuizAttempt = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked attempt".to_owned())?;
+    if !sources.contains(&attempt.student_address) {
+        return Err("Only the student can link their attempt".to_owned());
+    }
+    if *link_data.link.base() != attempt.quiz_address
+        || *link_data.link.tag() != attempt.student_address.to_string()
+    {
+        return Err(
+            "Attempt should be linked from it's quiz and tagged with the student".to_owned(),
+        );
+    }
+    validate_single_attempt(
+        &attempt.quiz_address,
+        &attempt.student_address,
+        link_data.link.target(),
+    )
+}
+
+fn validate_attempt_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_attempt_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove { .. } => {
+            Err("Quiz attempts can't be unlinked".to_owned())
+        }
+    }
+}
+
+// Holochain entry definition for QuizAttempt
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: QuizAttempt::entry_type(),
+        description: "this is the definition of a student's answers to a quiz",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<QuizAttempt>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    if !validation_data.sources().contains(&entry.student_address) {
+                        return Err("Quiz attempt can only be created by the student it belongs to".to_owned());
+                    }
+                    validate_attempt(&entry)
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Quiz attempts can't be modified".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the quiz.
+            // Tag of this link is an address of the student so we can find attempt of a particular student
+            from!(
+                Quiz::entry_type(),
+                link_type: QUIZ_TO_ATTEMPT_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_attempt_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
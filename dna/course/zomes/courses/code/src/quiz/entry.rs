@@ -0,0 +1,159 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::course::anchor::CourseAnchor;
+use crate::question::entry::{Question, QuestionDifficulty};
+
+pub const COURSE_ANCHOR_TO_QUIZ_LINK: &str = "course_anchor->quiz";
+
+// Quiz that draws question_count questions out of the teacher's questions on the given topics.
+// Every student gets their own set of questions (see quiz::generator)
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Quiz {
+    pub title: String,
+    pub topics: Vec<String>,
+    // if it's None, questions of any difficulty are drawn
+    pub difficulty: Option<QuestionDifficulty>,
+    pub question_count: u32,
+    // questions that matched topics and difficulty when quiz was created.
+    // We store them so questions added to the bank later don't change quizzes that students already took
+    pub question_pool: Vec<Address>,
+    pub course_anchor_address: Address,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Quiz {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        title: String,
+        topics: Vec<String>,
+        difficulty: Option<QuestionDifficulty>,
+        question_count: u32,
+        question_pool: Vec<Address>,
+        course_anchor_address: Address,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Quiz {
+            title: title,
+            topics: topics,
+            difficulty: difficulty,
+            question_count: question_count,
+            question_pool: question_pool,
+            course_anchor_address: course_anchor_address,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Quiz {
+    fn entry_type() -> String {
+        String::from("quiz")
+    }
+}
+
+fn validate_quiz(quiz: &Quiz) -> Result<(), String> {
+    if quiz.topics.is_empty() {
+        return Err("Quiz should have at least one topic".to_owned());
+    }
+    if quiz.question_count == 0 {
+        return Err("Quiz should have at least one question".to_owned());
+    }
+    if quiz.question_pool.len() < quiz.question_count as usize {
+        return Err(format!(
+            "Quiz needs {} questions, but only {} questions match it's topics",
+            quiz.question_count,
+            quiz.question_pool.len()
+        ));
+    }
+    Ok(())
+}
+
+// quiz is created by the teacher of the course out of their own questions
+fn validate_author(quiz: &Quiz, sources: &[Address]) -> Result<(), String> {
+    if !sources.contains(&quiz.teacher_address) {
+        return Err("Quiz can only be created by the teacher it belongs to".to_owned());
+    }
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(quiz.course_anchor_address.clone())
+            .map_err(|_| "Can't find the course of this quiz".to_owned())?;
+    if quiz.teacher_address != course_anchor.teacher_address {
+        return Err("Only the teacher of the course can create it's quizzes".to_owned());
+    }
+    for question_address in quiz.question_pool.iter() {
+        let question: Question = hdk::utils::get_as_type(question_address.clone())
+            .map_err(|_| "Can't find a question of this quiz".to_owned())?;
+        if question.teacher_address != quiz.teacher_address {
+            return Err(
+                "Quiz can only draw questions from it's teacher's question bank".to_owned(),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn validate_quiz_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let quiz: Quiz = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked quiz".to_owned())?;
+    if !sources.contains(&quiz.teacher_address) {
+        return Err("Only the teacher of the course can link it's quizzes".to_owned());
+    }
+    if *link_data.link.base() != quiz.course_anchor_address {
+        return Err("Quiz can only be linked from it's course".to_owned());
+    }
+    Ok(())
+}
+
+fn validate_quiz_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_quiz_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_quiz_link(&link, &validation_data.sources()),
+    }
+}
+
+// Holochain entry definition for Quiz
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Quiz::entry_type(),
+        description: "this is the definition of a quiz that's generated out of the teacher's question bank",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Quiz>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_quiz(&entry)?;
+                    validate_author(&entry, &validation_data.sources())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Quizzes can't be modified, create a new one instead".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_QUIZ_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    validate_quiz_link_change(validation_data)
+                }
+            )
+        ]
+    )
+}
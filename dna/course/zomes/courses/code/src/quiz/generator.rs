@@ -0,0 +1,125 @@
+use hdk::holochain_persistence_api::cas::content::Address;
+
+// Quiz generation has to give the same questions for the same student and quiz on every node,
+// so it's graded by the same questions that student has answered.
+// That's why we don't use std hashers or rand here: they don't promise the same output between versions
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+// FNV-1a hash of the string
+fn hash(seed_source: &str) -> u64 {
+    seed_source.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+// hash of the student and quiz addresses
+pub fn get_seed(student_address: &Address, quiz_address: &Address) -> u64 {
+    hash(&format!("{}:{}", student_address, quiz_address))
+}
+
+// hash of the base address and the whole pool. Pool is sorted first,
+// so the seed only depends on which addresses are in it
+pub fn get_pool_seed(base_address: &Address, pool: &[Address]) -> u64 {
+    let mut sorted_pool: Vec<String> = pool.iter().map(|address| address.to_string()).collect();
+    sorted_pool.sort();
+    hash(&format!("{}:{}", base_address, sorted_pool.join(",")))
+}
+
+// SplitMix64 pseudo-random number generator
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+// draws count addresses out of the pool in a random order defined by the seed.
+// Pool is sorted first, so the order it was stored in doesn't matter
+pub fn draw(pool: &[Address], count: usize, seed: u64) -> Vec<Address> {
+    let mut shuffled_pool = pool.to_vec();
+    shuffled_pool.sort_by_key(|address| address.to_string());
+    let count = std::cmp::min(count, shuffled_pool.len());
+
+    // partial Fisher-Yates shuffle: only first count positions are needed.
+    // Modulo bias is negligible for pool sizes of a question bank
+    let mut generator = SplitMix64 { state: seed };
+    for index in 0..count {
+        let remaining = (shuffled_pool.len() - index) as u64;
+        let swap_index = index + (generator.next() % remaining) as usize;
+        shuffled_pool.swap(index, swap_index);
+    }
+    shuffled_pool.truncate(count);
+    shuffled_pool
+}
+
+// these values are pinned: if they change, questions of students who've already taken a quiz
+// are generated differently and their attempts stop validating
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses(values: &[&str]) -> Vec<Address> {
+        values.iter().map(|value| Address::from(*value)).collect()
+    }
+
+    #[test]
+    fn splitmix_matches_reference_output() {
+        let mut generator = SplitMix64 { state: 0 };
+        assert_eq!(generator.next(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(generator.next(), 0x6e78_9e6a_a1b9_65f4);
+    }
+
+    #[test]
+    fn seed_is_pinned() {
+        assert_eq!(
+            get_seed(&Address::from("student"), &Address::from("quiz")),
+            0x316a_c34b_b380_9a0b
+        );
+    }
+
+    #[test]
+    fn pool_seed_doesnt_depend_on_pool_order() {
+        let seed = get_pool_seed(&Address::from("assignment"), &addresses(&["b", "a", "c"]));
+        assert_eq!(seed, 0xd135_b4e8_ff3a_c83a);
+        assert_eq!(
+            get_pool_seed(&Address::from("assignment"), &addresses(&["c", "b", "a"])),
+            seed
+        );
+    }
+
+    #[test]
+    fn draw_is_pinned() {
+        let pool = addresses(&["q1", "q2", "q3", "q4", "q5"]);
+        assert_eq!(draw(&pool, 3, 0), addresses(&["q1", "q2", "q4"]));
+        assert_eq!(
+            draw(&pool, 5, 0x316a_c34b_b380_9a0b),
+            addresses(&["q1", "q2", "q5", "q4", "q3"])
+        );
+    }
+
+    #[test]
+    fn draw_doesnt_depend_on_pool_order() {
+        let mut pool = addresses(&["q1", "q2", "q3", "q4", "q5"]);
+        let drawn = draw(&pool, 3, 0);
+        pool.reverse();
+        assert_eq!(draw(&pool, 3, 0), drawn);
+    }
+
+    #[test]
+    fn draw_takes_at_most_the_whole_pool() {
+        let pool = addresses(&["q1", "q2", "q3", "q4", "q5"]);
+        assert_eq!(
+            draw(&pool, 10, 42),
+            addresses(&["q4", "q5", "q3", "q1", "q2"])
+        );
+    }
+}
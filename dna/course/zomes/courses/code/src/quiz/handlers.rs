@@ -0,0 +1,278 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::attempt::{QuizAttempt, QUIZ_TO_ATTEMPT_LINK};
+use super::entry::{Quiz, COURSE_ANCHOR_TO_QUIZ_LINK};
+use super::generator;
+use crate::course;
+use crate::question;
+use crate::question::entry::{Question, QuestionDifficulty};
+
+// generated question with the correct answer. This is what teacher sees in the preview
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct GeneratedQuestion {
+    pub question_address: Address,
+    pub question: Question,
+}
+
+// generated question without the correct answer. This is what student sees when taking the quiz
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct QuizQuestion {
+    pub question_address: Address,
+    pub text: String,
+    pub options: Vec<String>,
+}
+
+pub fn create(
+    course_anchor_address: Address,
+    title: String,
+    topics: Vec<String>,
+    difficulty: Option<QuestionDifficulty>,
+    question_count: u32,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    course::handlers::ensure_teacher(&course_anchor_address)?;
+
+    let question_pool =
+        question::handlers::get_matching_questions(&AGENT_ADDRESS, &topics, &difficulty)?;
+    if question_pool.len() < question_count as usize {
+        return Err(ZomeApiError::from(format!(
+            "Quiz needs {} questions, but your question bank only has {} questions on these topics",
+            question_count,
+            question_pool.len()
+        )));
+    }
+
+    create_with_pool(
+        course_anchor_address,
+        title,
+        topics,
+        difficulty,
+        question_count,
+        question_pool,
+        timestamp,
+    )
+}
+
+// creates a quiz that draws it's questions from the given pool instead of the teacher's question bank.
+// This is used when importing quizzes together with their questions
+pub fn create_with_pool(
+    course_anchor_address: Address,
+    title: String,
+    topics: Vec<String>,
+    difficulty: Option<QuestionDifficulty>,
+    question_count: u32,
+    question_pool: Vec<Address>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let quiz = Quiz::new(
+        title,
+        topics,
+        difficulty,
+        question_count,
+        question_pool,
+        course_anchor_address.clone(),
+        AGENT_ADDRESS.clone(),
+        timestamp,
+    );
+    let quiz_address = hdk::commit_entry(&quiz.entry())?;
+
+    hdk::link_entries(
+        &course_anchor_address,
+        &quiz_address,
+        COURSE_ANCHOR_TO_QUIZ_LINK,
+        "",
+    )?;
+
+    Ok(quiz_address)
+}
+
+pub fn get_quiz(quiz_address: Address) -> ZomeApiResult<Option<Quiz>> {
+    match hdk::get_entry(&quiz_address)? {
+        Some(entry) => Ok(Quiz::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+pub fn get_quizzes(course_anchor_address: &Address) -> ZomeApiResult<Vec<(Quiz, Address)>> {
+    let quiz_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_QUIZ_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut quizzes = Vec::new();
+    for quiz_address in quiz_addresses {
+        let quiz: Quiz = hdk::utils::get_as_type(quiz_address.clone())?;
+        quizzes.push((quiz, quiz_address));
+    }
+    Ok(quizzes)
+}
+
+// wrapper for the get_quizzes that only returns addresses of quizzes
+pub fn list_quizzes(course_anchor_address: Address) -> ZomeApiResult<Vec<Address>> {
+    let quizzes = get_quizzes(&course_anchor_address)?;
+    Ok(quizzes
+        .into_iter()
+        .map(|(_quiz, quiz_address)| quiz_address)
+        .collect())
+}
+
+// returns questions of the quiz for the student in the order they're asked.
+// The same student always gets the same questions, so attempts can be graded by anyone
+pub fn generate(
+    quiz_address: &Address,
+    quiz: &Quiz,
+    student_address: &Address,
+) -> ZomeApiResult<Vec<GeneratedQuestion>> {
+    let seed = generator::get_seed(student_address, quiz_address);
+    generator::draw(&quiz.question_pool, quiz.question_count as usize, seed)
+        .into_iter()
+        .map(|question_address| {
+            let question: Question = hdk::utils::get_as_type(question_address.clone())?;
+            Ok(GeneratedQuestion {
+                question_address: question_address,
+                question: question,
+            })
+        })
+        .collect()
+}
+
+// shows teacher the questions that the student gets in this quiz
+pub fn preview(
+    quiz_address: Address,
+    student_address: Address,
+) -> ZomeApiResult<Vec<GeneratedQuestion>> {
+    let quiz: Quiz = hdk::utils::get_as_type(quiz_address.clone())?;
+    course::handlers::ensure_teacher(&quiz.course_anchor_address)?;
+    generate(&quiz_address, &quiz, &student_address)
+}
+
+// returns questions of the quiz for the agent that calls this function.
+// NOTE: questions are public entries, so hiding correct answers here only keeps honest UIs honest
+pub fn get_my_questions(quiz_address: Address) -> ZomeApiResult<Vec<QuizQuestion>> {
+    let quiz: Quiz = hdk::utils::get_as_type(quiz_address.clone())?;
+    Ok(generate(&quiz_address, &quiz, &AGENT_ADDRESS)?
+        .into_iter()
+        .map(|generated_question| QuizQuestion {
+            question_address: generated_question.question_address,
+            text: generated_question.question.text,
+            options: generated_question.question.options,
+        })
+        .collect())
+}
+
+// returns (correct answers, number of questions) for the student's answers to the quiz
+pub fn count_correct_answers(
+    quiz_address: &Address,
+    quiz: &Quiz,
+    student_address: &Address,
+    answers: &[usize],
+) -> ZomeApiResult<(u32, u32)> {
+    let generated_questions = generate(quiz_address, quiz, student_address)?;
+    if answers.len() != generated_questions.len() {
+        return Err(ZomeApiError::from(format!(
+            "Expected an answer for each of {} questions, got {}",
+            generated_questions.len(),
+            answers.len()
+        )));
+    }
+    let correct_count = generated_questions
+        .iter()
+        .zip(answers.iter())
+        .filter(|(generated_question, answer)| {
+            generated_question.question.correct_option == **answer
+        })
+        .count();
+    Ok((correct_count as u32, generated_questions.len() as u32))
+}
+
+pub fn get_student_attempt(
+    quiz_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<QuizAttempt>> {
+    let attempt_addresses = hdk::get_links(
+        quiz_address,
+        LinkMatch::Exactly(QUIZ_TO_ATTEMPT_LINK),
+        // links to attempts are tagged with the address of the student that made them
+        LinkMatch::Exactly(&student_address.to_string()),
+    )?
+    .addresses();
+
+    // validation rejects a second attempt, but if one got through we take the earliest
+    // so that the student gets the same score on every node
+    let mut attempts = Vec::new();
+    for attempt_address in attempt_addresses {
+        let attempt: QuizAttempt = hdk::utils::get_as_type(attempt_address.clone())?;
+        attempts.push((attempt, attempt_address));
+    }
+    attempts
+        .sort_by_key(|(attempt, attempt_address)| (attempt.timestamp, attempt_address.to_string()));
+    Ok(attempts
+        .into_iter()
+        .next()
+        .map(|(attempt, _attempt_address)| attempt))
+}
+
+pub fn get_my_attempt(quiz_address: Address) -> ZomeApiResult<Option<QuizAttempt>> {
+    get_student_attempt(&quiz_address, &AGENT_ADDRESS)
+}
+
+// grades answers of the agent that calls this function. Every student can take a quiz once
+pub fn submit_attempt(
+    quiz_address: Address,
+    answers: Vec<usize>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let quiz: Quiz = hdk::utils::get_as_type(quiz_address.clone())?;
+    if !course::handlers::is_enrolled(&quiz.course_anchor_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "Only students enrolled in this course can take quizzes".to_owned(),
+        ));
+    }
+    if get_student_attempt(&quiz_address, &AGENT_ADDRESS)?.is_some() {
+        return Err(ZomeApiError::from(
+            "You've already taken this quiz".to_owned(),
+        ));
+    }
+
+    let (correct_count, question_count) =
+        count_correct_answers(&quiz_address, &quiz, &AGENT_ADDRESS, &answers)?;
+    let attempt = QuizAttempt::new(
+        quiz_address.clone(),
+        AGENT_ADDRESS.clone(),
+        answers,
+        correct_count,
+        question_count,
+        timestamp,
+    );
+    let attempt_address = hdk::commit_entry(&attempt.entry())?;
+
+    hdk::link_entries(
+        &quiz_address,
+        &attempt_address,
+        QUIZ_TO_ATTEMPT_LINK,
+        AGENT_ADDRESS.to_string().as_str(),
+    )?;
+
+    Ok(attempt_address)
+}
+
+// average score of the student over quizzes of the course they've taken
+pub fn get_student_score(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<f64>> {
+    let mut scores = Vec::new();
+    for (_quiz, quiz_address) in get_quizzes(course_anchor_address)? {
+        if let Some(attempt) = get_student_attempt(&quiz_address, student_address)? {
+            scores.push(attempt.score());
+        }
+    }
+    if scores.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(scores.iter().sum::<f64>() / scores.len() as f64))
+}
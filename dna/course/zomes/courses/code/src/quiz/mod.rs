@@ -0,0 +1,4 @@
+pub mod attempt;
+pub mod entry;
+pub mod generator;
+pub mod handlers;
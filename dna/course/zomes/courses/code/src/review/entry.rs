@@ -0,0 +1,157 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use crate::anchor_trait::AnchorTrait;
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+
+pub const COURSE_ANCHOR_TO_REVIEW_LINK: &str = "course_anchor->review";
+
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 5;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Review {
+    pub rating: u8,
+    pub text: String,
+    pub student_address: Address,
+    pub course_anchor_address: Address,
+    pub timestamp: u64,
+}
+
+impl Review {
+    pub fn new(
+        rating: u8,
+        text: String,
+        student_address: Address,
+        course_anchor_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Review {
+            rating: rating,
+            text: text,
+            student_address: student_address,
+            course_anchor_address: course_anchor_address,
+            timestamp: timestamp,
+        }
+    }
+}
+
+impl HolochainEntry for Review {
+    fn entry_type() -> String {
+        String::from("review")
+    }
+}
+
+fn validate_rating(rating: u8) -> Result<(), String> {
+    if rating < MIN_RATING || rating > MAX_RATING {
+        return Err(format!(
+            "Rating must be between {} and {}",
+            MIN_RATING, MAX_RATING
+        ));
+    }
+    Ok(())
+}
+
+// students can only review courses they're enrolled in
+fn validate_reviewer(review: &Review) -> Result<(), String> {
+    match course::handlers::is_enrolled(&review.course_anchor_address, &review.student_address) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err("Only students enrolled in this course can review it".to_owned()),
+        Err(_) => Err("Can't check if student is enrolled in this course".to_owned()),
+    }
+}
+
+// Review is linked from the course once, by the address of it's first version, and it's tagged
+// with the student who wrote it. Student can only link their own review and only one of them
+fn validate_review_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let review: Review = hdk::utils::get_as_type(link_data.link.target().clone())
+        .map_err(|_| "Can't find the linked review".to_owned())?;
+    if !sources.contains(&review.student_address) {
+        return Err("Only the student who wrote the review can link it".to_owned());
+    }
+    if *link_data.link.tag() != review.student_address.to_string()
+        || review.course_anchor_address != *link_data.link.base()
+    {
+        return Err(
+            "Review link should be tagged with it's student and come from it's course".to_owned(),
+        );
+    }
+
+    let linked_review_addresses = hdk::get_links(
+        link_data.link.base(),
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_REVIEW_LINK),
+        LinkMatch::Exactly(link_data.link.tag()),
+    )
+    .map_err(|_| "Can't check if student has already reviewed this course".to_owned())?
+    .addresses();
+    if linked_review_addresses
+        .iter()
+        .any(|review_address| review_address != link_data.link.target())
+    {
+        return Err("Student has already reviewed this course".to_owned());
+    }
+    Ok(())
+}
+
+// Holochain entry definition for Review
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Review::entry_type(),
+        description: "this is the definition of a student's review of a course",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Review>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_rating(entry.rating)?;
+                    // only the student themselves can write a review in their name
+                    if !validation_data.sources().contains(&entry.student_address) {
+                        return Err("Review can only be written by the student it belongs to".to_owned());
+                    }
+                    validate_reviewer(&entry)
+                },
+                EntryValidationData::Modify { new_entry, old_entry, validation_data, .. } => {
+                    validate_rating(new_entry.rating)?;
+                    if new_entry.student_address != old_entry.student_address
+                        || new_entry.course_anchor_address != old_entry.course_anchor_address
+                    {
+                        return Err("Can't move a review to another student or course".to_owned());
+                    }
+                    if !validation_data.sources().contains(&old_entry.student_address) {
+                        return Err("Review can only be edited by the student who wrote it".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the course that is being reviewed.
+            // Tag of this link is an address of the student who wrote the review
+            // so we can quickly find the review of a particular student
+            from!(
+                CourseAnchor::entry_type(),
+                link_type: COURSE_ANCHOR_TO_REVIEW_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    match validation_data {
+                        hdk::LinkValidationData::LinkAdd { link, validation_data } => {
+                            validate_review_link(&link, &validation_data.sources())
+                        },
+                        hdk::LinkValidationData::LinkRemove { .. } => {
+                            Ok(())
+                        }
+                    }
+                }
+            )
+        ]
+    )
+}
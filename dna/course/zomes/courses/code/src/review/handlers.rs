@@ -0,0 +1,138 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{Review, COURSE_ANCHOR_TO_REVIEW_LINK};
+use crate::course;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct CourseRating {
+    // average rating of the course. It's 0.0 if course doesn't have any reviews yet
+    pub average: f64,
+    pub count: usize,
+}
+
+// retrieves review that student at student_address wrote for this course (if there is one)
+// together with it's address
+fn get_student_review(
+    course_anchor_address: &Address,
+    student_address: &Address,
+) -> ZomeApiResult<Option<(Review, Address)>> {
+    let review_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_REVIEW_LINK),
+        // links to reviews are tagged with the address of the student that wrote them
+        LinkMatch::Exactly(&student_address.to_string()),
+    )?
+    .addresses();
+
+    match review_addresses.first() {
+        Some(review_address) => {
+            let review: Review = hdk::utils::get_as_type(review_address.clone())?;
+            Ok(Some((review, review_address.clone())))
+        }
+        None => Ok(None),
+    }
+}
+
+// creates a review for the course or replaces the previous one if this student already reviewed it
+pub fn review_course(
+    course_anchor_address: Address,
+    rating: u8,
+    text: String,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    if !course::handlers::is_enrolled(&course_anchor_address, &AGENT_ADDRESS)? {
+        return Err(ZomeApiError::from(
+            "Only students enrolled in this course can review it".to_owned(),
+        ));
+    }
+
+    let student_tag = AGENT_ADDRESS.to_string();
+    let new_review = Review::new(
+        rating,
+        text,
+        AGENT_ADDRESS.clone(),
+        course_anchor_address.clone(),
+        timestamp,
+    );
+
+    match get_student_review(&course_anchor_address, &AGENT_ADDRESS)? {
+        // review stays linked by the address of it's first version and get_entry follows updates,
+        // so we only have to update the latest version of it
+        Some((previous_review, review_address)) => {
+            hdk::update_entry(new_review.entry(), &previous_review.address()?)?;
+            Ok(review_address)
+        }
+        None => {
+            let new_review_address = hdk::commit_entry(&new_review.entry())?;
+            hdk::link_entries(
+                &course_anchor_address,
+                &new_review_address,
+                COURSE_ANCHOR_TO_REVIEW_LINK,
+                student_tag.as_str(),
+            )?;
+
+            Ok(new_review_address)
+        }
+    }
+}
+
+pub fn get_my_review(course_anchor_address: Address) -> ZomeApiResult<Option<Review>> {
+    let review_result = get_student_review(&course_anchor_address, &AGENT_ADDRESS)?;
+    Ok(review_result.map(|(review, _review_address)| review))
+}
+
+// returns a single page of course reviews. page_number starts from 0
+pub fn get_reviews(
+    course_anchor_address: Address,
+    page_number: usize,
+    page_size: usize,
+) -> ZomeApiResult<Vec<Review>> {
+    let options = GetLinksOptions {
+        pagination: Some(Pagination::Size(SizePagination {
+            page_number: page_number,
+            page_size: page_size,
+        })),
+        ..GetLinksOptions::default()
+    };
+    let review_addresses = hdk::get_links_with_options(
+        &course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_REVIEW_LINK),
+        LinkMatch::Any,
+        options,
+    )?
+    .addresses();
+
+    review_addresses
+        .into_iter()
+        .map(|review_address| hdk::utils::get_as_type(review_address))
+        .collect()
+}
+
+pub fn get_course_rating(course_anchor_address: &Address) -> ZomeApiResult<CourseRating> {
+    let review_addresses = hdk::get_links(
+        course_anchor_address,
+        LinkMatch::Exactly(COURSE_ANCHOR_TO_REVIEW_LINK),
+        LinkMatch::Any,
+    )?
+    .addresses();
+
+    let mut rating_sum: u64 = 0;
+    for review_address in review_addresses.iter() {
+        let review: Review = hdk::utils::get_as_type(review_address.clone())?;
+        rating_sum += review.rating as u64;
+    }
+
+    let count = review_addresses.len();
+    let average = if count == 0 {
+        0.0
+    } else {
+        rating_sum as f64 / count as f64
+    };
+
+    Ok(CourseRating {
+        average: average,
+        count: count,
+    })
+}
@@ -0,0 +1,158 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+pub const TEACHER_TO_RUBRIC_LINK: &str = "teacher->rubric";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RubricLevel {
+    pub description: String,
+    pub points: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RubricCriterion {
+    pub title: String,
+    pub levels: Vec<RubricLevel>,
+}
+
+impl RubricCriterion {
+    pub fn max_points(&self) -> u32 {
+        self.levels
+            .iter()
+            .map(|level| level.points)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+// Set of criteria that teacher grades assignments by. Rubrics can't be changed,
+// so grades that were given by a rubric always stay valid. To adjust a rubric, teacher clones it
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct Rubric {
+    pub title: String,
+    pub criteria: Vec<RubricCriterion>,
+    pub teacher_address: Address,
+    pub timestamp: u64,
+}
+
+impl Rubric {
+    pub fn new(
+        title: String,
+        criteria: Vec<RubricCriterion>,
+        teacher_address: Address,
+        timestamp: u64,
+    ) -> Self {
+        Rubric {
+            title: title,
+            criteria: criteria,
+            teacher_address: teacher_address,
+            timestamp: timestamp,
+        }
+    }
+
+    // the highest score that can be achieved with this rubric.
+    // Fails if it doesn't fit into u32
+    pub fn max_points(&self) -> Result<u32, String> {
+        let mut max_points: u32 = 0;
+        for criterion in self.criteria.iter() {
+            max_points = max_points
+                .checked_add(criterion.max_points())
+                .ok_or_else(|| "Total points of the rubric are too big".to_owned())?;
+        }
+        Ok(max_points)
+    }
+
+    // total points for the given level of every criterion.
+    // Fails if a level isn't selected for every criterion or doesn't exist
+    pub fn get_points(&self, selected_levels: &[usize]) -> Result<u32, String> {
+        if selected_levels.len() != self.criteria.len() {
+            return Err(format!(
+                "Expected a level for each of {} criteria, got {}",
+                self.criteria.len(),
+                selected_levels.len()
+            ));
+        }
+        let mut points: u32 = 0;
+        for (criterion, level_index) in self.criteria.iter().zip(selected_levels.iter()) {
+            match criterion.levels.get(*level_index) {
+                Some(level) => {
+                    points = points
+                        .checked_add(level.points)
+                        .ok_or_else(|| "Total points of the rubric are too big".to_owned())?;
+                }
+                None => {
+                    return Err(format!(
+                        "Criterion '{}' doesn't have level {}",
+                        criterion.title, level_index
+                    ));
+                }
+            }
+        }
+        Ok(points)
+    }
+}
+
+impl HolochainEntry for Rubric {
+    fn entry_type() -> String {
+        String::from("rubric")
+    }
+}
+
+fn validate_rubric(rubric: &Rubric) -> Result<(), String> {
+    if rubric.criteria.is_empty() {
+        return Err("Rubric should have at least one criterion".to_owned());
+    }
+    for criterion in rubric.criteria.iter() {
+        if criterion.levels.is_empty() {
+            return Err(format!(
+                "Criterion '{}' should have at least one level",
+                criterion.title
+            ));
+        }
+    }
+    rubric.max_points()?;
+    Ok(())
+}
+
+// Holochain entry definition for Rubric
+pub fn entry_def() -> ValidatingEntryType {
+    entry!(
+        name: Rubric::entry_type(),
+        description: "this is the definition of a rubric that teacher grades assignments by",
+        sharing: Sharing::Public,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | validation_data: hdk::EntryValidationData<Rubric>| {
+            match validation_data {
+                EntryValidationData::Create { entry, validation_data } => {
+                    validate_rubric(&entry)?;
+                    if !validation_data.sources().contains(&entry.teacher_address) {
+                        return Err("Rubric can only be created by the teacher it belongs to".to_owned());
+                    }
+                    Ok(())
+                },
+                EntryValidationData::Modify { .. } => {
+                    Err("Rubrics can't be modified, clone them instead".to_owned())
+                },
+                EntryValidationData::Delete { .. } => {
+                    Ok(())
+                }
+            }
+        },
+        links: [
+            // link from the teacher who owns the rubric
+            // This is for teacher to keep track of all of their rubrics
+            from!(
+                "%agent_id", // this is a special string that would automatically expand to the hdk::AGENT_ADDRESS
+                link_type: TEACHER_TO_RUBRIC_LINK,
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | _validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
+            )
+        ]
+    )
+}
@@ -0,0 +1,42 @@
+use hdk::prelude::*;
+use hdk::AGENT_ADDRESS;
+use holochain_entry_utils::HolochainEntry;
+
+use super::entry::{Rubric, RubricCriterion, TEACHER_TO_RUBRIC_LINK};
+
+pub fn create(
+    title: String,
+    criteria: Vec<RubricCriterion>,
+    timestamp: u64,
+) -> ZomeApiResult<Address> {
+    let rubric = Rubric::new(title, criteria, AGENT_ADDRESS.clone(), timestamp);
+    let rubric_address = hdk::commit_entry(&rubric.entry())?;
+
+    hdk::link_entries(&AGENT_ADDRESS, &rubric_address, TEACHER_TO_RUBRIC_LINK, "")?;
+
+    Ok(rubric_address)
+}
+
+// creates a copy of any rubric owned by the agent that calls this function.
+// This is how teachers adjust their rubrics or reuse rubrics of other teachers
+pub fn clone(rubric_address: Address, title: String, timestamp: u64) -> ZomeApiResult<Address> {
+    let rubric: Rubric = hdk::utils::get_as_type(rubric_address)?;
+    create(title, rubric.criteria, timestamp)
+}
+
+pub fn get_rubric(rubric_address: Address) -> ZomeApiResult<Option<Rubric>> {
+    match hdk::get_entry(&rubric_address)? {
+        Some(entry) => Ok(Rubric::from_entry(&entry)),
+        None => Ok(None),
+    }
+}
+
+pub fn get_my_rubrics() -> ZomeApiResult<Vec<Address>> {
+    let links = hdk::get_links(
+        &AGENT_ADDRESS,
+        LinkMatch::Exactly(TEACHER_TO_RUBRIC_LINK),
+        LinkMatch::Any,
+    )?;
+
+    Ok(links.addresses())
+}
@@ -1,9 +1,11 @@
+use hdk::holochain_core_types::link::link_data::LinkData;
 use hdk::prelude::*;
 use holochain_entry_utils::HolochainEntry;
 
 use super::entry::Section;
 use crate::anchor_trait::AnchorTrait;
 use crate::content::entry::Content;
+use crate::course::anchor::CourseAnchor;
 
 pub const SECTION_TO_CONTENT_LINK: &str = "section_anchor->content";
 
@@ -36,6 +38,34 @@ impl SectionAnchor {
     }
 }
 
+// content doesn't know which course it's a part of, so it belongs to the course
+// of the section it's linked from. Only the teacher of that course can add or remove it
+fn validate_content_link(link_data: &LinkData, sources: &[Address]) -> Result<(), String> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(link_data.link.base().clone())
+        .map_err(|_| "Can't find the section of this content".to_owned())?;
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(section_anchor.course_anchor_address)
+        .map_err(|_| "Can't find the course of this content".to_owned())?;
+    if !sources.contains(&course_anchor.teacher_address) {
+        return Err(
+            "Only the teacher of the course can change contents of it's sections".to_owned(),
+        );
+    }
+    Ok(())
+}
+
+fn validate_content_link_change(validation_data: hdk::LinkValidationData) -> Result<(), String> {
+    match validation_data {
+        hdk::LinkValidationData::LinkAdd {
+            link,
+            validation_data,
+        } => validate_content_link(&link, &validation_data.sources()),
+        hdk::LinkValidationData::LinkRemove {
+            link,
+            validation_data,
+        } => validate_content_link(&link, &validation_data.sources()),
+    }
+}
+
 pub fn section_anchor_def() -> ValidatingEntryType {
     entry!(
         name: SectionAnchor::entry_type(),
@@ -76,8 +106,8 @@ pub fn section_anchor_def() -> ValidatingEntryType {
                 validation_package:||{
                     hdk::ValidationPackageDefinition::Entry
                 },
-                validation:|_validation_data: hdk::LinkValidationData|{
-                    Ok(())
+                validation:|validation_data: hdk::LinkValidationData|{
+                    validate_content_link_change(validation_data)
                 }
             )        ]
     )
@@ -6,11 +6,30 @@ use hdk::{
 };
 use holochain_entry_utils::HolochainEntry;
 
+// When section becomes available to students. Teacher always sees all sections
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SectionRelease {
+    Immediately,
+    // section is released for everyone at this moment
+    At { release_at: u64 },
+    // section is released for every student separately, offset seconds after they've enrolled
+    AfterEnrollment { offset: u64 },
+}
+
+impl Default for SectionRelease {
+    fn default() -> Self {
+        SectionRelease::Immediately
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
 pub struct Section {
     pub title: String,
     pub timestamp: u64,
     pub anchor_address: Address,
+    // NOTE: default value allows to read Section entries committed before this field was introduced
+    #[serde(default)]
+    pub release: SectionRelease,
 }
 
 impl Section {
@@ -19,6 +38,7 @@ impl Section {
             title: title,
             timestamp: timestamp,
             anchor_address: anchor_address,
+            release: SectionRelease::default(),
         }
     }
 }
@@ -1,12 +1,17 @@
 use hdk::error::{ZomeApiError, ZomeApiResult};
 use hdk::holochain_persistence_api::cas::content::Address;
+use hdk::AGENT_ADDRESS;
 use holochain_entry_utils::HolochainEntry;
 
 use super::anchor::SectionAnchor;
-use super::entry::Section;
+use super::entry::{Section, SectionRelease};
 use crate::anchor_trait::AnchorTrait;
 use crate::course;
+use crate::course::anchor::CourseAnchor;
+use crate::course::handlers::SectionTreeNode;
 use crate::helper;
+use crate::signal;
+use crate::signal::handlers::LeapSignal;
 
 pub fn create(
     title: String,
@@ -37,6 +42,15 @@ pub fn create(
 
             // add section into the course
             course::handlers::add_section(&course_anchor_address, &section_anchor_address)?;
+
+            signal::handlers::notify_course_students(
+                course_anchor_address,
+                &LeapSignal::SectionCreated {
+                    course_anchor_address: course_anchor_address.clone(),
+                    section_anchor_address: section_anchor_address.clone(),
+                },
+            )?;
+
             // SectionAnchor serves as this section's ID so we return it
             Ok(section_anchor_address)
         }
@@ -56,8 +70,41 @@ pub fn get_latest_section(
     helper::get_latest_data_entry::<Section>(section_anchor_address, &SectionAnchor::link_type())
 }
 
-// wrapper for the get_latest_course that only returns Section entry
-// and disregards it's address
+// moment when section becomes available to a student who enrolled at enrollment_timestamp.
+// It's None if section is released relative to enrollment and student hasn't enrolled
+pub fn get_release_time(section: &Section, enrollment_timestamp: Option<u64>) -> Option<u64> {
+    match section.release {
+        SectionRelease::Immediately => Some(0),
+        SectionRelease::At { release_at } => Some(release_at),
+        SectionRelease::AfterEnrollment { offset } => enrollment_timestamp
+            .map(|enrollment_timestamp| enrollment_timestamp.saturating_add(offset)),
+    }
+}
+
+// checks if section is available to the agent that calls this function at the given moment.
+// Teacher of the course can always see all of it's sections
+pub fn is_released(
+    section: &Section,
+    course_anchor_address: &Address,
+    timestamp: u64,
+) -> ZomeApiResult<bool> {
+    let course_anchor: CourseAnchor = hdk::utils::get_as_type(course_anchor_address.clone())?;
+    if course_anchor.teacher_address == *AGENT_ADDRESS {
+        return Ok(true);
+    }
+
+    let enrollment_timestamp =
+        course::handlers::get_enrollment_timestamp(course_anchor_address, &AGENT_ADDRESS)?;
+    match get_release_time(section, enrollment_timestamp) {
+        Some(release_time) => Ok(release_time <= timestamp),
+        None => Ok(false),
+    }
+}
+
+// wrapper for the get_latest_section that only returns Section entry
+// and disregards it's address.
+// NOTE: sections that aren't released yet are returned too, since their titles and release times
+// are visible in the course tree. Use get_section to know if section is locked for the caller
 pub fn get_latest_section_entry(section_anchor_address: Address) -> ZomeApiResult<Option<Section>> {
     let latest_section_result = get_latest_section(&section_anchor_address)?;
     match latest_section_result {
@@ -68,33 +115,81 @@ pub fn get_latest_section_entry(section_anchor_address: Address) -> ZomeApiResul
     }
 }
 
+// returns section as seen by the agent that calls this function at the given moment:
+// locked sections come without their contents
+pub fn get_section(
+    section_anchor_address: Address,
+    timestamp: u64,
+) -> ZomeApiResult<Option<SectionTreeNode>> {
+    let section = match get_latest_section(&section_anchor_address)? {
+        Some((section, _section_address)) => section,
+        None => return Ok(None),
+    };
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    let course_anchor: CourseAnchor =
+        hdk::utils::get_as_type(section_anchor.course_anchor_address.clone())?;
+    let enrollment_timestamp = course::handlers::get_enrollment_timestamp(
+        &section_anchor.course_anchor_address,
+        &AGENT_ADDRESS,
+    )?;
+    let section_node = course::handlers::build_section_node(
+        section_anchor_address,
+        section,
+        course_anchor.teacher_address == *AGENT_ADDRESS,
+        enrollment_timestamp,
+        timestamp,
+    )?;
+    Ok(Some(section_node))
+}
+
+// commits updated section, relinks SectionAnchor to it's new version and lets students know
+fn commit_update(
+    section: Section,
+    previous_section_address: &Address,
+    section_anchor_address: &Address,
+) -> ZomeApiResult<Address> {
+    // commit this update to the DHT.
+    let new_section_address = hdk::update_entry(section.entry(), previous_section_address)?;
+
+    // remove link to previous version of section
+    hdk::remove_link(
+        section_anchor_address,
+        previous_section_address,
+        SectionAnchor::link_type(),
+        "".to_owned(),
+    )?;
+
+    // create link to new version of section
+    hdk::link_entries(
+        section_anchor_address,
+        &new_section_address,
+        SectionAnchor::link_type(),
+        "".to_owned(),
+    )?;
+
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    signal::handlers::notify_course_students(
+        &section_anchor.course_anchor_address,
+        &LeapSignal::SectionUpdated {
+            course_anchor_address: section_anchor.course_anchor_address.clone(),
+            section_anchor_address: section_anchor_address.clone(),
+        },
+    )?;
+
+    Ok(section_anchor_address.clone())
+}
+
 pub fn update(title: String, section_anchor_address: &Address) -> ZomeApiResult<Address> {
     let latest_section_result = get_latest_section(section_anchor_address)?;
     match latest_section_result {
         Some((mut previous_section, previous_section_address)) => {
             // update the section
             previous_section.title = title;
-            // commit this update to the DHT.
-            let new_section_address =
-                hdk::update_entry(previous_section.entry(), &previous_section_address)?;
-
-            // remove link to previous version of section
-            hdk::remove_link(
-                section_anchor_address,
+            commit_update(
+                previous_section,
                 &previous_section_address,
-                SectionAnchor::link_type(),
-                "".to_owned(),
-            )?;
-
-            // create link to new version of section
-            hdk::link_entries(
                 section_anchor_address,
-                &new_section_address,
-                SectionAnchor::link_type(),
-                "".to_owned(),
-            )?;
-
-            Ok(section_anchor_address.clone())
+            )
         }
         None => {
             return Err(ZomeApiError::from(
@@ -104,6 +199,31 @@ pub fn update(title: String, section_anchor_address: &Address) -> ZomeApiResult<
     }
 }
 
+pub fn set_release(
+    section_anchor_address: &Address,
+    release: SectionRelease,
+) -> ZomeApiResult<Address> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    course::handlers::ensure_teacher(&section_anchor.course_anchor_address)?;
+
+    let latest_section_result = get_latest_section(section_anchor_address)?;
+    match latest_section_result {
+        Some((mut previous_section, previous_section_address)) => {
+            previous_section.release = release;
+            commit_update(
+                previous_section,
+                &previous_section_address,
+                section_anchor_address,
+            )
+        }
+        None => {
+            return Err(ZomeApiError::from(
+                "Can't change release of a deleted section".to_owned(),
+            ));
+        }
+    }
+}
+
 pub fn delete(section_anchor_address: Address) -> ZomeApiResult<Address> {
     let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
 
@@ -121,5 +241,14 @@ pub fn delete(section_anchor_address: Address) -> ZomeApiResult<Address> {
     // 1) without it, we won't be able to reach the section because everywhere we link to section we only use anchor address
     // 2) we'll avoid polluting DHT by new deletion metadata
     let result = hdk::remove_entry(&section_anchor_address)?;
+
+    signal::handlers::notify_course_students(
+        &section_anchor.course_anchor_address,
+        &LeapSignal::SectionDeleted {
+            course_anchor_address: section_anchor.course_anchor_address.clone(),
+            section_anchor_address: section_anchor_address,
+        },
+    )?;
+
     Ok(result)
-}
\ No newline at end of file
+}
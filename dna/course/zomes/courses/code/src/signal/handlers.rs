@@ -0,0 +1,280 @@
+use hdk::holochain_core_types::{entry::Entry, time::Timeout};
+use hdk::holochain_json_api::{error::JsonError, json::JsonString};
+use hdk::holochain_persistence_api::cas::content::Address;
+use hdk::{
+    error::{ZomeApiError, ZomeApiResult},
+    AGENT_ADDRESS,
+};
+use holochain_entry_utils::HolochainEntry;
+use std::convert::TryFrom;
+
+use super::outbox::{SignalDelivery, SignalOutbox};
+use crate::course;
+use crate::course::anchor::CourseAnchor;
+use crate::enrollment_request::entry::EnrollmentRequest;
+use crate::helper;
+use crate::section::anchor::SectionAnchor;
+
+// name of every signal that this zome emits. UI can tell signals apart by the LeapSignal variant
+pub const LEAP_SIGNAL_NAME: &str = "leap_signal";
+
+// how long we wait for other agents to acknowledge a direct message, in milliseconds.
+// It's kept short because agents that are offline would never respond
+const DIRECT_MESSAGE_TIMEOUT_MS: usize = 1000;
+// hdk::send blocks until the message is acknowledged or times out, so we only message this many agents
+// per zome call. This keeps a zome call from blocking for longer than a few seconds
+// when many students are offline. Other agents are messaged in batches by send_pending
+const MAX_DIRECT_MESSAGES: usize = 5;
+
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub enum LeapSignal {
+    CourseCreated {
+        course_anchor_address: Address,
+    },
+    CourseUpdated {
+        course_anchor_address: Address,
+    },
+    CourseDeleted {
+        course_anchor_address: Address,
+    },
+    SectionCreated {
+        course_anchor_address: Address,
+        section_anchor_address: Address,
+    },
+    SectionUpdated {
+        course_anchor_address: Address,
+        section_anchor_address: Address,
+    },
+    SectionDeleted {
+        course_anchor_address: Address,
+        section_anchor_address: Address,
+    },
+    ContentCreated {
+        section_anchor_address: Address,
+        content_address: Address,
+    },
+    ContentUpdated {
+        section_anchor_address: Address,
+        // address of the previous version of content
+        content_address: Address,
+        updated_content_address: Address,
+    },
+    ContentDeleted {
+        section_anchor_address: Address,
+        content_address: Address,
+    },
+    StudentEnrolled {
+        course_anchor_address: Address,
+        student_address: Address,
+    },
+    // teacher can promote students from the waitlist to the seat that was freed
+    StudentUnenrolled {
+        course_anchor_address: Address,
+        student_address: Address,
+    },
+    AnnouncementPosted {
+        course_anchor_address: Address,
+        announcement_address: Address,
+    },
+    EnrollmentRequested {
+        course_anchor_address: Address,
+        enrollment_request_address: Address,
+    },
+    EnrollmentRejected {
+        course_anchor_address: Address,
+        enrollment_request_address: Address,
+    },
+}
+
+// emits signal to the UI of the agent that calls this function
+pub fn emit(signal: &LeapSignal) -> ZomeApiResult<()> {
+    hdk::emit_signal(LEAP_SIGNAL_NAME, JsonString::from(signal))
+}
+
+fn send_batch(recipients: &[Address], signal: &LeapSignal) {
+    let payload = String::from(JsonString::from(signal));
+    for agent_address in recipients {
+        // NOTE: we're ignoring the result here because failing to reach one agent
+        // shouldn't fail the change that was already committed
+        let _send_result = hdk::send(
+            agent_address.clone(),
+            payload.clone(),
+            Timeout::new(DIRECT_MESSAGE_TIMEOUT_MS),
+        );
+    }
+}
+
+// sends signal to other agents as a direct message.
+// This is a best effort delivery: agents that are offline won't get the message and we don't retry.
+// Only MAX_DIRECT_MESSAGES agents are messaged right away, the rest are kept in a private outbox
+pub fn send_to_agents(agent_addresses: Vec<Address>, signal: &LeapSignal) -> ZomeApiResult<()> {
+    let mut recipients: Vec<Address> = agent_addresses
+        .into_iter()
+        // there's no need to message ourselves since we've already emitted this signal locally
+        .filter(|agent_address| *agent_address != *AGENT_ADDRESS)
+        .collect();
+    if recipients.len() > MAX_DIRECT_MESSAGES {
+        let remaining_recipients = recipients.split_off(MAX_DIRECT_MESSAGES);
+        let position = helper::query_my_entries::<SignalOutbox>()?.len();
+        let outbox = SignalOutbox::new(signal.clone(), remaining_recipients, position);
+        hdk::commit_entry(&outbox.entry())?;
+    }
+    send_batch(&recipients, signal);
+    Ok(())
+}
+
+// sends signals from the outbox to the next MAX_DIRECT_MESSAGES agents, oldest signals first.
+// Returns how many agents are still waiting for their signals, so UI calls it again until it's 0
+pub fn send_pending() -> ZomeApiResult<usize> {
+    let mut outboxes = helper::query_my_entries::<SignalOutbox>()?;
+    outboxes.sort_by_key(|(outbox, _outbox_address)| outbox.position);
+    let deliveries = helper::query_my_entries::<SignalDelivery>()?;
+
+    let mut batch_budget = MAX_DIRECT_MESSAGES;
+    let mut pending_count = 0;
+    for (outbox, outbox_address) in outboxes {
+        let sent_count = deliveries
+            .iter()
+            .filter(|(delivery, _address)| delivery.outbox_address == outbox_address)
+            .map(|(delivery, _address)| delivery.sent_count)
+            .max()
+            .unwrap_or(0);
+        let unsent_recipients = outbox.recipients.get(sent_count..).unwrap_or(&[]);
+        let batch_size = std::cmp::min(batch_budget, unsent_recipients.len());
+        if batch_size > 0 {
+            send_batch(&unsent_recipients[..batch_size], &outbox.signal);
+            let delivery = SignalDelivery::new(outbox_address, sent_count + batch_size);
+            hdk::commit_entry(&delivery.entry())?;
+            batch_budget -= batch_size;
+        }
+        pending_count += unsent_recipients.len() - batch_size;
+    }
+    Ok(pending_count)
+}
+
+// emits signal locally and sends it to all students enrolled in the course
+pub fn notify_course_students(
+    course_anchor_address: &Address,
+    signal: &LeapSignal,
+) -> ZomeApiResult<()> {
+    emit(signal)?;
+    let students = course::handlers::get_students(course_anchor_address.clone())?;
+    send_to_agents(students, signal)
+}
+
+// same as notify_course_students but for the course that section at section_anchor_address belongs to
+pub fn notify_section_students(
+    section_anchor_address: &Address,
+    signal: &LeapSignal,
+) -> ZomeApiResult<()> {
+    let section_anchor: SectionAnchor = hdk::utils::get_as_type(section_anchor_address.clone())?;
+    notify_course_students(&section_anchor.course_anchor_address, signal)
+}
+
+// reads entry at exactly this address, even if it was updated or deleted since.
+// Signals can be about deleted courses and sections, so we can't use get_as_type here
+fn get_initial_as_type<T: TryFrom<JsonString>>(address: &Address) -> ZomeApiResult<T> {
+    match hdk::get_entry_initial(address)? {
+        Some(Entry::App(_entry_type, entry_value)) => T::try_from(entry_value)
+            .map_err(|_| ZomeApiError::from("Entry has an unexpected type".to_owned())),
+        _ => Err(ZomeApiError::from("Can't find the entry".to_owned())),
+    }
+}
+
+fn get_course_teacher(course_anchor_address: &Address) -> ZomeApiResult<Address> {
+    let course_anchor: CourseAnchor = get_initial_as_type(course_anchor_address)?;
+    Ok(course_anchor.teacher_address)
+}
+
+fn get_section_teacher(section_anchor_address: &Address) -> ZomeApiResult<Address> {
+    let section_anchor: SectionAnchor = get_initial_as_type(section_anchor_address)?;
+    get_course_teacher(&section_anchor.course_anchor_address)
+}
+
+// agents who are allowed to send this signal: the teacher of the course for course changes
+// and the student for their own enrollment
+fn get_allowed_senders(signal: &LeapSignal) -> ZomeApiResult<Vec<Address>> {
+    match signal {
+        LeapSignal::CourseCreated {
+            course_anchor_address,
+        }
+        | LeapSignal::CourseUpdated {
+            course_anchor_address,
+        }
+        | LeapSignal::CourseDeleted {
+            course_anchor_address,
+        }
+        | LeapSignal::SectionCreated {
+            course_anchor_address,
+            ..
+        }
+        | LeapSignal::SectionUpdated {
+            course_anchor_address,
+            ..
+        }
+        | LeapSignal::SectionDeleted {
+            course_anchor_address,
+            ..
+        }
+        | LeapSignal::AnnouncementPosted {
+            course_anchor_address,
+            ..
+        }
+        | LeapSignal::EnrollmentRejected {
+            course_anchor_address,
+            ..
+        } => Ok(vec![get_course_teacher(course_anchor_address)?]),
+        LeapSignal::ContentCreated {
+            section_anchor_address,
+            ..
+        }
+        | LeapSignal::ContentUpdated {
+            section_anchor_address,
+            ..
+        }
+        | LeapSignal::ContentDeleted {
+            section_anchor_address,
+            ..
+        } => Ok(vec![get_section_teacher(section_anchor_address)?]),
+        // student enrolls themselves, or teacher admits them from the waitlist or a request
+        LeapSignal::StudentEnrolled {
+            course_anchor_address,
+            student_address,
+        } => Ok(vec![
+            student_address.clone(),
+            get_course_teacher(course_anchor_address)?,
+        ]),
+        LeapSignal::StudentUnenrolled {
+            student_address, ..
+        } => Ok(vec![student_address.clone()]),
+        LeapSignal::EnrollmentRequested {
+            enrollment_request_address,
+            ..
+        } => {
+            let enrollment_request: EnrollmentRequest =
+                get_initial_as_type(enrollment_request_address)?;
+            Ok(vec![enrollment_request.student_address])
+        }
+    }
+}
+
+// handles direct messages from other agents by passing signals they contain to our UI.
+// Signals are only passed on if they come from an agent who could've caused them
+pub fn receive(from: Address, payload: String) -> String {
+    let signal = match LeapSignal::try_from(JsonString::from_json(&payload)) {
+        Ok(signal) => signal,
+        Err(error) => return format!("error: unknown message: {}", error),
+    };
+    match get_allowed_senders(&signal) {
+        Ok(allowed_senders) => {
+            if !allowed_senders.contains(&from) {
+                return "error: this agent can't send this signal".to_owned();
+            }
+        }
+        Err(error) => return format!("error: {}", error),
+    }
+    match emit(&signal) {
+        Ok(()) => "ok".to_owned(),
+        Err(error) => format!("error: {}", error),
+    }
+}
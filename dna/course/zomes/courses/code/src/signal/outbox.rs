@@ -0,0 +1,85 @@
+use hdk::prelude::*;
+use holochain_entry_utils::HolochainEntry;
+
+use super::handlers::LeapSignal;
+
+// Private entry with a signal that still has to be sent to some of it's recipients.
+// Only a few direct messages are sent during the change itself (see signal::handlers::send_to_agents),
+// the rest are sent in batches by signal::handlers::send_pending
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct SignalOutbox {
+    pub signal: LeapSignal,
+    pub recipients: Vec<Address>,
+    // number of outboxes committed before this one. It keeps outboxes with the same signal
+    // and recipients apart and lets us send them in the order they were committed
+    pub position: usize,
+}
+
+impl SignalOutbox {
+    pub fn new(signal: LeapSignal, recipients: Vec<Address>, position: usize) -> Self {
+        SignalOutbox {
+            signal: signal,
+            recipients: recipients,
+            position: position,
+        }
+    }
+}
+
+impl HolochainEntry for SignalOutbox {
+    fn entry_type() -> String {
+        String::from("signal_outbox")
+    }
+}
+
+// Private entry that records how many recipients of the outbox were already messaged.
+// Source chain can't be changed, so a new one is committed after every batch and the biggest count wins
+#[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+pub struct SignalDelivery {
+    pub outbox_address: Address,
+    pub sent_count: usize,
+}
+
+impl SignalDelivery {
+    pub fn new(outbox_address: Address, sent_count: usize) -> Self {
+        SignalDelivery {
+            outbox_address: outbox_address,
+            sent_count: sent_count,
+        }
+    }
+}
+
+impl HolochainEntry for SignalDelivery {
+    fn entry_type() -> String {
+        String::from("signal_delivery")
+    }
+}
+
+// Holochain entry definition for SignalOutbox
+pub fn outbox_entry_def() -> ValidatingEntryType {
+    entry!(
+        name: SignalOutbox::entry_type(),
+        description: "this is the definition of a private list of agents that a signal still has to be sent to",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | _validation_data: hdk::EntryValidationData<SignalOutbox>| {
+            Ok(())
+        }
+    )
+}
+
+// Holochain entry definition for SignalDelivery
+pub fn delivery_entry_def() -> ValidatingEntryType {
+    entry!(
+        name: SignalDelivery::entry_type(),
+        description: "this is the definition of a private record of how much of a signal outbox was sent",
+        sharing: Sharing::Private,
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+        validation: | _validation_data: hdk::EntryValidationData<SignalDelivery>| {
+            Ok(())
+        }
+    )
+}